@@ -1,10 +1,15 @@
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, Vec};
 
 use crate::errors::AjoError;
 use crate::events;
 use crate::pausable;
 use crate::storage;
-use crate::types::{Group, GroupMetadata, GroupStatus};
+use crate::types::{
+    Bid, DefaultRecord, ExitRequest, Group, GroupEscrow, GroupMetadata, GroupParams,
+    GroupSettings, GroupStatus, GroupTemplate, JoinPolicy, JoinRequest, KeeperReport,
+    MemberPosition, PayoutRecord, PayoutStrategy, Proposal, ProposalAction, ProposalVote,
+    RefundRequest, RoundRecord, VoteChoice,
+};
 use crate::utils;
 
 /// The main Ajo contract
@@ -109,11 +114,61 @@ impl AjoContract {
         pausable::unpause(&env)
     }
 
+    /// Migrate storage written by earlier contract versions to the [`DataKey`] layout.
+    ///
+    /// Older deployments stored entries under symbol tuples such as `("GROUP", id)`.
+    /// After upgrading the Wasm, the admin calls this function to move the admin,
+    /// group counter and pause flag, then every entry of each group in the range
+    /// `start_group_id..start_group_id + max_groups`, to the new keys. Large
+    /// deployments can migrate in batches by passing the returned ID as the next
+    /// `start_group_id` until it exceeds the group counter.
+    ///
+    /// Groups that were already migrated (or never existed) are skipped, so the
+    /// function is safe to call more than once.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `start_group_id` - First group ID to migrate
+    /// * `max_groups` - Maximum number of group IDs to process in this call
+    ///
+    /// # Returns
+    /// The next group ID to migrate
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the contract has no admin or the caller is not the admin
+    ///
+    /// [`DataKey`]: crate::storage::DataKey
+    pub fn migrate_storage(
+        env: Env,
+        start_group_id: u64,
+        max_groups: u32,
+    ) -> Result<u64, AjoError> {
+        crate::migration::migrate_instance(&env);
+
+        let admin = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
+        admin.require_auth();
+
+        let last_group_id = storage::get_group_counter(&env);
+        let mut group_id = start_group_id.max(1);
+        let end = group_id
+            .saturating_add(max_groups as u64)
+            .min(last_group_id.saturating_add(1));
+
+        while group_id < end {
+            crate::migration::migrate_group(&env, group_id);
+            group_id += 1;
+        }
+
+        Ok(group_id)
+    }
+
     /// Create a new Ajo group.
     ///
     /// Initializes a new rotating savings group with the specified parameters.
     /// The creator becomes the first member and the contract validates all parameters
-    /// before storage. A unique group ID is assigned and returned.
+    /// before storage. A unique group ID is assigned and returned. The group
+    /// starts out `Forming` and begins its first cycle once it fills up or
+    /// the creator calls `start_group`.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
@@ -124,6 +179,8 @@ impl AjoContract {
     /// * `max_members` - Maximum number of members allowed in the group (must be >= 2 and <= 100)
     /// * `grace_period` - Grace period duration in seconds after cycle ends (default: 86400 = 24 hours)
     /// * `penalty_rate` - Penalty rate as percentage for late contributions (0-100, default: 5)
+    /// * `payout_strategy` - How the payout order is determined (see [`PayoutStrategy`])
+    /// * `settings` - Optional settings such as the join policy (see [`GroupSettings`])
     ///
     /// # Returns
     /// The unique group ID assigned to the new group
@@ -136,6 +193,12 @@ impl AjoContract {
     /// * `MaxMembersAboveLimit` - If max_members > 100
     /// * `InvalidGracePeriod` - If grace_period > 7 days
     /// * `InvalidPenaltyRate` - If penalty_rate > 100
+    /// * `MaxMembersBelowMinimum` - If max_members < settings.min_members
+    /// * `ContributionAmountNegative` - If settings.collateral_amount < 0
+    /// * `ContributionAmountNegative` - If settings.keeper_fee < 0
+    /// * `InvalidGroupState` - If the governance quorum or threshold is above 100
+    ///   or the voting period is longer than 30 days
+    /// * `InsufficientBalance` - If the creator can't cover the collateral
     pub fn create_group(
         env: Env,
         creator: Address,
@@ -145,10 +208,20 @@ impl AjoContract {
         max_members: u32,
         grace_period: u64,
         penalty_rate: u32,
+        payout_strategy: PayoutStrategy,
+        settings: GroupSettings,
     ) -> Result<u64, AjoError> {
-        // Validate parameters
-        utils::validate_group_params(contribution_amount, cycle_duration, max_members)?;
-        utils::validate_penalty_params(grace_period, penalty_rate)?;
+        let params = GroupParams {
+            token_address,
+            contribution_amount,
+            cycle_duration,
+            max_members,
+            grace_period,
+            penalty_rate,
+            payout_strategy,
+            settings,
+        };
+        validate_params(&params)?;
 
         // Check if paused
         pausable::ensure_not_paused(&env)?;
@@ -156,42 +229,163 @@ impl AjoContract {
         // Require authentication
         creator.require_auth();
 
-        // Generate new group ID
-        let group_id = storage::get_next_group_id(&env);
+        let group_id = open_group(&env, &creator, params)?;
 
-        // Initialize members list with creator
-        let mut members = Vec::new(&env);
-        members.push_back(creator.clone());
+        Ok(group_id)
+    }
 
-        // Get current timestamp
-        let now = utils::get_current_timestamp(&env);
+    /// Save a reusable set of group parameters.
+    ///
+    /// The parameters are validated with the same rules as `create_group`,
+    /// so groups created from the template can't fail validation later.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `owner` - Address saving the template
+    /// * `params` - Token, amounts, timings, payout strategy and settings for new groups
+    ///
+    /// # Returns
+    /// The unique template ID
+    ///
+    /// # Errors
+    /// * Any error `create_group` returns for invalid parameters
+    pub fn create_template(
+        env: Env,
+        owner: Address,
+        params: GroupParams,
+    ) -> Result<u64, AjoError> {
+        validate_params(&params)?;
 
-        // Create group
-        let group = Group {
-            id: group_id,
-            creator: creator.clone(),
-            token_address,
-            contribution_amount,
-            cycle_duration,
-            max_members,
-            members,
-            current_cycle: 1,
-            payout_index: 0,
-            created_at: now,
-            cycle_start_time: now,
-            is_complete: false,
-            grace_period,
-            penalty_rate,
-            state: crate::types::GroupState::Active,
+        pausable::ensure_not_paused(&env)?;
+        owner.require_auth();
+
+        let template = GroupTemplate {
+            id: storage::get_next_template_id(&env),
+            owner: owner.clone(),
+            params,
+            created_at: utils::get_current_timestamp(&env),
         };
+        storage::store_template(&env, &template);
 
-        // Store group
-        storage::store_group(&env, group_id, &group);
+        events::emit_template_created(&env, template.id, &owner);
 
-        // Emit event
-        events::emit_group_created(&env, group_id, &creator, contribution_amount, max_members);
+        Ok(template.id)
+    }
 
-        Ok(group_id)
+    /// Get a saved group template.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `template_id` - The template ID
+    ///
+    /// # Returns
+    /// The template
+    ///
+    /// # Errors
+    /// * `RecordNotFound` - If the template does not exist
+    pub fn get_template(env: Env, template_id: u64) -> Result<GroupTemplate, AjoError> {
+        storage::get_template(&env, template_id).ok_or(AjoError::RecordNotFound)
+    }
+
+    /// Create a new group from a saved template.
+    ///
+    /// Works like `create_group` with the template's parameters; anyone may
+    /// use any template. The creator becomes the first member and locks any
+    /// collateral the template requires.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `creator` - Address of the group creator (automatically becomes first member)
+    /// * `template_id` - The template to copy
+    ///
+    /// # Returns
+    /// The unique group ID assigned to the new group
+    ///
+    /// # Errors
+    /// * `RecordNotFound` - If the template does not exist
+    /// * `InsufficientBalance` - If the creator can't cover the collateral
+    pub fn create_group_from_template(
+        env: Env,
+        creator: Address,
+        template_id: u64,
+    ) -> Result<u64, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
+        let template =
+            storage::get_template(&env, template_id).ok_or(AjoError::RecordNotFound)?;
+
+        open_group(&env, &creator, template.params)
+    }
+
+    /// Start a new group with the same parameters as a completed one.
+    ///
+    /// Only the completed group's creator can clone it, and they become the
+    /// first member of the new group. With `invite_previous_members`, every
+    /// other member of the completed group is invited to the new one, so
+    /// they can join even if the group is invite-only.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `creator` - Creator of the completed group
+    /// * `group_id` - The completed group to copy
+    /// * `invite_previous_members` - Whether to invite the completed group's members
+    ///
+    /// # Returns
+    /// The unique group ID assigned to the new group
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller did not create the group
+    /// * `InvalidGroupState` - If the group has not completed
+    /// * `InsufficientBalance` - If the creator can't cover the collateral
+    pub fn clone_group(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        invite_previous_members: bool,
+    ) -> Result<u64, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+
+        if !group.is_complete {
+            return Err(AjoError::InvalidGroupState);
+        }
+
+        let params = GroupParams {
+            token_address: group.token_address.clone(),
+            contribution_amount: group.contribution_amount,
+            cycle_duration: group.cycle_duration,
+            max_members: group.max_members,
+            grace_period: group.grace_period,
+            penalty_rate: group.penalty_rate,
+            payout_strategy: group.payout_strategy,
+            settings: group.settings.clone(),
+        };
+        let new_group_id = open_group(&env, &creator, params)?;
+
+        if invite_previous_members {
+            let mut invites = Vec::new(&env);
+            for member in group.members.iter() {
+                if member != creator {
+                    invites.push_back(member.clone());
+                    events::emit_member_invited(&env, new_group_id, &member);
+                }
+            }
+            if !invites.is_empty() {
+                storage::store_invites(&env, new_group_id, &invites);
+            }
+        }
+
+        events::emit_group_cloned(&env, new_group_id, group_id);
+
+        Ok(new_group_id)
     }
 
     /// Get group information.
@@ -233,9 +427,19 @@ impl AjoContract {
 
     /// Join an existing group.
     ///
-    /// Adds a new member to an active group if space is available.
-    /// The member's authentication is required. The member cannot join if they
-    /// are already a member, the group is full, or the group has completed all cycles.
+    /// Adds a new member to a forming group if space is available.
+    /// The new member is queued at the end of the payout order, and the group
+    /// starts automatically once it has `max_members` slots. The member's authentication is required. The member cannot join if
+    /// the group is full, or the group has already started.
+    /// A member who joins again takes another slot, with its own payout
+    /// position, as long as they hold fewer than `max_slots_per_member`;
+    /// their contribution grows by `contribution_amount` for every slot.
+    /// [`JoinPolicy::InviteOnly`] and [`JoinPolicy::ApprovalRequired`] groups
+    /// only admit invited addresses; others must use `request_join`. Existing
+    /// members don't need another invite to take more slots.
+    /// Groups with a `collateral_amount` take the collateral, per slot, from
+    /// the member on joining. Joining closes any pending join request, whose
+    /// collateral counts towards the new slot.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
@@ -248,8 +452,13 @@ impl AjoContract {
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
     /// * `MaxMembersExceeded` - If the group has reached max members
-    /// * `AlreadyMember` - If the address is already a member
+    /// * `AlreadyMember` - If the address already holds `max_slots_per_member` slots
     /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `InvalidGroupState` - If the group has already started
+    /// * `Unauthorized` - If the group is restricted and the address wasn't invited
+    /// * `AlreadyContributed` - If a member taking another slot has prepaid cycles
+    /// * `InsufficientBalance` - If the member can't cover the collateral
     pub fn join_group(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
         // Check if paused
         pausable::ensure_not_paused(&env)?;
@@ -260,1063 +469,4219 @@ impl AjoContract {
         // Get group
         let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
 
-        // Cache member count for comparisons
-        let member_count = group.members.len() as u32;
-        let max_members = group.max_members;
+        // Restricted groups only admit invited addresses directly
+        if group.settings.join_policy != JoinPolicy::Open
+            && !utils::is_member(&group.members, &member)
+        {
+            let mut invites = storage::get_invites(&env, group_id);
+            let index = invites
+                .first_index_of(&member)
+                .ok_or(AjoError::Unauthorized)?;
+            invites.remove(index);
+            storage::store_invites(&env, group_id, &invites);
+        }
 
-        // Check if group is complete
-        if group.is_complete {
-            return Err(AjoError::GroupComplete);
+        add_member(&env, &mut group, &member)?;
+
+        // Joining closes any pending request; collateral locked with it
+        // counts towards the new slot
+        let mut requests = storage::get_join_requests(&env, group_id);
+        if let Some(index) = utils::find_join_request(&requests, &member) {
+            requests.remove(index);
+            storage::store_join_requests(&env, group_id, &requests);
         }
 
-        // Check if group is cancelled
-        if group.state == crate::types::GroupState::Cancelled {
-            return Err(AjoError::GroupCancelled);
+        lock_collateral(&env, &group, &member)
+    }
+
+    /// Start a forming group before it fills up.
+    ///
+    /// Begins the first cycle now and locks membership. Groups also start
+    /// automatically when they reach `max_members`.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `creator` - Address of the group creator (must authenticate)
+    /// * `group_id` - The group to start
+    ///
+    /// # Returns
+    /// `Ok(())` on successful start
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `InvalidGroupState` - If the group isn't forming, or has fewer than
+    ///   `min_members` members
+    pub fn start_group(env: Env, creator: Address, group_id: u64) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
         }
 
-        // Check if group is cancelled
-        if group.state == crate::types::GroupState::Cancelled {
-            return Err(AjoError::GroupCancelled);
+        if group.state != crate::types::GroupState::Forming {
+            return Err(AjoError::InvalidGroupState);
+        }
+
+        if group.members.len() < utils::min_members(&group) {
+            return Err(AjoError::InvalidGroupState);
+        }
+
+        activate_group(&env, &mut group);
+        storage::store_group(&env, group_id, &group);
+
+        Ok(())
+    }
+
+    /// Invite an address to join a group.
+    ///
+    /// Invited addresses can join [`JoinPolicy::InviteOnly`] and
+    /// [`JoinPolicy::ApprovalRequired`] groups directly with `join_group`.
+    /// The invite is used up when they join.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `creator` - Address of the group creator (must authenticate)
+    /// * `group_id` - The group
+    /// * `invitee` - The address to invite
+    ///
+    /// # Returns
+    /// `Ok(())` on successful invite
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `AlreadyMember` - If the address is already a member
+    pub fn invite_member(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        invitee: Address,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
         }
 
-        // Check if group is cancelled
         if group.state == crate::types::GroupState::Cancelled {
             return Err(AjoError::GroupCancelled);
         }
 
-        // Check if already a member
-        if utils::is_member(&group.members, &member) {
-            return Err(AjoError::AlreadyMember);
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
         }
 
-        // Check if group is full
-        if member_count >= max_members {
-            return Err(AjoError::MaxMembersExceeded);
+        if utils::is_member(&group.members, &invitee) {
+            return Err(AjoError::AlreadyMember);
         }
 
-        // Add member
-        group.members.push_back(member.clone());
-
-        // Update storage
-        storage::store_group(&env, group_id, &group);
+        let mut invites = storage::get_invites(&env, group_id);
+        if !invites.contains(&invitee) {
+            invites.push_back(invitee.clone());
+            storage::store_invites(&env, group_id, &invites);
+        }
 
-        // Emit event
-        events::emit_member_joined(&env, group_id, &member);
+        events::emit_member_invited(&env, group_id, &invitee);
 
         Ok(())
     }
 
-    /// Check if an address is a member of a group.
-    ///
-    /// Returns whether the provided address is currently a member of the specified group.
+    /// Get the addresses invited to join a group that haven't joined yet.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The group to check
-    /// * `address` - The address to check
+    /// * `group_id` - The group
     ///
     /// # Returns
-    /// `true` if the address is a member, `false` otherwise
+    /// The outstanding invites
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    pub fn is_member(env: Env, group_id: u64, address: Address) -> Result<bool, AjoError> {
-        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        Ok(utils::is_member(&group.members, &address))
+    pub fn get_invites(env: Env, group_id: u64) -> Result<Vec<Address>, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_invites(&env, group_id))
     }
 
-    /// Contribute to the current cycle.
-    ///
-    /// Records a member's contribution for the current cycle and transfers tokens from
-    /// the member to the contract. Each member can contribute once per cycle.
-    /// Authentication is required.
+    /// Request to join a group that requires creator approval.
     ///
-    /// The function transfers the contribution amount from the member's token balance
-    /// to the contract. Late contributions (after cycle ends but within grace period)
-    /// incur penalties. Contributions after grace period are rejected.
+    /// The request stays pending until the creator approves or rejects it;
+    /// requesting again refreshes the request's timestamp. Any collateral is
+    /// locked with the request, since the applicant isn't present when the
+    /// creator approves it, and released again if the request is rejected.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `member` - Address making the contribution (must authenticate)
-    /// * `group_id` - The group to contribute to
+    /// * `member` - Address requesting to join (must authenticate)
+    /// * `group_id` - The group to join
     ///
     /// # Returns
-    /// `Ok(())` on successful contribution and token transfer
+    /// `Ok(())` on successful request
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    /// * `NotMember` - If the address is not a member
-    /// * `AlreadyContributed` - If already contributed this cycle
+    /// * `InvalidGroupState` - If the group doesn't use [`JoinPolicy::ApprovalRequired`]
+    ///   or has already started
+    /// * `GroupCancelled` - If the group has been cancelled
     /// * `GroupComplete` - If the group has completed all cycles
-    /// * `GracePeriodExpired` - If contribution is too late (after grace period)
-    /// * `InsufficientBalance` - If member doesn't have enough tokens
-    /// * `TransferFailed` - If the token transfer fails
-    pub fn contribute(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
-        // Check if paused
+    /// * `AlreadyMember` - If the address is already a member
+    /// * `MaxMembersExceeded` - If the group has reached max members
+    /// * `InsufficientBalance` - If the applicant can't cover the collateral
+    pub fn request_join(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
         pausable::ensure_not_paused(&env)?;
-
-        // Require authentication
         member.require_auth();
 
-        // Get group (single fetch)
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
 
-        // Cache frequently accessed values
-        let group_id_cached = group.id;
-        let current_cycle = group.current_cycle;
-        let contribution_amount = group.contribution_amount;
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
 
-        // Check if group is complete
         if group.is_complete {
             return Err(AjoError::GroupComplete);
         }
 
-        // Check if group is cancelled
-        if group.state == crate::types::GroupState::Cancelled {
-            return Err(AjoError::GroupCancelled);
+        if group.settings.join_policy != JoinPolicy::ApprovalRequired
+            || group.state != crate::types::GroupState::Forming
+        {
+            return Err(AjoError::InvalidGroupState);
         }
 
-        // Check if member
-        if !utils::is_member(&group.members, &member) {
-            return Err(AjoError::NotMember);
+        if utils::is_member(&group.members, &member) {
+            return Err(AjoError::AlreadyMember);
         }
 
-        // Check if already contributed
-        if storage::has_contributed(&env, group_id_cached, current_cycle, &member) {
-            return Err(AjoError::AlreadyContributed);
+        if group.members.len() >= group.max_members {
+            return Err(AjoError::MaxMembersExceeded);
         }
 
-        // Get contract address for token transfer
-        let contract_address = env.current_contract_address();
+        let request = JoinRequest {
+            group_id,
+            member: member.clone(),
+            requested_at: utils::get_current_timestamp(&env),
+        };
+        let mut requests = storage::get_join_requests(&env, group_id);
+        match utils::find_join_request(&requests, &member) {
+            Some(index) => requests.set(index, request),
+            None => requests.push_back(request),
+        }
+        storage::store_join_requests(&env, group_id, &requests);
 
-        // Check member balance before transfer
-        crate::token::check_balance(&env, &group.token_address, &member, contribution_amount)?;
+        lock_collateral(&env, &group, &member)?;
 
-        // Transfer tokens from member to contract
-        crate::token::transfer_token(
-            &env,
-            &group.token_address,
-            &member,
-            &contract_address,
-            contribution_amount,
-        )?;
+        events::emit_join_requested(&env, group_id, &member);
 
-        // Record contribution
-        storage::store_contribution(&env, group_id_cached, current_cycle, &member, true);
+        Ok(())
+    }
 
-        // Emit event
-        events::emit_contribution_made(
-            &env,
-            group_id_cached,
-            &member,
-            current_cycle,
-            contribution_amount,
-        );
+    /// Approve a pending join request, adding the address to the group.
+    ///
+    /// If the applicant has joined through an invite in the meantime, the
+    /// request is just closed and no extra slot is added.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `creator` - Address of the group creator (must authenticate)
+    /// * `group_id` - The group
+    /// * `member` - The address whose request is approved
+    ///
+    /// # Returns
+    /// `Ok(())` on successful join
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `RecordNotFound` - If the address has no pending request
+    /// * `MaxMembersExceeded` - If the group has reached max members
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupCancelled` - If the group has been cancelled
+    pub fn approve_join(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
 
-        Ok(())
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+
+        let mut requests = storage::get_join_requests(&env, group_id);
+        let index =
+            utils::find_join_request(&requests, &member).ok_or(AjoError::RecordNotFound)?;
+        requests.remove(index);
+        storage::store_join_requests(&env, group_id, &requests);
+
+        // An applicant who has since joined doesn't get another slot; the
+        // request is just closed
+        if utils::is_member(&group.members, &member) {
+            return release_request_collateral(&env, &group, &member);
+        }
+
+        add_member(&env, &mut group, &member)
     }
 
-    /// Get contribution status for all members in a specific cycle.
+    /// Reject a pending join request.
     ///
-    /// Returns an ordered list of all members paired with their contribution status
-    /// for the specified cycle. Member order matches the group's member list order.
+    /// Collateral locked with the request is released to the applicant's
+    /// claimable balance.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The group to check
-    /// * `cycle_number` - The cycle to check (typically use current_cycle from group)
+    /// * `creator` - Address of the group creator (must authenticate)
+    /// * `group_id` - The group
+    /// * `member` - The address whose request is rejected
     ///
     /// # Returns
-    /// Vector of (Address, bool) tuples where `true` indicates the member has contributed
+    /// `Ok(())` on successful rejection
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    pub fn get_contribution_status(
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `RecordNotFound` - If the address has no pending request
+    pub fn reject_join(
         env: Env,
+        creator: Address,
         group_id: u64,
-        cycle_number: u32,
-    ) -> Result<Vec<(Address, bool)>, AjoError> {
+        member: Address,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        Ok(storage::get_cycle_contributions(
-            &env,
-            group_id,
-            cycle_number,
-            &group.members,
-        ))
+
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+
+        let mut requests = storage::get_join_requests(&env, group_id);
+        let index =
+            utils::find_join_request(&requests, &member).ok_or(AjoError::RecordNotFound)?;
+        requests.remove(index);
+        storage::store_join_requests(&env, group_id, &requests);
+
+        release_request_collateral(&env, &group, &member)?;
+
+        events::emit_join_rejected(&env, group_id, &member);
+
+        Ok(())
     }
 
-    /// Execute payout for the current cycle.
+    /// Get the pending requests to join a group.
     ///
-    /// This is the core function that rotates payouts through group members.
-    /// It verifies that all members have contributed, calculates the total payout
-    /// (including any penalties collected), transfers tokens from the contract to
-    /// the recipient, and advances the cycle. When all members have received their
-    /// payout, the group is marked complete.
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
     ///
-    /// Payout can only be executed after the grace period expires to ensure all
-    /// late contributions are collected.
+    /// # Returns
+    /// The pending `JoinRequest`s in the order they were made
     ///
-    /// Process:
-    /// 1. Verifies all members have contributed in the current cycle
-    /// 2. Ensures grace period has expired
-    /// 3. Calculates total payout (contribution_amount × member_count + penalties)
-    /// 4. Verifies contract has sufficient token balance
-    /// 5. Transfers tokens from contract to recipient
-    /// 6. Records payout to the current recipient
-    /// 7. Emits payout event with penalty bonus
-    /// 8. Advances to next cycle (or marks complete if done)
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_join_requests(env: Env, group_id: u64) -> Result<Vec<JoinRequest>, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_join_requests(&env, group_id))
+    }
+
+    /// Leave a group before its rotation starts.
+    ///
+    /// Members can leave freely while the group is `Forming`. No contributions
+    /// have been paid in yet; any collateral and prepaid cycles are released
+    /// to the member's claimable balance. Once the group has started, members must use
+    /// `request_exit` instead.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The group to execute payout for
+    /// * `member` - Address of the member leaving (must authenticate)
+    /// * `group_id` - The group to leave
     ///
     /// # Returns
-    /// `Ok(())` on successful payout execution and token transfer
+    /// `Ok(())` on successful exit
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    /// * `IncompleteContributions` - If not all members have contributed
-    /// * `GroupComplete` - If the group has already completed all payouts
-    /// * `NoMembers` - If the group has no members (should never happen)
-    /// * `OutsideCycleWindow` - If grace period has not expired yet
-    /// * `InsufficientContractBalance` - If contract doesn't have enough tokens
-    /// * `TransferFailed` - If the token transfer fails
-    pub fn execute_payout(env: Env, group_id: u64) -> Result<(), AjoError> {
-        // Check if paused
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `NotMember` - If the caller is not a member
+    /// * `Unauthorized` - If the caller is the group creator
+    /// * `InvalidGroupState` - If the group has already started
+    pub fn leave_group(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
         pausable::ensure_not_paused(&env)?;
+        member.require_auth();
 
-        // Get group (single fetch)
         let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
 
-        // Check if group is cancelled
         if group.state == crate::types::GroupState::Cancelled {
             return Err(AjoError::GroupCancelled);
         }
 
-        // Check if group is complete
-        if group.is_complete {
-            return Err(AjoError::GroupComplete);
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
         }
 
-        // Cache frequently accessed values
-        let group_id_cached = group.id;
-        let current_cycle = group.current_cycle;
-        let member_count = group.members.len();
-
-        // Check if all members have contributed
-        if !utils::all_members_contributed(&env, &group) {
-            return Err(AjoError::IncompleteContributions);
+        if group.creator == member {
+            return Err(AjoError::Unauthorized);
         }
 
-        // Ensure grace period has expired before executing payout
-        let current_time = utils::get_current_timestamp(&env);
-        let grace_end = utils::get_grace_period_end(&group);
-        if current_time < grace_end {
-            // Still within grace period - delay payout
-            return Err(AjoError::OutsideCycleWindow);
+        if group.state != crate::types::GroupState::Forming {
+            return Err(AjoError::InvalidGroupState);
         }
 
-        // Get payout recipient
-        let payout_recipient = group
-            .members
-            .get(group.payout_index)
-            .ok_or(AjoError::NoMembers)?;
+        let released = release_collateral(&env, group_id, &member)?
+            + release_prepayment(&env, &group, &member)?;
 
-        // Calculate payout amounts: base payout + collected penalties for this cycle
-        let base_payout = group.contribution_amount * (member_count as i128);
-        let penalty_bonus = storage::get_cycle_penalty_pool(&env, group_id_cached, current_cycle);
-        let payout_amount = base_payout + penalty_bonus;
+        utils::remove_member(&mut group, &member);
+        storage::remove_exit_request(&env, group_id, &member);
+        storage::remove_auto_contribute(&env, group_id, &member);
+        storage::remove_share_weight(&env, group_id, &member);
+        storage::remove_member_position(&env, group_id, &member);
+        storage::store_group(&env, group_id, &group);
 
-        // Get contract address for token transfer
-        let contract_address = env.current_contract_address();
+        events::emit_member_left(&env, group_id, &member, released, 0);
 
-        // Verify contract has sufficient balance
-        crate::token::check_contract_balance(
-            &env,
-            &group.token_address,
-            &contract_address,
-            payout_amount,
-        )?;
+        Ok(())
+    }
 
-        // Transfer tokens from contract to recipient
-        crate::token::transfer_token(
-            &env,
-            &group.token_address,
-            &contract_address,
-            &payout_recipient,
-            payout_amount,
-        )?;
+    /// Request to exit a group whose rotation has started.
+    ///
+    /// The request stays pending until the creator approves or rejects it;
+    /// requesting again refreshes the request's timestamp.
+    /// Members who have already received a payout still owe contributions for
+    /// the remaining cycles and cannot exit this way.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `member` - Address of the member requesting to exit (must authenticate)
+    /// * `group_id` - The group to exit
+    ///
+    /// # Returns
+    /// `Ok(())` on successful request
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all payouts
+    /// * `InvalidGroupState` - If the group hasn't started; use `leave_group`
+    /// * `NotMember` - If the caller is not a member
+    /// * `Unauthorized` - If the caller is the group creator
+    /// * `AlreadyReceivedPayout` - If the caller has already been paid out
+    pub fn request_exit(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        member.require_auth();
 
-        // Mark payout as received
-        storage::mark_payout_received(&env, group_id_cached, &payout_recipient);
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
 
-        // Emit payout event with penalty information
-        if penalty_bonus > 0 {
-            events::emit_penalty_distributed(
-                &env,
-                group_id,
-                &payout_recipient,
-                group.current_cycle,
-                base_payout,
-                penalty_bonus,
-            );
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
         }
 
-        events::emit_payout_executed(
-            &env,
-            group_id_cached,
-            &payout_recipient,
-            current_cycle,
-            payout_amount,
-        );
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
 
-        // Advance payout index
-        group.payout_index += 1;
+        if group.state == crate::types::GroupState::Forming {
+            return Err(AjoError::InvalidGroupState);
+        }
 
-        // Check if all members have received payout
-        if group.payout_index >= member_count as u32 {
-            // All members have received payout - mark complete
-            group.is_complete = true;
-            events::emit_group_completed(&env, group_id_cached);
-        } else {
-            // Advance to next cycle
-            group.current_cycle += 1;
-            group.cycle_start_time = utils::get_current_timestamp(&env);
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
         }
 
-        // Update storage (single write)
-        storage::store_group(&env, group_id, &group);
+        if group.creator == member {
+            return Err(AjoError::Unauthorized);
+        }
+
+        if storage::has_received_payout(&env, group_id, &member) {
+            return Err(AjoError::AlreadyReceivedPayout);
+        }
+
+        let request = ExitRequest {
+            group_id,
+            member: member.clone(),
+            requested_at: utils::get_current_timestamp(&env),
+        };
+        storage::store_exit_request(&env, group_id, &member, &request);
+
+        events::emit_exit_requested(&env, group_id, &member);
 
         Ok(())
     }
 
-    /// Check if a group has completed all cycles.
+    /// Approve a member's pending exit request.
     ///
-    /// Returns whether the group has completed its full rotation,
-    /// meaning all members have received at least one payout.
+    /// The member is removed from the group and its payout order, and the
+    /// remaining rotation shrinks by one cycle for every slot they held. Their contribution to the
+    /// current cycle hasn't been paid out yet, so it is credited back to
+    /// their claimable balance along with any remaining collateral and
+    /// prepaid cycles.
+    /// Contributions to earlier cycles were already paid out to other members
+    /// and are forfeited.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The group to check
+    /// * `creator` - Address of the group creator (must authenticate)
+    /// * `group_id` - The group
+    /// * `member` - The member whose exit is approved
     ///
     /// # Returns
-    /// `true` if the group has completed all payouts, `false` otherwise
+    /// `Ok(())` on successful exit
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    pub fn is_complete(env: Env, group_id: u64) -> Result<bool, AjoError> {
-        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        Ok(group.is_complete)
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all payouts
+    /// * `RecordNotFound` - If the member has no pending exit request
+    /// * `NotMember` - If the address is no longer a member
+    /// * `AlreadyReceivedPayout` - If the member has been paid out since requesting
+    pub fn approve_exit(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+
+        if storage::get_exit_request(&env, group_id, &member).is_none() {
+            return Err(AjoError::RecordNotFound);
+        }
+
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+
+        if storage::has_received_payout(&env, group_id, &member) {
+            return Err(AjoError::AlreadyReceivedPayout);
+        }
+
+        remove_started_member(&env, &mut group, &member)
     }
 
-    /// Get comprehensive group status.
-    ///
-    /// Returns a detailed snapshot of the group's current state, including cycle
-    /// information, contribution status, payout progress, and timing data.
-    /// This is the primary function for checking a group's overall progress.
-    ///
-    /// Returns information about:
-    /// - Current cycle number and progress
-    /// - Next recipient for payout
-    /// - Members who have contributed and those who are pending
-    /// - Cycle timing (start time, end time, whether cycle is active)
-    /// - Whether the group is complete
+    /// Reject a member's pending exit request.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The unique group identifier
+    /// * `creator` - Address of the group creator (must authenticate)
+    /// * `group_id` - The group
+    /// * `member` - The member whose exit is rejected
     ///
     /// # Returns
-    /// A `GroupStatus` struct containing comprehensive group state information
+    /// `Ok(())` on successful rejection
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    pub fn get_group_status(env: Env, group_id: u64) -> Result<GroupStatus, AjoError> {
-        // Get the group data (single fetch)
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `RecordNotFound` - If the member has no pending exit request
+    pub fn reject_exit(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
 
-        // Cache frequently accessed values
-        let current_time = utils::get_current_timestamp(&env);
-        let member_count = group.members.len();
-        let group_id_cached = group.id;
-        let current_cycle = group.current_cycle;
-
-        // Calculate cycle timing
-        let cycle_end_time = group.cycle_start_time + group.cycle_duration;
-        let grace_period_end_time = utils::get_grace_period_end(&group);
-        let is_cycle_active = current_time < cycle_end_time;
-        let is_in_grace_period = utils::is_within_grace_period(&group, current_time);
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
 
-        // Get penalty pool for current cycle
-        let cycle_penalty_pool = storage::get_cycle_penalty_pool(&env, group_id, group.current_cycle);
+        if storage::get_exit_request(&env, group_id, &member).is_none() {
+            return Err(AjoError::RecordNotFound);
+        }
 
-        // Build pending_contributors list
-        let mut contributions_received: u32 = 0;
-        let mut pending_contributors = Vec::new(&env);
+        storage::remove_exit_request(&env, group_id, &member);
 
-        // Single pass through members to check contributions
-        for member in group.members.iter() {
-            if storage::has_contributed(&env, group_id_cached, current_cycle, &member) {
-                contributions_received += 1;
-            } else {
-                pending_contributors.push_back(member);
-            }
-        }
+        events::emit_exit_rejected(&env, group_id, &member);
 
-        // Determine next recipient
-        let (has_next_recipient, next_recipient) = if group.is_complete {
-            // Use placeholder (creator) when complete
-            (false, group.creator.clone())
-        } else {
-            // Get the member at payout_index
-            let recipient = group
-                .members
-                .get(group.payout_index)
-                .unwrap_or_else(|| group.creator.clone());
-            (true, recipient)
-        };
+        Ok(())
+    }
 
-        // Build and return status
-        Ok(GroupStatus {
-            group_id: group.id,
-            current_cycle: group.current_cycle,
-            has_next_recipient,
-            next_recipient,
-            contributions_received,
-            total_members: group.members.len() as u32,
-            pending_contributors,
-            is_complete: group.is_complete,
-            is_cycle_active,
-            cycle_start_time: group.cycle_start_time,
-            cycle_end_time,
-            current_time,
-            cycle_penalty_pool,
-            is_in_grace_period,
-            grace_period_end_time,
-        })
+    /// Get a member's pending exit request.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The member's pending `ExitRequest`
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `RecordNotFound` - If the member has no pending exit request
+    pub fn get_exit_request(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<ExitRequest, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        storage::get_exit_request(&env, group_id, &member).ok_or(AjoError::RecordNotFound)
     }
 
-    /// Set or update metadata for an Ajo group.
+    /// Transfer a membership slot to another address.
     ///
-    /// Only the group creator can set or update metadata.
+    /// The new holder takes over every slot `from` holds, keeping each slot's
+    /// position in the payout order, along
+    /// with its contribution history, payout status and penalty record. A
+    /// member who has already been paid hands over the obligation to keep
+    /// contributing; a member still awaiting payout hands over their turn.
+    /// In restricted groups `to` must have been invited. Both parties must
+    /// authenticate. `to` locks their own collateral, and `from`'s collateral
+    /// and prepaid cycles are released to their claimable balance.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The unique group identifier
-    /// * `name` - The name of the group
-    /// * `description` - The description of the group
-    /// * `rules` - The rules of the group
+    /// * `from` - Address of the current member (must authenticate)
+    /// * `to` - Address taking over the slot (must authenticate)
+    /// * `group_id` - The group
+    ///
+    /// # Returns
+    /// `Ok(())` on successful transfer
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    /// * `Unauthorized` - If the caller is not the group creator
-    /// * `MetadataTooLong` - If any field exceeds its length limit
-    pub fn set_group_metadata(
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all payouts
+    /// * `NotMember` - If `from` is not a member
+    /// * `AlreadyMember` - If `to` is already a member
+    /// * `Unauthorized` - If `from` is the group creator, or the group is
+    ///   restricted and `to` wasn't invited
+    /// * `InsufficientBalance` - If `to` can't cover the collateral
+    pub fn transfer_slot(
         env: Env,
+        from: Address,
+        to: Address,
         group_id: u64,
-        name: soroban_sdk::String,
-        description: soroban_sdk::String,
-        rules: soroban_sdk::String,
     ) -> Result<(), AjoError> {
-        // Validate lengths
-        if name.len() > crate::types::MAX_NAME_LENGTH
-            || description.len() > crate::types::MAX_DESCRIPTION_LENGTH
-            || rules.len() > crate::types::MAX_RULES_LENGTH
-        {
-            return Err(AjoError::MetadataTooLong);
+        pausable::ensure_not_paused(&env)?;
+        from.require_auth();
+        to.require_auth();
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
         }
 
-        // Get group to verify existence and check creator
-        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
 
-        // Require creator authentication
-        group.creator.require_auth();
+        let member_index = group
+            .members
+            .first_index_of(&from)
+            .ok_or(AjoError::NotMember)?;
 
-        // Create and store metadata
-        let metadata = GroupMetadata {
-            name,
-            description,
-            rules,
-        };
-        storage::store_group_metadata(&env, group_id, &metadata);
+        if utils::is_member(&group.members, &to) {
+            return Err(AjoError::AlreadyMember);
+        }
+
+        // The creator manages the group and can't hand that role over
+        if group.creator == from {
+            return Err(AjoError::Unauthorized);
+        }
+
+        // Restricted groups only hand slots to invited addresses
+        if group.settings.join_policy != JoinPolicy::Open {
+            let mut invites = storage::get_invites(&env, group_id);
+            let index = invites.first_index_of(&to).ok_or(AjoError::Unauthorized)?;
+            invites.remove(index);
+            storage::store_invites(&env, group_id, &invites);
+        }
+
+        release_collateral(&env, group_id, &from)?;
+        release_prepayment(&env, &group, &from)?;
+
+        // Take over the slots in place so payout positions are unchanged
+        group.members.set(member_index, to.clone());
+        while let Some(order_index) = group.payout_order.first_index_of(&from) {
+            group.payout_order.set(order_index, to.clone());
+        }
+
+        storage::transfer_member_state(&env, &group, &from, &to);
+        storage::store_group(&env, group_id, &group);
+
+        lock_collateral(&env, &group, &to)?;
+
+        events::emit_slot_transferred(&env, group_id, &from, &to);
 
         Ok(())
     }
 
-    /// Get metadata for an Ajo group.
+    /// Set the payout order for a need-based group.
+    ///
+    /// Lets the creator of a [`PayoutStrategy::NeedBased`] group decide who is paid
+    /// first, e.g. according to members' needs. The order can be changed any number
+    /// of times until the first contribution is made. Members who join after the
+    /// order has been set are queued at the end.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The unique group identifier
+    /// * `creator` - Address of the group creator (must authenticate)
+    /// * `group_id` - The group to update
+    /// * `order` - Every current member once for each slot they hold, in
+    ///   payout order
     ///
     /// # Returns
-    /// The group metadata
+    /// `Ok(())` on successful update
     ///
     /// # Errors
-    /// * `GroupNotFound` - If metadata for the group doesn't exist
-    pub fn get_group_metadata(env: Env, group_id: u64) -> Result<GroupMetadata, AjoError> {
-        storage::get_group_metadata(&env, group_id).ok_or(AjoError::GroupNotFound)
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `InvalidPayoutStrategy` - If the group is not need-based
+    /// * `PayoutOrderLocked` - If contributions have already started
+    /// * `InvalidPayoutOrder` - If `order` is not a permutation of the slots
+    pub fn set_payout_order(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        order: Vec<Address>,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+
+        if group.payout_strategy != PayoutStrategy::NeedBased {
+            return Err(AjoError::InvalidPayoutStrategy);
+        }
+
+        if utils::has_contributions_started(&env, &group) {
+            return Err(AjoError::PayoutOrderLocked);
+        }
+
+        if !utils::is_valid_payout_order(&group.payout_order, &order) {
+            return Err(AjoError::InvalidPayoutOrder);
+        }
+
+        group.payout_order = order;
+        storage::store_group(&env, group_id, &group);
+
+        events::emit_payout_order_set(&env, group_id, &group.payout_order);
+
+        Ok(())
     }
 
-    /// Get member penalty statistics.
+    /// Place or update a bid in a bid-based group's payout auction.
     ///
-    /// Returns the penalty record for a member in a specific group, including
-    /// late contribution count, on-time count, total penalties paid, and reliability score.
+    /// The bid is the discount the member is willing to give up from the
+    /// cycle's pot. At payout, the unpaid member with the largest discount
+    /// receives the pot minus their discount, and the discount is shared
+    /// among the other members who paid that cycle, in proportion to their
+    /// contribution, as a claimable balance. If defaults shrink the pot, the
+    /// discount is capped at what is left. Bids can be raised or lowered until
+    /// the cycle ends.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The unique group identifier
-    /// * `member` - The member's address
+    /// * `member` - Address of the bidding member (must authenticate)
+    /// * `group_id` - The group to bid in
+    /// * `discount` - Amount to give up from the pot, in stroops
     ///
     /// # Returns
-    /// The member's penalty record
+    /// `Ok(())` on successful bid
     ///
     /// # Errors
-    /// * `GroupNotFound` - If the group doesn't exist
-    /// * `NotMember` - If the address is not a member of the group
-    pub fn get_member_penalty_record(
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `InvalidPayoutStrategy` - If the group is not bid-based
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all payouts
+    /// * `InvalidGroupState` - If the group hasn't started
+    /// * `NotMember` - If the caller is not a member
+    /// * `AlreadyReceivedPayout` - If every slot the caller holds has been paid out
+    /// * `OutsideCycleWindow` - If the current cycle has ended
+    /// * `InvalidBidAmount` - If the discount is negative or not below the pot
+    pub fn place_bid(
         env: Env,
-        group_id: u64,
         member: Address,
-    ) -> Result<crate::types::MemberPenaltyRecord, AjoError> {
-        // Verify group exists
+        group_id: u64,
+        discount: i128,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        member.require_auth();
+
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
 
-        // Verify member
+        if group.payout_strategy != PayoutStrategy::BidBased {
+            return Err(AjoError::InvalidPayoutStrategy);
+        }
+
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+
+        if group.state == crate::types::GroupState::Forming {
+            return Err(AjoError::InvalidGroupState);
+        }
+
         if !utils::is_member(&group.members, &member) {
             return Err(AjoError::NotMember);
         }
 
-        // Get penalty record or return default
-        Ok(storage::get_member_penalty(&env, group_id, &member).unwrap_or(
-            crate::types::MemberPenaltyRecord {
-                member: member.clone(),
-                group_id,
-                late_count: 0,
-                on_time_count: 0,
-                total_penalties: 0,
-                reliability_score: 100,
-            },
-        ))
+        if !utils::has_unpaid_slot(&group, &member) {
+            return Err(AjoError::AlreadyReceivedPayout);
+        }
+
+        // Bids close when the cycle ends
+        let now = utils::get_current_timestamp(&env);
+        if now >= group.cycle_start_time + group.cycle_duration {
+            return Err(AjoError::OutsideCycleWindow);
+        }
+
+        let pot = utils::calculate_payout_amount(&env, &group);
+        if discount < 0 || discount >= pot {
+            return Err(AjoError::InvalidBidAmount);
+        }
+
+        let bid = Bid {
+            group_id,
+            cycle: group.current_cycle,
+            bidder: member.clone(),
+            discount,
+            timestamp: now,
+        };
+        storage::store_bid(&env, group_id, group.current_cycle, &member, &bid);
+
+        events::emit_bid_placed(&env, group_id, &member, group.current_cycle, discount);
+
+        Ok(())
     }
 
-    /// Get detailed contribution record for a member in a specific cycle.
-    ///
-    /// Returns the full contribution record including timing, penalty information,
-    /// and whether the contribution was late.
+    /// Get a member's auction bid for a cycle.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The unique group identifier
+    /// * `group_id` - The group
     /// * `cycle` - The cycle number
     /// * `member` - The member's address
     ///
     /// # Returns
-    /// The detailed contribution record
+    /// The member's `Bid`
     ///
     /// # Errors
-    /// * `GroupNotFound` - If the group doesn't exist or contribution record doesn't exist
-    pub fn get_contribution_detail(
-        env: Env,
-        group_id: u64,
-        cycle: u32,
-        member: Address,
-    ) -> Result<crate::types::ContributionRecord, AjoError> {
-        storage::get_contribution_detail(&env, group_id, cycle, &member)
-            .ok_or(AjoError::GroupNotFound)
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NoBid` - If the member has not bid in that cycle
+    pub fn get_bid(env: Env, group_id: u64, cycle: u32, member: Address) -> Result<Bid, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        storage::get_bid(&env, group_id, cycle, &member).ok_or(AjoError::NoBid)
     }
 
-    /// Get the penalty pool for a specific cycle.
+    /// Check if an address is a member of a group.
     ///
-    /// Returns the total penalties collected during a cycle, which will be
-    /// distributed to the payout recipient along with regular contributions.
+    /// Returns whether the provided address is currently a member of the specified group.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The unique group identifier
-    /// * `cycle` - The cycle number
+    /// * `group_id` - The group to check
+    /// * `address` - The address to check
     ///
     /// # Returns
-    /// Total penalty amount in stroops
+    /// `true` if the address is a member, `false` otherwise
     ///
     /// # Errors
-    /// * `GroupNotFound` - If the group doesn't exist
-    pub fn get_cycle_penalty_pool(env: Env, group_id: u64, cycle: u32) -> Result<i128, AjoError> {
-        // Verify group exists
-        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-
-        Ok(storage::get_cycle_penalty_pool(&env, group_id, cycle))
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn is_member(env: Env, group_id: u64, address: Address) -> Result<bool, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(utils::is_member(&group.members, &address))
     }
 
-    /// Cancel a group and refund all members.
+    /// Choose a member's share weight.
+    ///
+    /// In groups created with `variable_shares`, members can contribute a
+    /// larger or smaller share than `contribution_amount`, e.g. 200 for a
+    /// double share or 50 for a half share. The weight is a percentage of
+    /// `contribution_amount` and scales everything the member pays each
+    /// cycle: contributions, installments, prepayments and late penalties.
+    /// The pot paid out each cycle is the sum of every member's weighted
+    /// contribution, and refunds return what each member actually paid.
     ///
-    /// Only the group creator can cancel a group, and only before the first payout.
-    /// All members who have contributed will receive their token contributions back.
+    /// Weights are chosen while the group is forming, when members join, and
+    /// are locked once the group starts.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `creator` - Address of the group creator
-    /// * `group_id` - The unique group identifier
+    /// * `member` - The member choosing their share (must authenticate)
+    /// * `group_id` - The group
+    /// * `weight` - Share weight between [`MIN_SHARE_WEIGHT`] and
+    ///   [`MAX_SHARE_WEIGHT`]
     ///
     /// # Returns
-    /// `Ok(())` on successful cancellation and refunds
+    /// `Ok(())` on successful update
     ///
     /// # Errors
-    /// * `GroupNotFound` - If the group doesn't exist
-    /// * `OnlyCreatorCanCancel` - If the caller is not the group creator
-    /// * `CannotCancelAfterPayout` - If any payout has been executed
-    /// * `GroupCancelled` - If the group is already cancelled
-    /// * `GroupComplete` - If the group is already complete
-    /// * `TransferFailed` - If any token refund transfer fails
-    pub fn cancel_group(env: Env, creator: Address, group_id: u64) -> Result<(), AjoError> {
+    /// * `InvalidShareWeight` - If the weight is outside the allowed range
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the group doesn't allow variable shares
+    /// * `InvalidGroupState` - If the group isn't forming
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyContributed` - If the member has already prepaid cycles at
+    ///   their current weight
+    ///
+    /// [`MIN_SHARE_WEIGHT`]: crate::types::MIN_SHARE_WEIGHT
+    /// [`MAX_SHARE_WEIGHT`]: crate::types::MAX_SHARE_WEIGHT
+    pub fn set_share_weight(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        weight: u32,
+    ) -> Result<(), AjoError> {
         pausable::ensure_not_paused(&env)?;
-        creator.require_auth();
-
-        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        member.require_auth();
 
-        // Verify creator
-        if group.creator != creator {
-            return Err(AjoError::OnlyCreatorCanCancel);
+        if !(crate::types::MIN_SHARE_WEIGHT..=crate::types::MAX_SHARE_WEIGHT).contains(&weight) {
+            return Err(AjoError::InvalidShareWeight);
         }
 
-        // Check if already cancelled or complete
-        if group.state == crate::types::GroupState::Cancelled {
-            return Err(AjoError::GroupCancelled);
-        }
-        if group.state == crate::types::GroupState::Complete {
-            return Err(AjoError::GroupComplete);
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if !group.settings.variable_shares {
+            return Err(AjoError::Unauthorized);
         }
 
-        // Cannot cancel after first payout
-        if group.payout_index > 0 {
-            return Err(AjoError::CannotCancelAfterPayout);
+        if group.state != crate::types::GroupState::Forming {
+            return Err(AjoError::InvalidGroupState);
         }
 
-        // Calculate refunds for each member who contributed
-        let contract_address = env.current_contract_address();
-        
-        for member in group.members.iter() {
-            if storage::has_contributed(&env, group_id, group.current_cycle, &member) {
-                let refund_amount = group.contribution_amount;
-
-                // Transfer tokens back to member
-                crate::token::transfer_token(
-                    &env,
-                    &group.token_address,
-                    &contract_address,
-                    &member,
-                    refund_amount,
-                )?;
-
-                // Store refund record
-                let refund_record = crate::types::RefundRecord {
-                    group_id,
-                    member: member.clone(),
-                    amount: refund_amount,
-                    timestamp: utils::get_current_timestamp(&env),
-                    reason: crate::types::RefundReason::CreatorCancellation,
-                };
-                storage::store_refund_record(&env, group_id, &member, &refund_record);
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
 
-                // Emit refund event
-                events::emit_refund_processed(&env, group_id, &member, refund_amount, 0);
-            }
+        if storage::get_prepaid_cycles(&env, group_id, &member) > 0 {
+            return Err(AjoError::AlreadyContributed);
         }
 
-        // Update group state
-        group.state = crate::types::GroupState::Cancelled;
-        storage::store_group(&env, group_id, &group);
+        storage::store_share_weight(&env, group_id, &member, weight);
 
-        // Emit cancellation event
-        events::emit_group_cancelled(
-            &env,
-            group_id,
-            &creator,
-            group.members.len(),
-            group.contribution_amount,
-        );
+        events::emit_share_weight_set(&env, group_id, &member, weight);
 
         Ok(())
     }
 
-    /// Request a refund for a group that has failed to complete.
-    ///
-    /// Any member can request a refund if the cycle deadline has passed and
-    /// not all members have contributed. This initiates a voting period where
-    /// members can vote on whether to approve the refund.
+    /// Get a member's share weight.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `requester` - Address of the member requesting the refund
-    /// * `group_id` - The unique group identifier
+    /// * `group_id` - The group
+    /// * `member` - The member's address
     ///
     /// # Returns
-    /// `Ok(())` on successful request creation
+    /// The member's share weight as a percentage of `contribution_amount`
     ///
     /// # Errors
-    /// * `GroupNotFound` - If the group doesn't exist
-    /// * `NotMember` - If the requester is not a member
-    /// * `GroupCancelled` - If the group is already cancelled
-    /// * `GroupComplete` - If the group is already complete
-    /// * `CycleNotExpired` - If the cycle deadline hasn't passed
-    /// * `RefundRequestExists` - If a refund request already exists
-    pub fn request_refund(env: Env, requester: Address, group_id: u64) -> Result<(), AjoError> {
-        pausable::ensure_not_paused(&env)?;
-        requester.require_auth();
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_share_weight(env: Env, group_id: u64, member: Address) -> Result<u32, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_share_weight(&env, group_id, &member))
+    }
 
+    /// Get how many payout slots a member holds.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The number of slots, or 0 if the address is not a member
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_member_slots(env: Env, group_id: u64, member: Address) -> Result<u32, AjoError> {
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(utils::slot_count(&group, &member))
+    }
 
-        // Verify member
-        if !utils::is_member(&group.members, &requester) {
-            return Err(AjoError::NotMember);
-        }
+    /// Contribute to the current cycle.
+    ///
+    /// Records a member's contribution for the current cycle and transfers tokens from
+    /// the member to the contract. Each member can contribute once per cycle.
+    /// Authentication is required.
+    ///
+    /// The function transfers the contribution amount, scaled by the member's
+    /// share weight and once for every slot they hold, from the member's token
+    /// balance to the contract, less anything already paid with
+    /// `contribute_partial`.
+    /// Late contributions (after cycle ends but within grace period)
+    /// incur a penalty of `penalty_rate` percent of that amount, which is transferred
+    /// alongside the contribution and added to the cycle's penalty pool. Contributions
+    /// after grace period are rejected.
+    ///
+    /// Every contribution writes a [`ContributionRecord`](crate::types::ContributionRecord)
+    /// and updates the member's [`MemberPenaltyRecord`](crate::types::MemberPenaltyRecord),
+    /// including a recomputed reliability score.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `member` - Address making the contribution (must authenticate)
+    /// * `group_id` - The group to contribute to
+    ///
+    /// # Returns
+    /// `Ok(())` on successful contribution and token transfer
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyContributed` - If already contributed this cycle
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GracePeriodExpired` - If contribution is too late (after grace period)
+    /// * `InsufficientBalance` - If member doesn't have enough tokens (including any penalty)
+    /// * `TransferFailed` - If the token transfer fails
+    pub fn contribute(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
+        // Check if paused
+        pausable::ensure_not_paused(&env)?;
 
-        // Check group state
-        if group.state == crate::types::GroupState::Cancelled {
-            return Err(AjoError::GroupCancelled);
-        }
-        if group.state == crate::types::GroupState::Complete {
-            return Err(AjoError::GroupComplete);
-        }
+        // Require authentication
+        member.require_auth();
 
-        // Check if cycle has expired (past grace period)
+        // Get group (single fetch)
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        ensure_can_contribute(&env, &group, &member)?;
+
+        // Classify the contribution: on time, late (within grace period), or expired
         let now = utils::get_current_timestamp(&env);
-        let grace_end = utils::get_grace_period_end(&group);
-        if now <= grace_end {
-            return Err(AjoError::CycleNotExpired);
+        if now > utils::get_grace_period_end(&group) {
+            return Err(AjoError::GracePeriodExpired);
         }
+        let is_late = utils::is_within_grace_period(&group, now);
+        let penalty = if is_late {
+            utils::calculate_penalty(&env, &group, &member)
+        } else {
+            0
+        };
+        let owed = utils::contribution_due(&env, &group, &member)
+            - storage::get_partial_contribution(&env, group_id, group.current_cycle, &member);
+        let total_amount = owed + penalty;
 
-        // Check if refund request already exists
-        if storage::has_refund_request(&env, group_id) {
-            return Err(AjoError::RefundRequestExists);
-        }
+        // Get contract address for token transfer
+        let contract_address = env.current_contract_address();
 
-        // Create refund request
-        let voting_deadline = now + crate::types::VOTING_PERIOD;
-        let request = crate::types::RefundRequest {
-            group_id,
-            requester: requester.clone(),
-            created_at: now,
-            voting_deadline,
-            votes_for: 0,
-            votes_against: 0,
-            executed: false,
-            approved: false,
-        };
+        // Check member balance before transfer
+        crate::token::check_balance(&env, &group.token_address, &member, total_amount)?;
 
-        storage::store_refund_request(&env, group_id, &request);
+        // Transfer contribution plus any penalty from member to contract
+        crate::token::transfer_token(
+            &env,
+            &group.token_address,
+            &member,
+            &contract_address,
+            total_amount,
+        )?;
+        deposit_to_escrow(&env, group_id, total_amount, penalty);
 
-        // Emit event
-        events::emit_refund_requested(&env, group_id, &requester, voting_deadline);
+        record_contribution(&env, &group, &member, now, is_late, penalty);
 
         Ok(())
     }
 
-    /// Vote on a refund request.
+    /// Pay part of the current cycle's contribution.
     ///
-    /// Members can vote in favor or against a refund request during the voting period.
-    /// Each member can only vote once.
+    /// Members who can't pay their whole contribution at once can pay it
+    /// in installments. Installments accumulate towards the cycle's
+    /// contribution, and the member only counts as having contributed once
+    /// they add up to the full amount. Anything beyond what is still owed is
+    /// not taken. If the final installment is paid after the cycle ends, the
+    /// late penalty is charged with it.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `voter` - Address of the voting member
-    /// * `group_id` - The unique group identifier
-    /// * `in_favor` - true to vote in favor, false to vote against
+    /// * `member` - Address making the payment (must authenticate)
+    /// * `group_id` - The group to contribute to
+    /// * `amount` - The installment amount
     ///
     /// # Returns
-    /// `Ok(())` on successful vote
+    /// The amount paid towards the cycle's contribution so far
     ///
     /// # Errors
-    /// * `GroupNotFound` - If the group doesn't exist
-    /// * `NotMember` - If the voter is not a member
-    /// * `NoRefundRequest` - If no refund request exists
-    /// * `AlreadyVoted` - If the member has already voted
-    /// * `VotingPeriodEnded` - If the voting period has ended
-    pub fn vote_refund(
+    /// * `ContributionAmountZero` - If amount == 0
+    /// * `ContributionAmountNegative` - If amount < 0
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `InvalidGroupState` - If the group hasn't started
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyContributed` - If the contribution is already paid in full
+    /// * `GracePeriodExpired` - If the grace period has ended
+    /// * `InsufficientBalance` - If member doesn't have enough tokens (including any penalty)
+    pub fn contribute_partial(
         env: Env,
-        voter: Address,
+        member: Address,
         group_id: u64,
-        in_favor: bool,
-    ) -> Result<(), AjoError> {
+        amount: i128,
+    ) -> Result<i128, AjoError> {
         pausable::ensure_not_paused(&env)?;
-        voter.require_auth();
-
-        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        member.require_auth();
 
-        // Verify member
-        if !utils::is_member(&group.members, &voter) {
-            return Err(AjoError::NotMember);
+        if amount == 0 {
+            return Err(AjoError::ContributionAmountZero);
         }
-
-        // Get refund request
-        let mut request = storage::get_refund_request(&env, group_id)
-            .ok_or(AjoError::NoRefundRequest)?;
-
-        // Check if already voted
-        if storage::has_voted(&env, group_id, &voter) {
-            return Err(AjoError::AlreadyVoted);
+        if amount < 0 {
+            return Err(AjoError::ContributionAmountNegative);
         }
 
-        // Check voting period
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        ensure_can_contribute(&env, &group, &member)?;
+
         let now = utils::get_current_timestamp(&env);
-        if now > request.voting_deadline {
-            return Err(AjoError::VotingPeriodEnded);
+        if now > utils::get_grace_period_end(&group) {
+            return Err(AjoError::GracePeriodExpired);
         }
 
-        // Record vote
-        let vote = crate::types::RefundVote {
-            group_id,
-            voter: voter.clone(),
-            in_favor,
-            timestamp: now,
+        let cycle = group.current_cycle;
+        let paid = storage::get_partial_contribution(&env, group_id, cycle, &member);
+        let due = utils::contribution_due(&env, &group, &member);
+        let installment = amount.min(due - paid);
+        let paid_so_far = paid + installment;
+        let completes = paid_so_far == due;
+
+        // The late penalty is charged once, with the installment that completes
+        // the contribution
+        let is_late = utils::is_within_grace_period(&group, now);
+        let penalty = if completes && is_late {
+            utils::calculate_penalty(&env, &group, &member)
+        } else {
+            0
         };
-        storage::store_refund_vote(&env, group_id, &voter, &vote);
+        let total_amount = installment + penalty;
 
-        // Update vote counts
-        if in_favor {
-            request.votes_for += 1;
+        let contract_address = env.current_contract_address();
+        crate::token::check_balance(&env, &group.token_address, &member, total_amount)?;
+        crate::token::transfer_token(
+            &env,
+            &group.token_address,
+            &member,
+            &contract_address,
+            total_amount,
+        )?;
+        deposit_to_escrow(&env, group_id, total_amount, penalty);
+
+        events::emit_installment_paid(&env, group_id, &member, cycle, installment, paid_so_far);
+
+        if completes {
+            record_contribution(&env, &group, &member, now, is_late, penalty);
         } else {
-            request.votes_against += 1;
+            storage::store_partial_contribution(&env, group_id, cycle, &member, paid_so_far);
         }
-        storage::store_refund_request(&env, group_id, &request);
 
-        // Emit event
-        events::emit_refund_vote(&env, group_id, &voter, in_favor);
+        Ok(paid_so_far)
+    }
 
-        Ok(())
+    /// Get how much a member has paid towards a cycle's contribution.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `cycle` - The cycle number
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The member's full contribution if they have contributed, otherwise the
+    /// sum of their installments so far
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_amount_paid(
+        env: Env,
+        group_id: u64,
+        cycle: u32,
+        member: Address,
+    ) -> Result<i128, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(utils::amount_paid(&env, &group, cycle, &member))
     }
 
-    /// Execute a refund after voting period ends.
+    /// Pay the contribution for several future cycles in advance.
     ///
-    /// Can be called by any member after the voting period ends. If the refund
-    /// is approved (>51% votes in favor), all members receive token refunds
-    /// based on their contributions.
+    /// The member's per-cycle contribution × `cycles` is escrowed by the
+    /// contract now, and as each of the following cycles starts (the first
+    /// cycle, for a group that hasn't started yet) the member is recorded as
+    /// having contributed to it on time. Prepaid cycles that are never used, because the group is
+    /// cancelled, completes or the member leaves, are refunded to the
+    /// member's claimable balance.
+    ///
+    /// `cycles` is capped at the number of cycles the group has left, less
+    /// any cycles already prepaid.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `executor` - Address executing the refund
-    /// * `group_id` - The unique group identifier
+    /// * `member` - Address paying ahead (must authenticate)
+    /// * `group_id` - The group
+    /// * `cycles` - How many future cycles to pay for
     ///
     /// # Returns
-    /// `Ok(())` on successful execution and token refunds
+    /// The number of cycles actually prepaid
     ///
     /// # Errors
-    /// * `GroupNotFound` - If the group doesn't exist
-    /// * `NoRefundRequest` - If no refund request exists
-    /// * `VotingPeriodActive` - If the voting period hasn't ended
-    /// * `RefundNotApproved` - If the refund wasn't approved
-    /// * `RefundAlreadyExecuted` - If the refund has already been executed
-    /// * `TransferFailed` - If any token refund transfer fails
-    pub fn execute_refund(env: Env, executor: Address, group_id: u64) -> Result<(), AjoError> {
+    /// * `ContributionAmountZero` - If cycles == 0
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyContributed` - If every remaining cycle is already prepaid
+    /// * `InsufficientBalance` - If member doesn't have enough tokens
+    pub fn contribute_ahead(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        cycles: u32,
+    ) -> Result<u32, AjoError> {
         pausable::ensure_not_paused(&env)?;
-        executor.require_auth();
+        member.require_auth();
 
-        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        let mut request = storage::get_refund_request(&env, group_id)
-            .ok_or(AjoError::NoRefundRequest)?;
+        if cycles == 0 {
+            return Err(AjoError::ContributionAmountZero);
+        }
 
-        // Check if already executed
-        if request.executed {
-            return Err(AjoError::RefundAlreadyExecuted);
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
         }
 
-        // Check voting period ended
-        let now = utils::get_current_timestamp(&env);
-        if now <= request.voting_deadline {
-            return Err(AjoError::VotingPeriodActive);
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
         }
 
-        // Calculate approval percentage
-        let total_votes = request.votes_for + request.votes_against;
-        let approval_percentage = if total_votes > 0 {
-            (request.votes_for * 100) / total_votes
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+
+        // A forming group has all of its cycles ahead of it; an active one
+        // only those after the current cycle, including later rounds
+        let later_rounds = utils::total_rounds(&group) - group.round;
+        let future_cycles = if group.state == crate::types::GroupState::Forming {
+            group.max_members.saturating_mul(later_rounds + 1)
         } else {
-            0
+            let slots = group.payout_order.len();
+            (slots - group.payout_index - 1).saturating_add(slots.saturating_mul(later_rounds))
         };
-
-        // Check if approved
-        if approval_percentage < crate::types::REFUND_APPROVAL_THRESHOLD {
-            request.executed = true;
-            request.approved = false;
-            storage::store_refund_request(&env, group_id, &request);
-            return Err(AjoError::RefundNotApproved);
+        let prepaid = storage::get_prepaid_cycles(&env, group_id, &member);
+        let taken = cycles.min(future_cycles.saturating_sub(prepaid));
+        if taken == 0 {
+            return Err(AjoError::AlreadyContributed);
         }
 
-        // Process refunds for all members who contributed
-        let contract_address = env.current_contract_address();
-        
-        for member in group.members.iter() {
-            if storage::has_contributed(&env, group_id, group.current_cycle, &member) {
-                let refund_amount = group.contribution_amount;
-
-                // Transfer tokens back to member
-                crate::token::transfer_token(
-                    &env,
-                    &group.token_address,
-                    &contract_address,
-                    &member,
-                    refund_amount,
-                )?;
-
-                // Store refund record
-                let refund_record = crate::types::RefundRecord {
-                    group_id,
-                    member: member.clone(),
-                    amount: refund_amount,
-                    timestamp: now,
-                    reason: crate::types::RefundReason::MemberVote,
-                };
-                storage::store_refund_record(&env, group_id, &member, &refund_record);
+        let amount = utils::contribution_due(&env, &group, &member) * taken as i128;
+        crate::token::check_balance(&env, &group.token_address, &member, amount)?;
+        crate::token::transfer_token(
+            &env,
+            &group.token_address,
+            &member,
+            &env.current_contract_address(),
+            amount,
+        )?;
+        deposit_to_escrow(&env, group_id, amount, 0);
+        storage::store_prepaid_cycles(&env, group_id, &member, prepaid + taken);
 
-                // Emit refund event
-                events::emit_refund_processed(&env, group_id, &member, refund_amount, 1);
-            }
+        events::emit_contributed_ahead(&env, group_id, &member, taken, amount);
+
+        Ok(taken)
+    }
+
+    /// Get how many future cycles a member has paid for in advance.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The number of prepaid cycles not yet used
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_prepaid_cycles(env: Env, group_id: u64, member: Address) -> Result<u32, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_prepaid_cycles(&env, group_id, &member))
+    }
+
+    /// Pre-authorize contributions for the next `cycles` cycles.
+    ///
+    /// Members who don't want to call `contribute` every cycle can opt in
+    /// here and `approve` the contract as a spender on the group's token.
+    /// Anyone can then call `collect_contribution` during each cycle to pull
+    /// the member's contribution using that allowance. The allowance should
+    /// cover the member's per-cycle contribution × `cycles`.
+    ///
+    /// Calling this again replaces the number of remaining cycles; passing 0
+    /// turns auto-contribution off.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `member` - The member opting in (must authenticate)
+    /// * `group_id` - The group
+    /// * `cycles` - How many cycles may be collected
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    pub fn enable_auto_contribute(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        cycles: u32,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        member.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
         }
 
-        // Update request and group state
-        request.executed = true;
-        request.approved = true;
-        storage::store_refund_request(&env, group_id, &request);
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
 
-        group.state = crate::types::GroupState::Cancelled;
-        storage::store_group(&env, group_id, &group);
+        if cycles == 0 {
+            storage::remove_auto_contribute(&env, group_id, &member);
+        } else {
+            storage::store_auto_contribute(&env, group_id, &member, cycles);
+        }
+
+        events::emit_auto_contribute_set(&env, group_id, &member, cycles);
 
         Ok(())
     }
 
-    /// Emergency refund by admin.
-    ///
-    /// Allows the contract admin to force a refund in case of disputes or emergencies.
-    /// All members who have contributed receive their token contributions back.
+    /// Get how many more cycles can be collected for a member.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `admin` - Address of the contract admin
-    /// * `group_id` - The unique group identifier
+    /// * `group_id` - The group
+    /// * `member` - The member's address
     ///
     /// # Returns
-    /// `Ok(())` on successful emergency refund and token transfers
+    /// The number of remaining cycles, or 0 if auto-contribution is off
     ///
     /// # Errors
-    /// * `Unauthorized` - If the caller is not the admin
-    /// * `GroupNotFound` - If the group doesn't exist
-    /// * `GroupCancelled` - If the group is already cancelled
-    /// * `TransferFailed` - If any token refund transfer fails
-    pub fn emergency_refund(env: Env, admin: Address, group_id: u64) -> Result<(), AjoError> {
-        admin.require_auth();
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_auto_contribute(env: Env, group_id: u64, member: Address) -> Result<u32, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_auto_contribute(&env, group_id, &member))
+    }
 
-        // Verify admin
-        let stored_admin = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
-        if admin != stored_admin {
-            return Err(AjoError::Unauthorized);
-        }
+    /// Collect a member's contribution using their token allowance.
+    ///
+    /// Anyone, typically a keeper, can call this for a member who opted in
+    /// with `enable_auto_contribute`. The contribution is pulled with
+    /// `transfer_from` and recorded exactly like a call to `contribute`,
+    /// including any installments already paid.
+    /// Collection only happens within the cycle window, so it never incurs a
+    /// late penalty. Each collection uses up one of the member's cycles.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `member` - The member whose contribution is collected
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `InvalidGroupState` - If the group hasn't started
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyContributed` - If already contributed this cycle
+    /// * `Unauthorized` - If the member has no cycles left to collect
+    /// * `OutsideCycleWindow` - If the current cycle has ended
+    /// * `InsufficientBalance` - If the member doesn't have enough tokens
+    /// * `InsufficientAllowance` - If the contract's allowance doesn't cover
+    ///   the contribution
+    pub fn collect_contribution(env: Env, group_id: u64, member: Address) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
 
-        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        ensure_can_contribute(&env, &group, &member)?;
 
-        // Check if already cancelled
-        if group.state == crate::types::GroupState::Cancelled {
-            return Err(AjoError::GroupCancelled);
+        let remaining = storage::get_auto_contribute(&env, group_id, &member);
+        if remaining == 0 {
+            return Err(AjoError::Unauthorized);
         }
 
         let now = utils::get_current_timestamp(&env);
-        let mut total_refunded = 0i128;
+        if now >= group.cycle_start_time + group.cycle_duration {
+            return Err(AjoError::OutsideCycleWindow);
+        }
+
+        let owed = utils::contribution_due(&env, &group, &member)
+            - storage::get_partial_contribution(&env, group_id, group.current_cycle, &member);
         let contract_address = env.current_contract_address();
+        crate::token::check_balance(&env, &group.token_address, &member, owed)?;
+        crate::token::transfer_token_from(
+            &env,
+            &group.token_address,
+            &contract_address,
+            &member,
+            &contract_address,
+            owed,
+        )?;
+        deposit_to_escrow(&env, group_id, owed, 0);
 
-        // Process refunds for all members who contributed
-        for member in group.members.iter() {
-            if storage::has_contributed(&env, group_id, group.current_cycle, &member) {
-                let refund_amount = group.contribution_amount;
-                total_refunded += refund_amount;
-
-                // Transfer tokens back to member
-                crate::token::transfer_token(
-                    &env,
-                    &group.token_address,
-                    &contract_address,
-                    &member,
-                    refund_amount,
-                )?;
-
-                // Store refund record
-                let refund_record = crate::types::RefundRecord {
-                    group_id,
-                    member: member.clone(),
-                    amount: refund_amount,
-                    timestamp: now,
-                    reason: crate::types::RefundReason::EmergencyRefund,
-                };
-                storage::store_refund_record(&env, group_id, &member, &refund_record);
+        record_contribution(&env, &group, &member, now, false, 0);
 
-                // Emit refund event
-                events::emit_refund_processed(&env, group_id, &member, refund_amount, 2);
-            }
+        if remaining == 1 {
+            storage::remove_auto_contribute(&env, group_id, &member);
+        } else {
+            storage::store_auto_contribute(&env, group_id, &member, remaining - 1);
         }
 
-        // Update group state
-        group.state = crate::types::GroupState::Cancelled;
-        storage::store_group(&env, group_id, &group);
-
-        // Emit emergency refund event
-        events::emit_emergency_refund(&env, group_id, &admin, total_refunded);
-
         Ok(())
     }
 
-    /// Get refund request for a group.
+    /// Get contribution status for all members in a specific cycle.
     ///
-    /// Returns the current refund request if one exists.
+    /// Returns an ordered list of all members paired with their contribution status
+    /// for the specified cycle. Member order matches the group's member list order.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The unique group identifier
+    /// * `group_id` - The group to check
+    /// * `cycle_number` - The cycle to check (typically use current_cycle from group)
     ///
     /// # Returns
-    /// The refund request
+    /// Vector of (Address, bool) tuples where `true` indicates the member has contributed
     ///
     /// # Errors
-    /// * `NoRefundRequest` - If no refund request exists
-    pub fn get_refund_request(
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_contribution_status(
         env: Env,
         group_id: u64,
-    ) -> Result<crate::types::RefundRequest, AjoError> {
-        storage::get_refund_request(&env, group_id).ok_or(AjoError::NoRefundRequest)
+        cycle_number: u32,
+    ) -> Result<Vec<(Address, bool)>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_cycle_contributions(
+            &env,
+            group_id,
+            cycle_number,
+            &group.members,
+        ))
     }
 
-    /// Get refund record for a member.
+    /// Mark a member as defaulted on the current cycle's contribution.
     ///
-    /// Returns the refund record if the member has received a refund.
+    /// Once the grace period has expired, anyone can mark a member who still
+    /// hasn't contributed so that the rotation can continue. Any installments
+    /// the member paid count towards the missed contribution; the rest is
+    /// covered from the member's collateral first, then from the cycle's
+    /// penalty pool, and whatever remains reduces this cycle's payout.
+    /// A defaulter who hasn't been paid yet is moved to the end of the payout
+    /// order and can't win this cycle's auction.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `group_id` - The unique group identifier
+    /// * `group_id` - The group
+    /// * `member` - The member who failed to contribute
+    ///
+    /// # Returns
+    /// The `DefaultRecord` describing how the contribution was covered
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all payouts
+    /// * `InvalidGroupState` - If the group hasn't started
+    /// * `NotMember` - If the address is not a member
+    /// * `CycleNotExpired` - If the grace period hasn't expired yet
+    /// * `AlreadyContributed` - If the member's contribution for this cycle is
+    ///   already settled, by paying or by an earlier default
+    pub fn mark_default(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<DefaultRecord, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+
+        if group.state == crate::types::GroupState::Forming {
+            return Err(AjoError::InvalidGroupState);
+        }
+
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+
+        let now = utils::get_current_timestamp(&env);
+        if now <= utils::get_grace_period_end(&group) {
+            return Err(AjoError::CycleNotExpired);
+        }
+
+        let cycle = group.current_cycle;
+        if storage::has_contributed(&env, group_id, cycle, &member)
+            || storage::get_default_record(&env, group_id, cycle, &member).is_some()
+        {
+            return Err(AjoError::AlreadyContributed);
+        }
+
+        // Installments count towards the contribution; cover the rest from
+        // collateral, then from the penalty pool
+        let from_installments = storage::get_partial_contribution(&env, group_id, cycle, &member);
+        let mut outstanding = utils::contribution_due(&env, &group, &member) - from_installments;
+
+        let collateral = storage::get_collateral(&env, group_id, &member);
+        let from_collateral = collateral.min(outstanding);
+        if from_collateral > 0 {
+            let remaining = collateral - from_collateral;
+            storage::store_collateral(&env, group_id, &member, remaining);
+            events::emit_collateral_slashed(
+                &env,
+                group_id,
+                &member,
+                cycle,
+                from_collateral,
+                remaining,
+            );
+        }
+        outstanding -= from_collateral;
+
+        let penalty_pool = storage::get_cycle_penalty_pool(&env, group_id, cycle);
+        let from_penalty_pool = penalty_pool.min(outstanding);
+        if from_penalty_pool > 0 {
+            storage::store_cycle_penalty_pool(
+                &env,
+                group_id,
+                cycle,
+                penalty_pool - from_penalty_pool,
+            );
+        }
+        outstanding -= from_penalty_pool;
+
+        let record = DefaultRecord {
+            group_id,
+            cycle,
+            member: member.clone(),
+            from_installments,
+            from_collateral,
+            from_penalty_pool,
+            shortfall: outstanding,
+            timestamp: now,
+        };
+        storage::store_default_record(&env, group_id, cycle, &member, &record);
+
+        // Installments and collateral paid for the cycle as far as they went
+        let mut position = storage::get_member_position(&env, group_id, &member);
+        position.contributed += from_installments + from_collateral;
+        storage::store_member_position(&env, group_id, &member, &position);
+
+        // Defaulters wait until everyone else has been paid
+        if utils::has_unpaid_slot(&group, &member) {
+            utils::demote_in_payout_order(&mut group, &member);
+            storage::store_group(&env, group_id, &group);
+        }
+
+        events::emit_member_defaulted(
+            &env,
+            group_id,
+            &member,
+            cycle,
+            from_collateral,
+            from_penalty_pool,
+            outstanding,
+        );
+
+        Ok(record)
+    }
+
+    /// Get a member's default on a cycle.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `cycle` - The cycle number
     /// * `member` - The member's address
     ///
     /// # Returns
-    /// The refund record
+    /// The member's `DefaultRecord` for that cycle
     ///
     /// # Errors
-    /// * `GroupNotFound` - If no refund record exists
-    pub fn get_refund_record(
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `RecordNotFound` - If the member didn't default on that cycle
+    pub fn get_default_record(
         env: Env,
         group_id: u64,
+        cycle: u32,
         member: Address,
-    ) -> Result<crate::types::RefundRecord, AjoError> {
-        storage::get_refund_record(&env, group_id, &member).ok_or(AjoError::GroupNotFound)
+    ) -> Result<DefaultRecord, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        storage::get_default_record(&env, group_id, cycle, &member).ok_or(AjoError::RecordNotFound)
     }
 
-    /// Get the contract's token balance for a specific token.
+    /// Execute payout for the current cycle.
     ///
-    /// Returns the amount of tokens held by the contract for a given token address.
-    /// Useful for checking if the contract has sufficient funds for payouts.
+    /// This is the core function that rotates payouts through group members.
+    /// It verifies that all members have contributed, calculates the total payout
+    /// (including any penalties collected), credits it to the recipient's
+    /// claimable balance, and advances the cycle. When every slot has received its
+    /// payout the round is over: the group starts its next round if it runs
+    /// several (see `GroupSettings::rounds`), otherwise it is marked complete.
+    ///
+    /// Payout can only be executed after the grace period expires to ensure all
+    /// late contributions are collected. Members who never paid must first be
+    /// marked with `mark_default`; any shortfall they leave reduces the payout.
+    ///
+    /// Process:
+    /// 1. Verifies all members have contributed in the current cycle (or were
+    ///    marked as defaulted)
+    /// 2. Ensures grace period has expired
+    /// 3. Calculates total payout (every member's weighted contribution + penalties - shortfall)
+    /// 4. Credits the payout from the group's escrow to the recipient's claimable balance
+    /// 5. Records payout to the current recipient
+    /// 6. Emits payout event with penalty bonus
+    /// 7. Advances to next cycle, starting a new round or marking the group
+    ///    complete and releasing collateral when the round is over
+    ///
+    /// The recipient withdraws the payout with `claim` or `claim_all`, so a
+    /// recipient whose account can't receive tokens doesn't hold up the group.
     ///
     /// # Arguments
     /// * `env` - The Soroban contract environment
-    /// * `token_address` - Address of the token contract
+    /// * `group_id` - The group to execute payout for
     ///
     /// # Returns
-    /// The token balance held by the contract
-    pub fn get_contract_balance(env: Env, token_address: Address) -> i128 {
-        let contract_address = env.current_contract_address();
-        crate::token::get_balance(&env, &token_address, &contract_address)
+    /// `Ok(())` on successful payout execution and token transfer
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `IncompleteContributions` - If not all members have contributed or
+    ///   been marked as defaulted
+    /// * `GroupComplete` - If the group has already completed all payouts
+    /// * `NoMembers` - If the group has no members (should never happen)
+    /// * `OutsideCycleWindow` - If grace period has not expired yet
+    /// * `InsufficientContractBalance` - If the group's escrow can't cover the payout
+    pub fn execute_payout(env: Env, group_id: u64) -> Result<(), AjoError> {
+        // Check if paused
+        pausable::ensure_not_paused(&env)?;
+
+        // Get group (single fetch)
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // Check if group is cancelled
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+
+        // Check if group has started
+        if group.state == crate::types::GroupState::Forming {
+            return Err(AjoError::InvalidGroupState);
+        }
+
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+
+        // Cache frequently accessed values
+        let group_id_cached = group.id;
+        let current_cycle = group.current_cycle;
+        let slot_count = group.payout_order.len();
+
+        // Check if all members have contributed
+        if !utils::all_members_contributed(&env, &group) {
+            return Err(AjoError::IncompleteContributions);
+        }
+
+        // Ensure grace period has expired before executing payout
+        let current_time = utils::get_current_timestamp(&env);
+        let grace_end = utils::get_grace_period_end(&group);
+        if current_time < grace_end {
+            // Still within grace period - delay payout
+            return Err(AjoError::OutsideCycleWindow);
+        }
+
+        // Bid-based groups pay the highest bidder, who gives up their discount
+        let mut discount = 0i128;
+        if group.payout_strategy == PayoutStrategy::BidBased {
+            if let Some((index, bid)) = utils::find_winning_bid(&env, &group) {
+                utils::promote_in_payout_order(&mut group, index);
+                discount = bid.discount;
+            }
+        }
+
+        // Get payout recipient
+        let payout_recipient = group
+            .payout_order
+            .get(group.payout_index)
+            .ok_or(AjoError::NoMembers)?;
+
+        // Calculate payout amounts: base payout + collected penalties for this cycle,
+        // less whatever defaulted members left uncovered
+        let base_payout =
+            utils::calculate_payout_amount(&env, &group) - utils::cycle_shortfall(&env, &group);
+        let penalty_bonus = storage::get_cycle_penalty_pool(&env, group_id_cached, current_cycle);
+
+        // Check the escrow covers every credit below before making any of them
+        ensure_escrow_covers(&env, group_id_cached, payout_credits(&env, &group))?;
+
+        // The bid was checked against the full pot; defaults since then may
+        // have shrunk it, so the discount never exceeds what is left
+        let discount = discount.min(base_payout + penalty_bonus).max(0);
+
+        // Split the discount among the other members who paid this cycle, in
+        // proportion to what they contribute; any remainder stays with the
+        // recipient
+        let mut discount_shares = Vec::new(&env);
+        let mut total_weight = 0i128;
+        for member in group.members.iter() {
+            if member == payout_recipient
+                || storage::get_default_record(&env, group_id_cached, current_cycle, &member)
+                    .is_some()
+            {
+                continue;
+            }
+            let weight = utils::contribution_due(&env, &group, &member);
+            total_weight += weight;
+            discount_shares.push_back((member, weight));
+        }
+        let mut distributed_discount = 0i128;
+        for i in 0..discount_shares.len() {
+            let (member, weight) = discount_shares.get(i).unwrap();
+            let share = if total_weight > 0 {
+                discount * weight / total_weight
+            } else {
+                0
+            };
+            distributed_discount += share;
+            discount_shares.set(i, (member, share));
+        }
+        let payout_amount = base_payout - distributed_discount + penalty_bonus;
+
+        // Credit the recipient, who withdraws with `claim`
+        credit_from_escrow(
+            &env,
+            group_id_cached,
+            &payout_recipient,
+            payout_amount,
+            EscrowCredit::Payout,
+        )?;
+        let mut position = storage::get_member_position(&env, group_id_cached, &payout_recipient);
+        position.received += payout_amount;
+        storage::store_member_position(&env, group_id_cached, &payout_recipient, &position);
+
+        // Mark payout as received
+        storage::mark_payout_received(&env, group_id_cached, &payout_recipient);
+        storage::store_payout_record(
+            &env,
+            group_id_cached,
+            current_cycle,
+            &PayoutRecord {
+                group_id: group_id_cached,
+                cycle: current_cycle,
+                member: payout_recipient.clone(),
+                amount: payout_amount,
+                discount: distributed_discount,
+                timestamp: current_time,
+            },
+        );
+
+        // Credit each other member's share of the discount
+        if distributed_discount > 0 {
+            for (member, share) in discount_shares.iter() {
+                if share == 0 {
+                    continue;
+                }
+                credit_from_escrow(&env, group_id_cached, &member, share, EscrowCredit::Payout)?;
+                let mut position = storage::get_member_position(&env, group_id_cached, &member);
+                position.received += share;
+                storage::store_member_position(&env, group_id_cached, &member, &position);
+            }
+            events::emit_auction_settled(
+                &env,
+                group_id_cached,
+                &payout_recipient,
+                current_cycle,
+                distributed_discount,
+                discount_shares.len(),
+            );
+        }
+
+        // Emit payout event with penalty information
+        if penalty_bonus > 0 {
+            events::emit_penalty_distributed(
+                &env,
+                group_id,
+                &payout_recipient,
+                group.current_cycle,
+                base_payout,
+                penalty_bonus,
+            );
+        }
+
+        events::emit_payout_executed(
+            &env,
+            group_id_cached,
+            &payout_recipient,
+            current_cycle,
+            payout_amount,
+        );
+
+        // Advance payout index
+        group.payout_index += 1;
+
+        // Once every slot has been paid out the round is over; the group
+        // either completes or starts its next round
+        let round_over = group.payout_index >= slot_count;
+        if !round_over || finish_round(&env, &mut group, current_cycle)? {
+            // Advance to next cycle
+            group.current_cycle += 1;
+            group.cycle_start_time = utils::get_current_timestamp(&env);
+            apply_prepayments(&env, &group);
+        }
+
+        // Update storage (single write)
+        storage::store_group(&env, group_id, &group);
+
+        Ok(())
+    }
+
+    /// Check if a group has completed all cycles.
+    ///
+    /// Returns whether the group has completed its last round, meaning all
+    /// members have received a payout in every round.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// `true` if the group has completed all payouts, `false` otherwise
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn is_complete(env: Env, group_id: u64) -> Result<bool, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(group.is_complete)
+    }
+
+    /// Advance a group by whatever step is due.
+    ///
+    /// Lets anyone, typically an off-chain keeper bot, keep a group moving
+    /// without waiting on its members:
+    /// - Once a refund vote has closed, the refund is executed if it was
+    ///   approved and the request is rejected otherwise. While a vote is still
+    ///   open the group is left for its members to decide.
+    /// - Once the cycle's grace period has expired, members who haven't paid
+    ///   are marked as defaulted and the payout is executed.
+    ///
+    /// If anything was done, the caller is credited the group's `keeper_fee`
+    /// from the current cycle's penalty pool. Groups with nothing due are left
+    /// untouched and pay no fee.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `caller` - Address credited with the keeper fee
+    /// * `group_id` - The group to advance
+    ///
+    /// # Returns
+    /// A `KeeperReport` describing what was done
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn poke(env: Env, caller: Address, group_id: u64) -> Result<KeeperReport, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        caller.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        keep_group(&env, &caller, group)
+    }
+
+    /// Advance up to `max_groups` groups with `poke`.
+    ///
+    /// Groups are visited in ID order starting where the previous call left
+    /// off, wrapping back to the first group after the last, so repeated calls
+    /// cycle through every group. A group that fails to advance, e.g. because
+    /// its escrow can't cover the payout, is skipped with a `keepskip` event
+    /// carrying the error code rather than failing the whole sweep. Payouts
+    /// and refunds check up front that the escrow covers everything they
+    /// credit, so a skipped group keeps no partial payout, refund or keeper
+    /// fee; only defaults marked before the failure stay recorded.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `caller` - Address credited with the keeper fees
+    /// * `max_groups` - Maximum number of groups to visit in this call
+    ///
+    /// # Returns
+    /// A `KeeperReport` for each group that was advanced
+    pub fn process_due_groups(
+        env: Env,
+        caller: Address,
+        max_groups: u32,
+    ) -> Result<Vec<KeeperReport>, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        caller.require_auth();
+
+        let mut reports = Vec::new(&env);
+        let last_group_id = storage::get_group_counter(&env);
+        let mut group_id = storage::get_keeper_cursor(&env);
+
+        for _ in 0..(max_groups as u64).min(last_group_id) {
+            if group_id > last_group_id {
+                group_id = 1;
+            }
+            // A group that can't be advanced is skipped so it doesn't hold
+            // up the rest of the sweep
+            if let Some(group) = storage::get_group(&env, group_id) {
+                match keep_group(&env, &caller, group) {
+                    Ok(report) if report.did_work() => reports.push_back(report),
+                    Ok(_) => {}
+                    Err(error) => events::emit_keeper_skipped(&env, group_id, error as u32),
+                }
+            }
+            group_id += 1;
+        }
+
+        storage::set_keeper_cursor(&env, group_id);
+        Ok(reports)
+    }
+
+    /// Get comprehensive group status.
+    ///
+    /// Returns a detailed snapshot of the group's current state, including cycle
+    /// information, contribution status, payout progress, and timing data.
+    /// This is the primary function for checking a group's overall progress.
+    ///
+    /// Returns information about:
+    /// - Current cycle number and progress
+    /// - Next recipient for payout
+    /// - Members who have contributed and those who are pending
+    /// - Cycle timing (start time, end time, whether cycle is active)
+    /// - Whether the group is complete
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// A `GroupStatus` struct containing comprehensive group state information
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_group_status(env: Env, group_id: u64) -> Result<GroupStatus, AjoError> {
+        // Get the group data (single fetch)
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // Cache frequently accessed values
+        let current_time = utils::get_current_timestamp(&env);
+        let member_count = group.members.len();
+        let group_id_cached = group.id;
+        let current_cycle = group.current_cycle;
+
+        // Calculate cycle timing
+        let cycle_end_time = group.cycle_start_time + group.cycle_duration;
+        let grace_period_end_time = utils::get_grace_period_end(&group);
+        let is_active = group.state == crate::types::GroupState::Active;
+        let is_cycle_active = is_active && current_time < cycle_end_time;
+        let is_in_grace_period =
+            is_active && utils::is_within_grace_period(&group, current_time);
+
+        // Get penalty pool for current cycle
+        let cycle_penalty_pool = storage::get_cycle_penalty_pool(&env, group_id, group.current_cycle);
+
+        // Build pending_contributors list
+        let mut contributions_received: u32 = 0;
+        let mut pending_contributors = Vec::new(&env);
+
+        // Single pass through members to check contributions
+        for member in group.members.iter() {
+            if storage::has_contributed(&env, group_id_cached, current_cycle, &member) {
+                contributions_received += 1;
+            } else {
+                pending_contributors.push_back(member);
+            }
+        }
+
+        // Determine next recipient
+        let (has_next_recipient, next_recipient) = if group.is_complete {
+            // Use placeholder (creator) when complete
+            (false, group.creator.clone())
+        } else {
+            // Get the member at payout_index
+            let recipient = group
+                .payout_order
+                .get(group.payout_index)
+                .unwrap_or_else(|| group.creator.clone());
+            (true, recipient)
+        };
+
+        // Build and return status
+        Ok(GroupStatus {
+            group_id: group.id,
+            current_cycle: group.current_cycle,
+            has_next_recipient,
+            next_recipient,
+            contributions_received,
+            total_members: group.members.len() as u32,
+            pending_contributors,
+            is_complete: group.is_complete,
+            is_cycle_active,
+            cycle_start_time: group.cycle_start_time,
+            cycle_end_time,
+            current_time,
+            cycle_penalty_pool,
+            is_in_grace_period,
+            grace_period_end_time,
+            payout_strategy: group.payout_strategy,
+            payout_order: group.payout_order,
+            state: group.state,
+        })
+    }
+
+    /// Set or update metadata for an Ajo group.
+    ///
+    /// Only the group creator can set or update metadata.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `name` - The name of the group
+    /// * `description` - The description of the group
+    /// * `rules` - The rules of the group
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `MetadataTooLong` - If any field exceeds its length limit
+    pub fn set_group_metadata(
+        env: Env,
+        group_id: u64,
+        name: soroban_sdk::String,
+        description: soroban_sdk::String,
+        rules: soroban_sdk::String,
+    ) -> Result<(), AjoError> {
+        // Validate lengths
+        if name.len() > crate::types::MAX_NAME_LENGTH
+            || description.len() > crate::types::MAX_DESCRIPTION_LENGTH
+            || rules.len() > crate::types::MAX_RULES_LENGTH
+        {
+            return Err(AjoError::MetadataTooLong);
+        }
+
+        // Get group to verify existence and check creator
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // Require creator authentication
+        group.creator.require_auth();
+
+        // Create and store metadata
+        let metadata = GroupMetadata {
+            name,
+            description,
+            rules,
+        };
+        storage::store_group_metadata(&env, group_id, &metadata);
+
+        Ok(())
+    }
+
+    /// Get metadata for an Ajo group.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// The group metadata
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If metadata for the group doesn't exist
+    pub fn get_group_metadata(env: Env, group_id: u64) -> Result<GroupMetadata, AjoError> {
+        storage::get_group_metadata(&env, group_id).ok_or(AjoError::GroupNotFound)
+    }
+
+    /// Get member penalty statistics.
+    ///
+    /// Returns the penalty record for a member in a specific group, including
+    /// late contribution count, on-time count, total penalties paid, and reliability score.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The member's penalty record
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `NotMember` - If the address is not a member of the group
+    pub fn get_member_penalty_record(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<crate::types::MemberPenaltyRecord, AjoError> {
+        // Verify group exists
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // Verify member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+
+        // Get penalty record or return default
+        Ok(storage::get_member_penalty(&env, group_id, &member).unwrap_or(
+            crate::types::MemberPenaltyRecord {
+                member: member.clone(),
+                group_id,
+                late_count: 0,
+                on_time_count: 0,
+                total_penalties: 0,
+                reliability_score: 100,
+            },
+        ))
+    }
+
+    /// Get detailed contribution record for a member in a specific cycle.
+    ///
+    /// Returns the full contribution record including timing, penalty information,
+    /// and whether the contribution was late.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `cycle` - The cycle number
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The detailed contribution record
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist or contribution record doesn't exist
+    pub fn get_contribution_detail(
+        env: Env,
+        group_id: u64,
+        cycle: u32,
+        member: Address,
+    ) -> Result<crate::types::ContributionRecord, AjoError> {
+        storage::get_contribution_detail(&env, group_id, cycle, &member)
+            .ok_or(AjoError::GroupNotFound)
+    }
+
+    /// Get the penalty pool for a specific cycle.
+    ///
+    /// Returns the total penalties collected during a cycle, which will be
+    /// distributed to the payout recipient along with regular contributions.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `cycle` - The cycle number
+    ///
+    /// # Returns
+    /// Total penalty amount in stroops
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    pub fn get_cycle_penalty_pool(env: Env, group_id: u64, cycle: u32) -> Result<i128, AjoError> {
+        // Verify group exists
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        Ok(storage::get_cycle_penalty_pool(&env, group_id, cycle))
+    }
+
+    /// Cancel a group and refund all members.
+    ///
+    /// Only the group creator can cancel a group, and only before its first
+    /// payout; a multi-round group can't be cancelled between rounds either.
+    /// Collateral and unused prepayments are released, and
+    /// the rest of the group's escrow is shared out according to each
+    /// member's net position (see `execute_refund`). Everything is credited
+    /// to members' claimable balances, to be withdrawn with `claim`.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// `Ok(())` on successful cancellation and refunds
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `OnlyCreatorCanCancel` - If the caller is not the group creator
+    /// * `CannotCancelAfterPayout` - If any payout has been executed
+    /// * `GroupCancelled` - If the group is already cancelled
+    /// * `GroupComplete` - If the group is already complete
+    /// * `InsufficientContractBalance` - If the group's escrow can't cover the refunds
+    pub fn cancel_group(env: Env, creator: Address, group_id: u64) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        creator.require_auth();
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // Verify creator
+        if group.creator != creator {
+            return Err(AjoError::OnlyCreatorCanCancel);
+        }
+
+        // Check if already cancelled or complete
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        if group.state == crate::types::GroupState::Complete {
+            return Err(AjoError::GroupComplete);
+        }
+
+        // Cannot cancel after first payout; `payout_index` restarts with
+        // every round, so later rounds count as paid out too
+        if group.round > 1 || group.payout_index > 0 {
+            return Err(AjoError::CannotCancelAfterPayout);
+        }
+
+        // Settle every member's net position out of the group's escrow
+        let now = utils::get_current_timestamp(&env);
+        settle_group(
+            &env,
+            &group,
+            crate::types::RefundReason::CreatorCancellation,
+            now,
+            0,
+        )?;
+        close_refund_request(&env, &group);
+
+        // Update group state
+        group.state = crate::types::GroupState::Cancelled;
+        storage::store_group(&env, group_id, &group);
+
+        // Emit cancellation event
+        events::emit_group_cancelled(
+            &env,
+            group_id,
+            &creator,
+            group.members.len(),
+            group.contribution_amount,
+        );
+
+        Ok(())
+    }
+
+    /// Request a refund for a group that has failed to complete.
+    ///
+    /// Any member can request a refund if the cycle deadline has passed and
+    /// not all members have contributed. This opens a new refund proposal
+    /// with its own voting period, where members can vote on whether to
+    /// approve the refund.
+    ///
+    /// Only one proposal can be open at a time. Once an earlier proposal's
+    /// voting period has ended without approval it is closed as rejected,
+    /// its votes are cleared, and a new proposal can be made.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `requester` - Address of the member requesting the refund
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// The ID of the new refund proposal
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `NotMember` - If the requester is not a member
+    /// * `GroupCancelled` - If the group is already cancelled
+    /// * `GroupComplete` - If the group is already complete
+    /// * `CycleNotExpired` - If the cycle deadline hasn't passed
+    /// * `RefundRequestExists` - If an earlier proposal is still being voted on,
+    ///   or was approved but not yet executed
+    pub fn request_refund(env: Env, requester: Address, group_id: u64) -> Result<u64, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        requester.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // Verify member
+        if !utils::is_member(&group.members, &requester) {
+            return Err(AjoError::NotMember);
+        }
+
+        // Check group state
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        if group.state == crate::types::GroupState::Complete {
+            return Err(AjoError::GroupComplete);
+        }
+        if group.state == crate::types::GroupState::Forming {
+            return Err(AjoError::InvalidGroupState);
+        }
+
+        // Check if cycle has expired (past grace period)
+        let now = utils::get_current_timestamp(&env);
+        let grace_end = utils::get_grace_period_end(&group);
+        if now <= grace_end {
+            return Err(AjoError::CycleNotExpired);
+        }
+
+        // An open proposal blocks new ones until it fails
+        if let Some(mut latest) = storage::get_latest_refund_request(&env, group_id) {
+            if !latest.executed {
+                if now <= latest.voting_deadline || utils::is_refund_approved(&group, &latest) {
+                    return Err(AjoError::RefundRequestExists);
+                }
+                reject_refund_request(&env, &group, &mut latest);
+            }
+        }
+
+        // Create refund request
+        let proposal_id = storage::get_next_refund_proposal_id(&env, group_id);
+        let voting_deadline = now + utils::voting_period(&group);
+        let request = crate::types::RefundRequest {
+            group_id,
+            id: proposal_id,
+            requester: requester.clone(),
+            created_at: now,
+            voting_deadline,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            executed: false,
+            approved: false,
+        };
+
+        storage::store_refund_request(&env, &request);
+
+        // Emit event
+        events::emit_refund_requested(&env, group_id, proposal_id, &requester, voting_deadline);
+
+        Ok(proposal_id)
+    }
+
+    /// Vote on a group's open refund proposal.
+    ///
+    /// Members can vote for or against the proposal, or abstain, during
+    /// the voting period. Abstaining counts towards the group's quorum
+    /// without taking a side. A member can change their vote until the
+    /// voting deadline.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `voter` - Address of the voting member
+    /// * `group_id` - The unique group identifier
+    /// * `choice` - For, against or abstain
+    ///
+    /// # Returns
+    /// `Ok(())` on successful vote
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `NotMember` - If the voter is not a member
+    /// * `NoRefundRequest` - If no refund request exists
+    /// * `AlreadyVoted` - If the member already cast the same vote
+    /// * `VotingPeriodEnded` - If the voting period has ended
+    pub fn vote_refund(
+        env: Env,
+        voter: Address,
+        group_id: u64,
+        choice: VoteChoice,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        voter.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // Verify member
+        if !utils::is_member(&group.members, &voter) {
+            return Err(AjoError::NotMember);
+        }
+
+        // Get refund request
+        let mut request = storage::get_latest_refund_request(&env, group_id)
+            .ok_or(AjoError::NoRefundRequest)?;
+
+        // Check voting period
+        let now = utils::get_current_timestamp(&env);
+        if now > request.voting_deadline {
+            return Err(AjoError::VotingPeriodEnded);
+        }
+
+        // A changed vote first takes back the previous one
+        if let Some(previous) = storage::get_refund_vote(&env, group_id, request.id, &voter) {
+            if previous.choice == choice {
+                return Err(AjoError::AlreadyVoted);
+            }
+            match previous.choice {
+                VoteChoice::For => request.votes_for -= 1,
+                VoteChoice::Against => request.votes_against -= 1,
+                VoteChoice::Abstain => request.votes_abstain -= 1,
+            }
+        }
+
+        // Record vote
+        let vote = crate::types::RefundVote {
+            group_id,
+            proposal_id: request.id,
+            voter: voter.clone(),
+            choice,
+            timestamp: now,
+        };
+        storage::store_refund_vote(&env, group_id, request.id, &voter, &vote);
+
+        // Update vote counts
+        match choice {
+            VoteChoice::For => request.votes_for += 1,
+            VoteChoice::Against => request.votes_against += 1,
+            VoteChoice::Abstain => request.votes_abstain += 1,
+        }
+        storage::store_refund_request(&env, &request);
+
+        // Emit event
+        events::emit_refund_vote(&env, group_id, request.id, &voter, choice as u32);
+
+        Ok(())
+    }
+
+    /// Execute the group's latest refund proposal after its voting period ends.
+    ///
+    /// Can be called by any member after the voting period ends. The refund
+    /// is approved if the share of members who voted meets the group's
+    /// quorum and enough of the for and against votes are in favour to meet
+    /// its approval threshold (see `GovernanceSettings`). If so, the group is
+    /// settled: collateral and unused prepayments are released, then the rest of the group's escrow is
+    /// shared between the members in proportion to their net position, i.e.
+    /// everything they paid in contributions and penalties across all cycles
+    /// minus the payouts they received. Members who already received more
+    /// than they paid get nothing back. Refunds are credited to claimable
+    /// balances, and each member's `RefundRecord` holds the breakdown.
+    /// Otherwise the proposal is closed as rejected and its votes cleared.
+    /// A proposal still open when the group is cancelled or completes is
+    /// closed as rejected at that point instead.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `executor` - Address executing the refund
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// `true` if the refund was approved and the group settled, `false` if
+    /// the proposal was rejected
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `GroupCancelled` - If the group was cancelled while the vote was open
+    /// * `GroupComplete` - If the group completed while the vote was open
+    /// * `NoRefundRequest` - If no refund request exists
+    /// * `VotingPeriodActive` - If the voting period hasn't ended
+    /// * `RefundAlreadyExecuted` - If the refund has already been executed
+    /// * `InsufficientContractBalance` - If the group's escrow can't cover the refunds
+    pub fn execute_refund(env: Env, executor: Address, group_id: u64) -> Result<bool, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        executor.require_auth();
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // A group that has already ended some other way can't be refunded
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+
+        let mut request = storage::get_latest_refund_request(&env, group_id)
+            .ok_or(AjoError::NoRefundRequest)?;
+
+        // Check if already executed
+        if request.executed {
+            return Err(AjoError::RefundAlreadyExecuted);
+        }
+
+        // Check voting period ended
+        let now = utils::get_current_timestamp(&env);
+        if now <= request.voting_deadline {
+            return Err(AjoError::VotingPeriodActive);
+        }
+
+        // A proposal that wasn't approved is closed as rejected
+        if !utils::is_refund_approved(&group, &request) {
+            reject_refund_request(&env, &group, &mut request);
+            return Ok(false);
+        }
+
+        refund_group(&env, &mut group, &mut request, now, 0)?;
+
+        Ok(true)
+    }
+
+    /// Emergency refund by admin.
+    ///
+    /// Allows the contract admin to force a refund in case of disputes or emergencies.
+    /// The group is settled by net position, as with `execute_refund`, and
+    /// everything is credited to members' claimable balances.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `admin` - Address of the contract admin
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// `Ok(())` on successful emergency refund and token transfers
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the caller is not the admin
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `GroupCancelled` - If the group is already cancelled
+    /// * `InsufficientContractBalance` - If the group's escrow can't cover the refunds
+    pub fn emergency_refund(env: Env, admin: Address, group_id: u64) -> Result<(), AjoError> {
+        admin.require_auth();
+
+        // Verify admin
+        let stored_admin = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(AjoError::Unauthorized);
+        }
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        // Check if already cancelled
+        if group.state == crate::types::GroupState::Cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+
+        // Settle every member's net position out of the group's escrow
+        let now = utils::get_current_timestamp(&env);
+        let total_refunded = settle_group(
+            &env,
+            &group,
+            crate::types::RefundReason::EmergencyRefund,
+            now,
+            0,
+        )?;
+        close_refund_request(&env, &group);
+
+        // Update group state
+        group.state = crate::types::GroupState::Cancelled;
+        storage::store_group(&env, group_id, &group);
+
+        // Emit emergency refund event
+        events::emit_emergency_refund(&env, group_id, &admin, total_refunded);
+
+        Ok(())
+    }
+
+    /// Get a member's running position in a group.
+    ///
+    /// The position tracks the contributions and penalties the member has
+    /// paid and the payouts they have received, and decides their share if
+    /// the group is refunded.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The member's position
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_member_position(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<MemberPosition, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_member_position(&env, group_id, &member))
+    }
+
+    /// Get a group's latest refund proposal.
+    ///
+    /// Returns the open refund proposal, or the most recent one if none is
+    /// open.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// The refund request
+    ///
+    /// # Errors
+    /// * `NoRefundRequest` - If no refund request exists
+    pub fn get_refund_request(
+        env: Env,
+        group_id: u64,
+    ) -> Result<crate::types::RefundRequest, AjoError> {
+        storage::get_latest_refund_request(&env, group_id).ok_or(AjoError::NoRefundRequest)
+    }
+
+    /// Get one of a group's refund proposals by ID.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `proposal_id` - The proposal ID returned by `request_refund`
+    ///
+    /// # Returns
+    /// The refund request
+    ///
+    /// # Errors
+    /// * `NoRefundRequest` - If the proposal doesn't exist
+    pub fn get_refund_proposal(
+        env: Env,
+        group_id: u64,
+        proposal_id: u64,
+    ) -> Result<crate::types::RefundRequest, AjoError> {
+        storage::get_refund_request(&env, group_id, proposal_id).ok_or(AjoError::NoRefundRequest)
+    }
+
+    /// List every refund proposal made for a group, oldest first.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// The group's refund proposals, including rejected and executed ones
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    pub fn list_refund_proposals(
+        env: Env,
+        group_id: u64,
+    ) -> Result<Vec<crate::types::RefundRequest>, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        let mut proposals = Vec::new(&env);
+        for proposal_id in 1..=storage::get_refund_proposal_count(&env, group_id) {
+            if let Some(request) = storage::get_refund_request(&env, group_id, proposal_id) {
+                proposals.push_back(request);
+            }
+        }
+        Ok(proposals)
+    }
+
+    /// Get a member's vote on a refund proposal.
+    ///
+    /// Votes on rejected proposals are removed once the proposal is closed;
+    /// only its totals are kept.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `proposal_id` - The proposal ID
+    /// * `member` - Address of the member
+    ///
+    /// # Returns
+    /// The member's current vote
+    ///
+    /// # Errors
+    /// * `RecordNotFound` - If the member hasn't voted
+    pub fn get_refund_vote(
+        env: Env,
+        group_id: u64,
+        proposal_id: u64,
+        member: Address,
+    ) -> Result<crate::types::RefundVote, AjoError> {
+        storage::get_refund_vote(&env, group_id, proposal_id, &member)
+            .ok_or(AjoError::RecordNotFound)
+    }
+
+    /// Propose a change to a group for members to vote on.
+    ///
+    /// Any member of a running group can propose changing the grace period,
+    /// penalty rate, cycle duration or the order of unpaid slots, removing a
+    /// member, or replacing the creator (see [`ProposalAction`]). Voting is
+    /// open for the group's voting period, and the proposal needs the
+    /// group's quorum plus the threshold for its action to pass (see
+    /// `GovernanceSettings`). Several proposals can be open at once.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `proposer` - Address of the member making the proposal (must authenticate)
+    /// * `group_id` - The unique group identifier
+    /// * `action` - The change to apply if the proposal passes
+    ///
+    /// # Returns
+    /// The ID of the new proposal
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all payouts
+    /// * `InvalidGroupState` - If the group hasn't started
+    /// * `NotMember` - If the proposer, or the member the action names, is not a member
+    /// * `InvalidGracePeriod` - If a new grace period is longer than 7 days
+    /// * `InvalidPenaltyRate` - If a new penalty rate is above 100
+    /// * `CycleDurationZero` - If a new cycle duration is 0 or outside the allowed range
+    /// * `InvalidPayoutOrder` - If a new order isn't a permutation of the unpaid slots
+    /// * `Unauthorized` - If the proposal would remove the creator
+    /// * `AlreadyReceivedPayout` - If the member to remove has been paid out
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        group_id: u64,
+        action: ProposalAction,
+    ) -> Result<u64, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        proposer.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        ensure_governable(&group)?;
+        if !utils::is_member(&group.members, &proposer) {
+            return Err(AjoError::NotMember);
+        }
+        validate_action(&env, &group, &action)?;
+
+        let now = utils::get_current_timestamp(&env);
+        let proposal_id = storage::get_next_proposal_id(&env, group_id);
+        let voting_deadline = now + utils::voting_period(&group);
+        let proposal = Proposal {
+            group_id,
+            id: proposal_id,
+            proposer: proposer.clone(),
+            action,
+            created_at: now,
+            voting_deadline,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            voters: Vec::new(&env),
+            executed: false,
+            approved: false,
+        };
+        storage::store_proposal(&env, &proposal);
+
+        events::emit_proposal_created(&env, group_id, proposal_id, &proposer, voting_deadline);
+
+        Ok(proposal_id)
+    }
+
+    /// Vote on a governance proposal.
+    ///
+    /// Works like `vote_refund`: members can vote for or against, or
+    /// abstain, and can change their vote until the voting deadline.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `voter` - Address of the voting member (must authenticate)
+    /// * `group_id` - The unique group identifier
+    /// * `proposal_id` - The proposal to vote on
+    /// * `choice` - For, against or abstain
+    ///
+    /// # Returns
+    /// `Ok(())` on successful vote
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `NotMember` - If the voter is not a member
+    /// * `RecordNotFound` - If the proposal doesn't exist
+    /// * `AlreadyVoted` - If the member already cast the same vote
+    /// * `VotingPeriodEnded` - If the voting period has ended
+    pub fn vote(
+        env: Env,
+        voter: Address,
+        group_id: u64,
+        proposal_id: u64,
+        choice: VoteChoice,
+    ) -> Result<(), AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        voter.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        if !utils::is_member(&group.members, &voter) {
+            return Err(AjoError::NotMember);
+        }
+
+        let mut proposal =
+            storage::get_proposal(&env, group_id, proposal_id).ok_or(AjoError::RecordNotFound)?;
+
+        let now = utils::get_current_timestamp(&env);
+        if proposal.executed || now > proposal.voting_deadline {
+            return Err(AjoError::VotingPeriodEnded);
+        }
+
+        // A changed vote first takes back the previous one
+        if let Some(previous) = storage::get_proposal_vote(&env, group_id, proposal_id, &voter) {
+            if previous.choice == choice {
+                return Err(AjoError::AlreadyVoted);
+            }
+            match previous.choice {
+                VoteChoice::For => proposal.votes_for -= 1,
+                VoteChoice::Against => proposal.votes_against -= 1,
+                VoteChoice::Abstain => proposal.votes_abstain -= 1,
+            }
+        } else {
+            proposal.voters.push_back(voter.clone());
+        }
+
+        storage::store_proposal_vote(
+            &env,
+            &ProposalVote {
+                group_id,
+                proposal_id,
+                voter: voter.clone(),
+                choice,
+                timestamp: now,
+            },
+        );
+
+        match choice {
+            VoteChoice::For => proposal.votes_for += 1,
+            VoteChoice::Against => proposal.votes_against += 1,
+            VoteChoice::Abstain => proposal.votes_abstain += 1,
+        }
+        storage::store_proposal(&env, &proposal);
+
+        events::emit_proposal_vote(&env, group_id, proposal_id, &voter, choice as u32);
+
+        Ok(())
+    }
+
+    /// Execute a governance proposal after its voting period ends.
+    ///
+    /// Anyone can execute a proposal once voting has ended. If it passed,
+    /// its action is applied; either way the proposal is closed, and the
+    /// individual votes are removed while the proposal keeps the totals.
+    /// A member removed by proposal leaves as with an approved exit.
+    ///
+    /// A proposal that passed but can no longer be applied, e.g. because the
+    /// member it removes has already left, is closed as not approved.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `executor` - Address executing the proposal
+    /// * `group_id` - The unique group identifier
+    /// * `proposal_id` - The proposal to execute
+    ///
+    /// # Returns
+    /// `true` if the proposal passed and was applied, `false` if it was rejected
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    /// * `RecordNotFound` - If the proposal doesn't exist
+    /// * `RefundAlreadyExecuted` - If the proposal has already been executed
+    /// * `VotingPeriodActive` - If the voting period hasn't ended
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all payouts
+    pub fn execute_proposal(
+        env: Env,
+        executor: Address,
+        group_id: u64,
+        proposal_id: u64,
+    ) -> Result<bool, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        executor.require_auth();
+
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        let mut proposal =
+            storage::get_proposal(&env, group_id, proposal_id).ok_or(AjoError::RecordNotFound)?;
+
+        if proposal.executed {
+            return Err(AjoError::RefundAlreadyExecuted);
+        }
+
+        let now = utils::get_current_timestamp(&env);
+        if now <= proposal.voting_deadline {
+            return Err(AjoError::VotingPeriodActive);
+        }
+
+        ensure_governable(&group)?;
+
+        // Votes are only needed while the proposal is open
+        for voter in proposal.voters.iter() {
+            storage::remove_proposal_vote(&env, group_id, proposal_id, &voter);
+        }
+
+        let approved = utils::is_proposal_approved(&group, &proposal)
+            && validate_action(&env, &group, &proposal.action).is_ok();
+        if approved {
+            apply_action(&env, &mut group, &proposal.action)?;
+        }
+
+        proposal.executed = true;
+        proposal.approved = approved;
+        storage::store_proposal(&env, &proposal);
+
+        events::emit_proposal_executed(&env, group_id, proposal_id, approved);
+
+        Ok(approved)
+    }
+
+    /// Get one of a group's governance proposals.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `proposal_id` - The proposal ID returned by `propose`
+    ///
+    /// # Returns
+    /// The proposal
+    ///
+    /// # Errors
+    /// * `RecordNotFound` - If the proposal doesn't exist
+    pub fn get_proposal(env: Env, group_id: u64, proposal_id: u64) -> Result<Proposal, AjoError> {
+        storage::get_proposal(&env, group_id, proposal_id).ok_or(AjoError::RecordNotFound)
+    }
+
+    /// List every governance proposal made for a group, oldest first.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// The group's proposals, open and closed
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist
+    pub fn list_proposals(env: Env, group_id: u64) -> Result<Vec<Proposal>, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        let mut proposals = Vec::new(&env);
+        for proposal_id in 1..=storage::get_proposal_count(&env, group_id) {
+            if let Some(proposal) = storage::get_proposal(&env, group_id, proposal_id) {
+                proposals.push_back(proposal);
+            }
+        }
+        Ok(proposals)
+    }
+
+    /// Get refund record for a member.
+    ///
+    /// Returns the refund record, with the breakdown of the member's net
+    /// position, once the group has been refunded.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The refund record
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If no refund record exists
+    pub fn get_refund_record(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<crate::types::RefundRecord, AjoError> {
+        storage::get_refund_record(&env, group_id, &member).ok_or(AjoError::GroupNotFound)
+    }
+
+    /// Get the payout record for a group's cycle.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `cycle` - The cycle number
+    ///
+    /// # Returns
+    /// The `PayoutRecord` for that cycle, including any auction discount
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NoPayoutRecord` - If no payout has been made for that cycle
+    pub fn get_payout_record(
+        env: Env,
+        group_id: u64,
+        cycle: u32,
+    ) -> Result<PayoutRecord, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        storage::get_payout_record(&env, group_id, cycle).ok_or(AjoError::NoPayoutRecord)
+    }
+
+    /// Get the record of a completed round of a multi-round group.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `round` - The round number
+    ///
+    /// # Returns
+    /// The `RoundRecord`, giving the round's cycles and payout order
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `RecordNotFound` - If the round hasn't completed
+    pub fn get_round_record(env: Env, group_id: u64, round: u32) -> Result<RoundRecord, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        storage::get_round_record(&env, group_id, round).ok_or(AjoError::RecordNotFound)
+    }
+
+    /// Get the balance a member can withdraw from a group with `claim`.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The claimable amount in stroops
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_claimable_balance(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<i128, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_claimable(&env, group_id, &member))
+    }
+
+    /// Get the collateral a member currently has locked in a group.
+    ///
+    /// Collateral is taken when a member joins (or requests to join) a group
+    /// with a `collateral_amount`, reduced when it covers a missed
+    /// contribution, and released once the group completes.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    /// * `member` - The member's address
+    ///
+    /// # Returns
+    /// The locked amount in stroops
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_member_collateral(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<i128, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_collateral(&env, group_id, &member))
+    }
+
+    /// Withdraw a member's claimable balance from a group.
+    ///
+    /// Payouts, refunds, auction discount shares, exit refunds and released
+    /// collateral are all credited to claimable balances rather than pushed
+    /// to members, so one account that can't receive tokens doesn't block
+    /// everyone else.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `member` - Address of the member (must authenticate)
+    /// * `group_id` - The group to claim from
+    ///
+    /// # Returns
+    /// The amount transferred
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NothingToClaim` - If the member has no claimable balance
+    /// * `InsufficientContractBalance` - If the group's escrow doesn't hold enough tokens
+    /// * `TransferFailed` - If the token transfer fails
+    pub fn claim(env: Env, member: Address, group_id: u64) -> Result<i128, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        member.require_auth();
+
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+
+        let amount = storage::get_claimable(&env, group_id, &member);
+        if amount <= 0 {
+            return Err(AjoError::NothingToClaim);
+        }
+
+        // Clear the balance before transferring
+        storage::remove_claimable(&env, group_id, &member);
+
+        let contract_address = env.current_contract_address();
+        withdraw_from_escrow(&env, &group, amount)?;
+        crate::token::transfer_token(
+            &env,
+            &group.token_address,
+            &contract_address,
+            &member,
+            amount,
+        )?;
+
+        events::emit_claimed(&env, group_id, &member, amount);
+
+        Ok(amount)
+    }
+
+    /// Withdraw a member's claimable balances from every group at once.
+    ///
+    /// Balances are totalled per token, so each token is transferred once.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `member` - Address of the member (must authenticate)
+    ///
+    /// # Returns
+    /// The amount transferred for each token address
+    ///
+    /// # Errors
+    /// * `NothingToClaim` - If the member has no claimable balance in any group
+    /// * `InsufficientContractBalance` - If a group's escrow doesn't hold enough tokens
+    /// * `TransferFailed` - If a token transfer fails
+    pub fn claim_all(env: Env, member: Address) -> Result<Map<Address, i128>, AjoError> {
+        pausable::ensure_not_paused(&env)?;
+        member.require_auth();
+
+        let mut totals: Map<Address, i128> = Map::new(&env);
+        for group_id in storage::get_claimable_groups(&env, &member).iter() {
+            let amount = storage::get_claimable(&env, group_id, &member);
+            storage::remove_claimable(&env, group_id, &member);
+            if amount <= 0 {
+                continue;
+            }
+
+            let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+            withdraw_from_escrow(&env, &group, amount)?;
+            let total = totals.get(group.token_address.clone()).unwrap_or(0);
+            totals.set(group.token_address, total + amount);
+
+            events::emit_claimed(&env, group_id, &member, amount);
+        }
+
+        if totals.is_empty() {
+            return Err(AjoError::NothingToClaim);
+        }
+
+        let contract_address = env.current_contract_address();
+        for (token_address, amount) in totals.iter() {
+            crate::token::transfer_token(&env, &token_address, &contract_address, &member, amount)?;
+        }
+
+        Ok(totals)
+    }
+
+    /// Extend the storage TTL of the live entries belonging to a group.
+    ///
+    /// Group data lives in persistent storage, which is archived once its TTL
+    /// runs out. TTLs are extended automatically whenever entries are read or
+    /// written, but a group that sits idle (e.g. during a long cycle) can be kept
+    /// alive by calling this function. Entries are extended to cover the group's
+    /// remaining cycles plus a retention window.
+    ///
+    /// Members' entries for the current round are extended a batch at a time
+    /// so a single call stays within the network's footprint limits. Start with
+    /// a `cursor` of `0` and keep calling with the returned cursor until it is
+    /// `None`. Group-wide entries are extended on every call.
+    ///
+    /// Anyone can call this function; it does not modify any group state and
+    /// remains available while the contract is paused.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The unique group identifier
+    /// * `cursor` - Where to resume, `0` for the first call
+    ///
+    /// # Returns
+    /// The cursor for the next call, or `None` once every batch has been extended
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn bump_group(env: Env, group_id: u64, cursor: u32) -> Result<Option<u32>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        let next = storage::bump_group(&env, &group, cursor);
+        storage::extend_instance_ttl(&env);
+        Ok(next)
+    }
+
+    /// Get the escrow ledger of the tokens the contract holds for a group.
+    ///
+    /// Shows what the group has paid in as contributions, collateral and
+    /// penalties, what it has credited to members as payouts and refunds, and
+    /// what members have withdrawn. Payouts and refunds are only credited out
+    /// of the group's own escrow, never from another group's deposits.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `group_id` - The group
+    ///
+    /// # Returns
+    /// The group's escrow ledger
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `RecordNotFound` - If the group was created before escrow was tracked
+    pub fn get_group_escrow(env: Env, group_id: u64) -> Result<GroupEscrow, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        storage::get_escrow(&env, group_id).ok_or(AjoError::RecordNotFound)
+    }
+
+    /// Get the contract's token balance for a specific token.
+    ///
+    /// Returns the amount of tokens held by the contract for a given token address.
+    /// Useful for checking if the contract has sufficient funds for payouts.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban contract environment
+    /// * `token_address` - Address of the token contract
+    ///
+    /// # Returns
+    /// The token balance held by the contract
+    pub fn get_contract_balance(env: Env, token_address: Address) -> i128 {
+        let contract_address = env.current_contract_address();
+        crate::token::get_balance(&env, &token_address, &contract_address)
+    }
+}
+
+/// Checks a parameter set with the same rules as `create_group`.
+fn validate_params(params: &GroupParams) -> Result<(), AjoError> {
+    utils::validate_group_params(
+        params.contribution_amount,
+        params.cycle_duration,
+        params.max_members,
+    )?;
+    utils::validate_penalty_params(params.grace_period, params.penalty_rate)?;
+    if params.max_members < params.settings.min_members {
+        return Err(AjoError::MaxMembersBelowMinimum);
+    }
+    if params.settings.collateral_amount < 0 || params.settings.keeper_fee < 0 {
+        return Err(AjoError::ContributionAmountNegative);
+    }
+    utils::validate_governance_params(&params.settings.governance)?;
+    Ok(())
+}
+
+/// Creates a forming group with `creator` as its first member.
+///
+/// Shared by `create_group`, `create_group_from_template` and `clone_group`
+/// once the parameters have been validated and the creator authenticated.
+/// Stores the group, emits the created event and locks the creator's
+/// collateral.
+fn open_group(env: &Env, creator: &Address, params: GroupParams) -> Result<u64, AjoError> {
+    // Generate new group ID
+    let group_id = storage::get_next_group_id(env);
+
+    // Initialize members list and payout order with creator
+    let mut members = Vec::new(env);
+    members.push_back(creator.clone());
+    let payout_order = members.clone();
+
+    // Get current timestamp
+    let now = utils::get_current_timestamp(env);
+
+    // Create group
+    let group = Group {
+        id: group_id,
+        creator: creator.clone(),
+        token_address: params.token_address,
+        contribution_amount: params.contribution_amount,
+        cycle_duration: params.cycle_duration,
+        max_members: params.max_members,
+        members,
+        payout_order,
+        current_cycle: 1,
+        payout_index: 0,
+        round: 1,
+        round_start_cycle: 1,
+        created_at: now,
+        cycle_start_time: now,
+        is_complete: false,
+        grace_period: params.grace_period,
+        penalty_rate: params.penalty_rate,
+        state: crate::types::GroupState::Forming,
+        payout_strategy: params.payout_strategy,
+        settings: params.settings,
+    };
+
+    // Store group
+    storage::store_group(env, group_id, &group);
+    storage::store_escrow(
+        env,
+        &GroupEscrow {
+            group_id,
+            deposits: 0,
+            penalties: 0,
+            payouts: 0,
+            refunds: 0,
+            withdrawn: 0,
+        },
+    );
+
+    // Emit event
+    events::emit_group_created(
+        env,
+        group_id,
+        creator,
+        group.contribution_amount,
+        group.max_members,
+    );
+
+    // The creator is a member too, so they lock collateral like everyone else
+    lock_collateral(env, &group, creator)?;
+
+    Ok(group_id)
+}
+
+/// Adds a member to a group, queued at the end of the payout order.
+///
+/// Shared by `join_group` and `approve_join` once the join policy has been
+/// satisfied. An existing member is given another slot instead. Starts the
+/// group if it is now full, stores the group and emits the joined event.
+fn add_member(env: &Env, group: &mut Group, member: &Address) -> Result<(), AjoError> {
+    // Check if group is complete
+    if group.is_complete {
+        return Err(AjoError::GroupComplete);
+    }
+
+    // Check if group is cancelled
+    if group.state == crate::types::GroupState::Cancelled {
+        return Err(AjoError::GroupCancelled);
+    }
+
+    // Members may only hold up to the group's slot limit
+    let slots = utils::slot_count(group, member);
+    if slots >= utils::max_slots_per_member(group) {
+        return Err(AjoError::AlreadyMember);
+    }
+
+    // Prepaid cycles were paid at the member's current contribution
+    if slots > 0 && storage::get_prepaid_cycles(env, group.id, member) > 0 {
+        return Err(AjoError::AlreadyContributed);
+    }
+
+    // Check if group is full
+    if group.payout_order.len() >= group.max_members {
+        return Err(AjoError::MaxMembersExceeded);
+    }
+
+    // Membership is locked once the group starts
+    if group.state != crate::types::GroupState::Forming {
+        return Err(AjoError::InvalidGroupState);
+    }
+
+    // Add the slot, queued at the end of the payout order
+    group.payout_order.push_back(member.clone());
+    if slots == 0 {
+        group.members.push_back(member.clone());
+        events::emit_member_joined(env, group.id, member);
+    } else {
+        events::emit_slot_added(env, group.id, member, slots + 1);
+    }
+
+    // Full groups start automatically
+    if group.payout_order.len() == group.max_members {
+        activate_group(env, group);
+    }
+
+    // Update storage
+    storage::store_group(env, group.id, group);
+
+    Ok(())
+}
+
+/// Moves a forming group to `Active` and starts its first cycle now.
+///
+/// Membership is locked from here on, so [`PayoutStrategy::Randomized`]
+/// groups have their payout order shuffled. The caller stores the group.
+fn activate_group(env: &Env, group: &mut Group) {
+    group.state = crate::types::GroupState::Active;
+    group.cycle_start_time = utils::get_current_timestamp(env);
+
+    if group.payout_strategy == PayoutStrategy::Randomized {
+        utils::shuffle_unpaid_payout_order(env, group);
+    }
+
+    events::emit_group_started(
+        env,
+        group.id,
+        group.members.len(),
+        group.cycle_start_time,
+    );
+
+    apply_prepayments(env, group);
+}
+
+/// Locks the group's collateral for each of `member`'s slots, or for the one
+/// slot an applicant is asking for, topping up anything already held.
+fn lock_collateral(env: &Env, group: &Group, member: &Address) -> Result<(), AjoError> {
+    let locked = storage::get_collateral(env, group.id, member);
+    let slots = utils::slot_count(group, member).max(1);
+    let required = group.settings.collateral_amount * slots as i128;
+    let amount = required - locked;
+    if amount <= 0 {
+        return Ok(());
+    }
+
+    crate::token::check_balance(env, &group.token_address, member, amount)?;
+    crate::token::transfer_token(
+        env,
+        &group.token_address,
+        member,
+        &env.current_contract_address(),
+        amount,
+    )?;
+    deposit_to_escrow(env, group.id, amount, 0);
+    storage::store_collateral(env, group.id, member, locked + amount);
+
+    events::emit_collateral_locked(env, group.id, member, amount);
+
+    Ok(())
+}
+
+/// Closes the group's current round once every slot has been paid out.
+///
+/// Records the round's history, then either starts the next round with the
+/// same members, or after the last round completes the group and releases
+/// all collateral and unused prepayments. `last_cycle` is the cycle whose
+/// payout finished the round. Returns `true` if another round started.
+fn finish_round(env: &Env, group: &mut Group, last_cycle: u32) -> Result<bool, AjoError> {
+    storage::store_round_record(
+        env,
+        group.id,
+        &RoundRecord {
+            group_id: group.id,
+            round: group.round,
+            first_cycle: group.round_start_cycle,
+            last_cycle,
+            payout_order: group.payout_order.clone(),
+            completed_at: utils::get_current_timestamp(env),
+        },
+    );
+
+    if group.round >= utils::total_rounds(group) {
+        group.is_complete = true;
+        events::emit_group_completed(env, group.id);
+        close_refund_request(env, group);
+
+        // Every cycle was paid for, so collateral goes back to its owners
+        release_all_collateral(env, group)?;
+        release_all_prepayments(env, group)?;
+        return Ok(false);
+    }
+
+    group.round += 1;
+    group.round_start_cycle = last_cycle + 1;
+    group.payout_index = 0;
+    for member in group.members.iter() {
+        storage::clear_payout_received(env, group.id, &member);
+    }
+    if group.payout_strategy == PayoutStrategy::Randomized {
+        utils::shuffle_unpaid_payout_order(env, group);
+    }
+
+    events::emit_round_started(env, group.id, group.round, group.round_start_cycle);
+
+    Ok(true)
+}
+
+/// What an amount credited out of a group's escrow is for.
+enum EscrowCredit {
+    Payout,
+    Refund,
+}
+
+/// Records `amount` paid into a group's escrow, of which `penalty` is a late
+/// penalty.
+fn deposit_to_escrow(env: &Env, group_id: u64, amount: i128, penalty: i128) {
+    if let Some(mut escrow) = storage::get_escrow(env, group_id) {
+        escrow.deposits += amount - penalty;
+        escrow.penalties += penalty;
+        storage::store_escrow(env, &escrow);
+    }
+}
+
+/// Credits `amount` out of a group's escrow to a member's claimable balance.
+///
+/// Fails with `InsufficientContractBalance` if the group hasn't paid in
+/// enough to cover it. Groups created before escrow was tracked have no
+/// ledger and are credited unchecked.
+fn credit_from_escrow(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    amount: i128,
+    credit: EscrowCredit,
+) -> Result<(), AjoError> {
+    if let Some(mut escrow) = storage::get_escrow(env, group_id) {
+        if amount > escrow.available() {
+            return Err(AjoError::InsufficientContractBalance);
+        }
+        match credit {
+            EscrowCredit::Payout => escrow.payouts += amount,
+            EscrowCredit::Refund => escrow.refunds += amount,
+        }
+        storage::store_escrow(env, &escrow);
+    }
+
+    storage::add_claimable(env, group_id, member, amount);
+
+    Ok(())
+}
+
+/// Fails with `InsufficientContractBalance` unless a group's escrow can cover
+/// `amount` in credits, so a step that credits several members can check up
+/// front instead of failing after some of them were credited.
+fn ensure_escrow_covers(env: &Env, group_id: u64, amount: i128) -> Result<(), AjoError> {
+    match storage::get_escrow(env, group_id) {
+        Some(escrow) if amount > escrow.available() => Err(AjoError::InsufficientContractBalance),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the collateral and unused prepayments held for a group's members
+/// and pending applicants, which are released when the group finishes or is
+/// cancelled.
+fn locked_funds(env: &Env, group: &Group) -> i128 {
+    let mut total = 0i128;
+    for member in group.members.iter() {
+        total += storage::get_collateral(env, group.id, &member);
+        total += utils::contribution_due(env, group, &member)
+            * storage::get_prepaid_cycles(env, group.id, &member) as i128;
+    }
+    for request in storage::get_join_requests(env, group.id).iter() {
+        if !utils::is_member(&group.members, &request.member) {
+            total += storage::get_collateral(env, group.id, &request.member);
+        }
+    }
+    total
+}
+
+/// Returns everything paying out the current cycle credits from the group's
+/// escrow: the pot and its penalty pool, including any auction discount
+/// shares and keeper fee taken from them, and the collateral and prepayments
+/// released if the payout completes the group.
+fn payout_credits(env: &Env, group: &Group) -> i128 {
+    let mut total = utils::calculate_payout_amount(env, group) - utils::cycle_shortfall(env, group)
+        + storage::get_cycle_penalty_pool(env, group.id, group.current_cycle);
+    if group.payout_index + 1 >= group.payout_order.len()
+        && group.round >= utils::total_rounds(group)
+    {
+        total += locked_funds(env, group);
+    }
+    total
+}
+
+/// Records `amount` of a group's credited funds leaving the contract.
+///
+/// Groups created before escrow was tracked fall back to checking the
+/// contract's total balance of the token.
+fn withdraw_from_escrow(env: &Env, group: &Group, amount: i128) -> Result<(), AjoError> {
+    match storage::get_escrow(env, group.id) {
+        Some(mut escrow) => {
+            if amount > escrow.balance() {
+                return Err(AjoError::InsufficientContractBalance);
+            }
+            escrow.withdrawn += amount;
+            storage::store_escrow(env, &escrow);
+            Ok(())
+        }
+        None => crate::token::check_contract_balance(
+            env,
+            &group.token_address,
+            &env.current_contract_address(),
+            amount,
+        ),
+    }
+}
+
+/// Moves whatever collateral `member` still has locked into their claimable
+/// balance. Returns the amount released.
+fn release_collateral(env: &Env, group_id: u64, member: &Address) -> Result<i128, AjoError> {
+    let amount = storage::get_collateral(env, group_id, member);
+    if amount <= 0 {
+        return Ok(0);
+    }
+
+    storage::remove_collateral(env, group_id, member);
+    credit_from_escrow(env, group_id, member, amount, EscrowCredit::Refund)?;
+
+    events::emit_collateral_released(env, group_id, member, amount);
+
+    Ok(amount)
+}
+
+/// Releases the collateral an applicant locked with a join request that is
+/// closed without adding a slot. Collateral backing slots the address already
+/// holds stays locked.
+fn release_request_collateral(env: &Env, group: &Group, member: &Address) -> Result<(), AjoError> {
+    let locked = storage::get_collateral(env, group.id, member);
+    let required = group.settings.collateral_amount * utils::slot_count(group, member) as i128;
+    let amount = locked - required;
+    if amount <= 0 {
+        return Ok(());
+    }
+
+    if required > 0 {
+        storage::store_collateral(env, group.id, member, required);
+    } else {
+        storage::remove_collateral(env, group.id, member);
+    }
+    credit_from_escrow(env, group.id, member, amount, EscrowCredit::Refund)?;
+
+    events::emit_collateral_released(env, group.id, member, amount);
+
+    Ok(())
+}
+
+/// Releases the collateral of every member, and of every pending applicant,
+/// of a group that has finished or been cancelled.
+fn release_all_collateral(env: &Env, group: &Group) -> Result<(), AjoError> {
+    for member in group.members.iter() {
+        release_collateral(env, group.id, &member)?;
+    }
+    for request in storage::get_join_requests(env, group.id).iter() {
+        release_collateral(env, group.id, &request.member)?;
+    }
+
+    Ok(())
+}
+
+/// Records the current cycle's contribution for every member who prepaid it
+/// with `contribute_ahead`. Called whenever a cycle starts.
+fn apply_prepayments(env: &Env, group: &Group) {
+    let now = utils::get_current_timestamp(env);
+    for member in group.members.iter() {
+        let prepaid = storage::get_prepaid_cycles(env, group.id, &member);
+        if prepaid == 0 || storage::has_contributed(env, group.id, group.current_cycle, &member) {
+            continue;
+        }
+
+        record_contribution(env, group, &member, now, false, 0);
+
+        if prepaid == 1 {
+            storage::remove_prepaid_cycles(env, group.id, &member);
+        } else {
+            storage::store_prepaid_cycles(env, group.id, &member, prepaid - 1);
+        }
+    }
+}
+
+/// Moves the escrow for any cycles `member` prepaid but never used into their
+/// claimable balance. Returns the amount released.
+fn release_prepayment(env: &Env, group: &Group, member: &Address) -> Result<i128, AjoError> {
+    let prepaid = storage::get_prepaid_cycles(env, group.id, member);
+    if prepaid == 0 {
+        return Ok(0);
+    }
+
+    let amount = utils::contribution_due(env, group, member) * prepaid as i128;
+    storage::remove_prepaid_cycles(env, group.id, member);
+    credit_from_escrow(env, group.id, member, amount, EscrowCredit::Refund)?;
+
+    events::emit_prepayment_released(env, group.id, member, amount);
+
+    Ok(amount)
+}
+
+/// Releases every member's unused prepayments once a group has finished or
+/// been cancelled.
+fn release_all_prepayments(env: &Env, group: &Group) -> Result<(), AjoError> {
+    for member in group.members.iter() {
+        release_prepayment(env, group, &member)?;
+    }
+
+    Ok(())
+}
+
+/// Refunds what each member has paid towards the current cycle, releases all
+/// collateral and cancels the group after an approved refund vote. `reserved`
+/// is left in escrow for a keeper fee credited afterwards.
+fn refund_group(
+    env: &Env,
+    group: &mut Group,
+    request: &mut RefundRequest,
+    now: u64,
+    reserved: i128,
+) -> Result<(), AjoError> {
+    settle_group(
+        env,
+        group,
+        crate::types::RefundReason::MemberVote,
+        now,
+        reserved,
+    )?;
+
+    // Update request and group state
+    request.executed = true;
+    request.approved = true;
+    storage::store_refund_request(env, request);
+
+    group.state = crate::types::GroupState::Cancelled;
+    storage::store_group(env, group.id, group);
+
+    Ok(())
+}
+
+/// Removes a member from a group that has started, as an approved exit.
+///
+/// Their contribution to the current cycle hasn't been paid out yet, so it is
+/// credited back to their claimable balance along with any remaining
+/// collateral and prepaid cycles. Contributions to earlier cycles are
+/// forfeited. Stores the group.
+fn remove_started_member(env: &Env, group: &mut Group, member: &Address) -> Result<(), AjoError> {
+    let group_id = group.id;
+
+    // Refund the current cycle's contribution; earlier cycles were paid out
+    let current_cycle = group.current_cycle;
+    let penalty_paid = cycle_penalty_paid(env, group_id, current_cycle, member);
+    let mut refunded = utils::amount_paid(env, group, current_cycle, member);
+    if refunded > 0 {
+        storage::store_contribution(env, group_id, current_cycle, member, false);
+        storage::remove_partial_contribution(env, group_id, current_cycle, member);
+        credit_from_escrow(env, group_id, member, refunded, EscrowCredit::Refund)?;
+    }
+    let earlier_contributions = (1..current_cycle)
+        .filter(|cycle| storage::has_contributed(env, group_id, *cycle, member))
+        .count() as i128;
+    let forfeited = earlier_contributions * utils::contribution_due(env, group, member);
+
+    refunded += release_collateral(env, group_id, member)?;
+    refunded += release_prepayment(env, group, member)?;
+
+    utils::remove_member(group, member);
+    storage::remove_exit_request(env, group_id, member);
+    storage::remove_auto_contribute(env, group_id, member);
+    storage::remove_share_weight(env, group_id, member);
+    storage::remove_member_position(env, group_id, member);
+
+    // The member may have been the last one waiting for a payout this
+    // round; the current cycle then pays the next round's first recipient
+    if group.payout_index >= group.payout_order.len() {
+        let last_cycle = group.current_cycle - 1;
+        if !finish_round(env, group, last_cycle)? {
+            // The group is complete, so the current cycle is never paid out;
+            // everything paid towards it goes back to whoever paid it
+            if penalty_paid > 0 {
+                let pool = storage::get_cycle_penalty_pool(env, group_id, current_cycle);
+                storage::store_cycle_penalty_pool(
+                    env,
+                    group_id,
+                    current_cycle,
+                    pool - penalty_paid,
+                );
+                credit_from_escrow(env, group_id, member, penalty_paid, EscrowCredit::Refund)?;
+                refunded += penalty_paid;
+            }
+            for other in group.members.iter() {
+                refund_unpaid_cycle(env, group, current_cycle, &other)?;
+            }
+        }
+    }
+
+    storage::store_group(env, group_id, group);
+
+    events::emit_member_left(env, group_id, member, refunded, forfeited);
+
+    Ok(())
+}
+
+/// Returns the late penalty `member` paid with their completed contribution
+/// to `cycle`, if any.
+fn cycle_penalty_paid(env: &Env, group_id: u64, cycle: u32, member: &Address) -> i128 {
+    if !storage::has_contributed(env, group_id, cycle, member) {
+        return 0;
+    }
+    storage::get_contribution_detail(env, group_id, cycle, member)
+        .map_or(0, |record| record.penalty_amount)
+}
+
+/// Credits back everything `member` paid towards a cycle that will never be
+/// paid out, including any late penalty, and takes it off their position.
+fn refund_unpaid_cycle(
+    env: &Env,
+    group: &Group,
+    cycle: u32,
+    member: &Address,
+) -> Result<(), AjoError> {
+    let group_id = group.id;
+    let contributed = storage::has_contributed(env, group_id, cycle, member);
+    let penalty = cycle_penalty_paid(env, group_id, cycle, member);
+    let paid = utils::amount_paid(env, group, cycle, member);
+    if paid + penalty == 0 {
+        return Ok(());
+    }
+
+    storage::store_contribution(env, group_id, cycle, member, false);
+    storage::remove_partial_contribution(env, group_id, cycle, member);
+    if penalty > 0 {
+        let pool = storage::get_cycle_penalty_pool(env, group_id, cycle);
+        storage::store_cycle_penalty_pool(env, group_id, cycle, pool - penalty);
+    }
+    credit_from_escrow(env, group_id, member, paid + penalty, EscrowCredit::Refund)?;
+
+    // Installments only count towards the position once the contribution is complete
+    let mut position = storage::get_member_position(env, group_id, member);
+    if contributed {
+        position.contributed -= paid;
+    }
+    position.penalties -= penalty;
+    storage::store_member_position(env, group_id, member, &position);
+
+    Ok(())
+}
+
+/// Checks that a group is running, so members can govern it.
+fn ensure_governable(group: &Group) -> Result<(), AjoError> {
+    if group.state == crate::types::GroupState::Cancelled {
+        return Err(AjoError::GroupCancelled);
+    }
+    if group.is_complete {
+        return Err(AjoError::GroupComplete);
+    }
+    if group.state == crate::types::GroupState::Forming {
+        return Err(AjoError::InvalidGroupState);
+    }
+    Ok(())
+}
+
+/// Checks that a proposal's action can be applied to the group as it is now.
+fn validate_action(env: &Env, group: &Group, action: &ProposalAction) -> Result<(), AjoError> {
+    match action {
+        ProposalAction::SetGracePeriod(grace_period) => {
+            utils::validate_penalty_params(*grace_period, group.penalty_rate)
+        }
+        ProposalAction::SetPenaltyRate(penalty_rate) => {
+            utils::validate_penalty_params(group.grace_period, *penalty_rate)
+        }
+        ProposalAction::SetCycleDuration(cycle_duration) => {
+            crate::security::validate_cycle_duration(*cycle_duration)
+        }
+        ProposalAction::SetPayoutOrder(order) => {
+            let unpaid = group
+                .payout_order
+                .slice(group.payout_index..group.payout_order.len());
+            if !utils::is_valid_payout_order(&unpaid, order) {
+                return Err(AjoError::InvalidPayoutOrder);
+            }
+            Ok(())
+        }
+        ProposalAction::RemoveMember(member) => {
+            if !utils::is_member(&group.members, member) {
+                return Err(AjoError::NotMember);
+            }
+            if group.creator == *member {
+                return Err(AjoError::Unauthorized);
+            }
+            if storage::has_received_payout(env, group.id, member) {
+                return Err(AjoError::AlreadyReceivedPayout);
+            }
+            Ok(())
+        }
+        ProposalAction::ReplaceCreator(member) => {
+            if !utils::is_member(&group.members, member) {
+                return Err(AjoError::NotMember);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Applies an approved proposal's action to the group and stores it.
+fn apply_action(env: &Env, group: &mut Group, action: &ProposalAction) -> Result<(), AjoError> {
+    match action {
+        ProposalAction::SetGracePeriod(grace_period) => group.grace_period = *grace_period,
+        ProposalAction::SetPenaltyRate(penalty_rate) => group.penalty_rate = *penalty_rate,
+        ProposalAction::SetCycleDuration(cycle_duration) => {
+            group.cycle_duration = *cycle_duration
+        }
+        ProposalAction::SetPayoutOrder(order) => {
+            let mut payout_order = group.payout_order.slice(0..group.payout_index);
+            payout_order.append(order);
+            group.payout_order = payout_order;
+            events::emit_payout_order_set(env, group.id, &group.payout_order);
+        }
+        ProposalAction::RemoveMember(member) => {
+            return remove_started_member(env, group, member);
+        }
+        ProposalAction::ReplaceCreator(member) => group.creator = member.clone(),
+    }
+    storage::store_group(env, group.id, group);
+    Ok(())
+}
+
+/// Closes a refund proposal that wasn't approved.
+///
+/// The proposal stays in the group's history with its final vote counts,
+/// while the individual votes are removed.
+fn reject_refund_request(env: &Env, group: &Group, request: &mut RefundRequest) {
+    request.executed = true;
+    request.approved = false;
+    storage::store_refund_request(env, request);
+
+    for member in group.members.iter() {
+        storage::remove_refund_vote(env, group.id, request.id, &member);
+    }
+}
+
+/// Closes the group's refund proposal, if one is still open, once the group
+/// has been cancelled or completed, so it can't be executed afterwards.
+fn close_refund_request(env: &Env, group: &Group) {
+    if let Some(mut request) = storage::get_latest_refund_request(env, group.id) {
+        if !request.executed {
+            reject_refund_request(env, group, &mut request);
+        }
+    }
+}
+
+/// Settles a group that is being refunded before it completes.
+///
+/// Releases all collateral and unused prepayments, then shares whatever is
+/// left in the group's escrow between the members in proportion to their
+/// net positions: what each paid in contributions (including installments
+/// towards the current cycle) and penalties, minus what they received.
+/// Members who received more than they paid get nothing back. If nobody is
+/// owed anything but escrow is left over, it is shared in proportion to what
+/// each member paid in, or evenly if nobody paid anything. Any rounding
+/// remainder goes to the first member with a share.
+///
+/// Groups created before escrow was tracked have no reliable positions, so
+/// their members get back what they paid towards the current cycle instead.
+///
+/// `reserved` is left in escrow rather than shared, for a keeper fee credited
+/// once the group is settled.
+///
+/// Stores a `RefundRecord` with the breakdown for every member and returns
+/// the total refunded, not counting collateral and prepayments.
+fn settle_group(
+    env: &Env,
+    group: &Group,
+    reason: crate::types::RefundReason,
+    now: u64,
+    reserved: i128,
+) -> Result<i128, AjoError> {
+    release_all_collateral(env, group)?;
+    release_all_prepayments(env, group)?;
+
+    let group_id = group.id;
+    let cycle = group.current_cycle;
+
+    // Installments towards an unfinished contribution haven't been added to
+    // the position yet, unless a default already counted them
+    let mut positions = Vec::new(env);
+    let mut total_owed = 0i128;
+    for member in group.members.iter() {
+        let mut position = storage::get_member_position(env, group_id, &member);
+        if !storage::has_contributed(env, group_id, cycle, &member)
+            && storage::get_default_record(env, group_id, cycle, &member).is_none()
+        {
+            position.contributed += storage::get_partial_contribution(env, group_id, cycle, &member);
+        }
+        total_owed += position.net().max(0);
+        positions.push_back((member, position));
+    }
+
+    let mut refunds = Vec::new(env);
+    match storage::get_escrow(env, group_id) {
+        Some(escrow) => {
+            // With nobody owed anything, whatever is left goes back in
+            // proportion to what each member paid in, or evenly if nobody paid
+            let remaining = escrow.available() - reserved;
+            let mut weights = Vec::new(env);
+            let mut total_weight = 0i128;
+            for (_, position) in positions.iter() {
+                let weight = if total_owed > 0 {
+                    position.net().max(0)
+                } else {
+                    position.contributed + position.penalties
+                };
+                total_weight += weight;
+                weights.push_back(weight);
+            }
+            if total_weight <= 0 {
+                weights = Vec::new(env);
+                for _ in positions.iter() {
+                    weights.push_back(1);
+                }
+                total_weight = positions.len() as i128;
+            }
+
+            let mut distributed = 0i128;
+            for weight in weights.iter() {
+                let share = if remaining > 0 && weight > 0 {
+                    remaining * weight / total_weight
+                } else {
+                    0
+                };
+                distributed += share;
+                refunds.push_back(share);
+            }
+
+            if remaining > 0 {
+                if let Some(creditor) = weights.iter().position(|weight| weight > 0) {
+                    let creditor = creditor as u32;
+                    let share = refunds.get(creditor).unwrap_or(0);
+                    refunds.set(creditor, share + remaining - distributed);
+                }
+            }
+        }
+        None => {
+            for (member, _) in positions.iter() {
+                refunds.push_back(utils::amount_paid(env, group, cycle, &member));
+            }
+        }
+    }
+
+    let mut total_refunded = 0i128;
+    for (index, (member, position)) in positions.iter().enumerate() {
+        let amount = refunds.get(index as u32).unwrap_or(0);
+        if amount > 0 {
+            credit_from_escrow(env, group_id, &member, amount, EscrowCredit::Refund)?;
+            events::emit_refund_processed(env, group_id, &member, amount, reason as u32);
+            total_refunded += amount;
+        }
+
+        let record = crate::types::RefundRecord {
+            group_id,
+            member: member.clone(),
+            amount,
+            contributed: position.contributed,
+            penalties_paid: position.penalties,
+            received: position.received,
+            timestamp: now,
+            reason,
+        };
+        storage::store_refund_record(env, group_id, &member, &record);
+    }
+
+    Ok(total_refunded)
+}
+
+/// Performs whatever step is due for `group` on behalf of `poke`.
+fn keep_group(env: &Env, keeper: &Address, mut group: Group) -> Result<KeeperReport, AjoError> {
+    let mut report = KeeperReport {
+        group_id: group.id,
+        defaults_marked: 0,
+        payout_executed: false,
+        refund_finalized: false,
+        fee: 0,
+    };
+
+    if group.state != crate::types::GroupState::Active || group.is_complete {
+        return Ok(report);
+    }
+
+    let now = utils::get_current_timestamp(env);
+
+    // An open refund request decides the group's fate first
+    if let Some(mut request) = storage::get_latest_refund_request(env, group.id) {
+        if !request.executed {
+            if now <= request.voting_deadline {
+                return Ok(report);
+            }
+
+            // The fee is held back from the settlement and only credited once
+            // the request has been closed
+            let pool = storage::get_cycle_penalty_pool(env, group.id, group.current_cycle);
+            let fee = keeper_fee(&group, pool);
+            if utils::is_refund_approved(&group, &request) {
+                ensure_escrow_covers(env, group.id, fee + locked_funds(env, &group))?;
+                refund_group(env, &mut group, &mut request, now, fee)?;
+            } else {
+                ensure_escrow_covers(env, group.id, fee)?;
+                reject_refund_request(env, &group, &mut request);
+            }
+            report.fee = pay_keeper(env, &group, keeper)?;
+            report.refund_finalized = true;
+            return Ok(report);
+        }
+    }
+
+    if now <= utils::get_grace_period_end(&group) {
+        return Ok(report);
+    }
+
+    for member in group.members.iter() {
+        if !storage::has_contributed(env, group.id, group.current_cycle, &member)
+            && storage::get_default_record(env, group.id, group.current_cycle, &member).is_none()
+        {
+            AjoContract::mark_default(env.clone(), group.id, member)?;
+            report.defaults_marked += 1;
+        }
+    }
+
+    // The fee comes out of the pool before it's paid out as a bonus, but is
+    // only credited once the payout has gone through, so a sweep that skips
+    // a failing group doesn't pay for it
+    ensure_escrow_covers(env, group.id, payout_credits(env, &group))?;
+    let cycle = group.current_cycle;
+    let pool = storage::get_cycle_penalty_pool(env, group.id, cycle);
+    let fee = keeper_fee(&group, pool);
+    storage::store_cycle_penalty_pool(env, group.id, cycle, pool - fee);
+    if let Err(error) = AjoContract::execute_payout(env.clone(), group.id) {
+        storage::store_cycle_penalty_pool(env, group.id, cycle, pool);
+        return Err(error);
+    }
+    report.payout_executed = true;
+
+    if fee > 0 {
+        credit_from_escrow(env, group.id, keeper, fee, EscrowCredit::Payout)?;
+        events::emit_keeper_paid(env, group.id, keeper, fee);
+    }
+    report.fee = fee;
+
+    Ok(report)
+}
+
+/// Returns the group's keeper fee, capped at what the penalty `pool` holds.
+fn keeper_fee(group: &Group, pool: i128) -> i128 {
+    group.settings.keeper_fee.min(pool).max(0)
+}
+
+/// Credits `keeper` with the group's keeper fee from the current cycle's
+/// penalty pool, capped at what the pool holds. Returns the fee paid.
+fn pay_keeper(env: &Env, group: &Group, keeper: &Address) -> Result<i128, AjoError> {
+    let cycle = group.current_cycle;
+    let pool = storage::get_cycle_penalty_pool(env, group.id, cycle);
+    let fee = keeper_fee(group, pool);
+    if fee == 0 {
+        return Ok(0);
+    }
+
+    storage::store_cycle_penalty_pool(env, group.id, cycle, pool - fee);
+    credit_from_escrow(env, group.id, keeper, fee, EscrowCredit::Payout)?;
+    events::emit_keeper_paid(env, group.id, keeper, fee);
+    Ok(fee)
+}
+
+/// Checks that `member` can pay the current cycle's contribution to `group`.
+fn ensure_can_contribute(env: &Env, group: &Group, member: &Address) -> Result<(), AjoError> {
+    // Check if group is complete
+    if group.is_complete {
+        return Err(AjoError::GroupComplete);
+    }
+
+    // Check if group is cancelled
+    if group.state == crate::types::GroupState::Cancelled {
+        return Err(AjoError::GroupCancelled);
+    }
+
+    // Check if group has started
+    if group.state == crate::types::GroupState::Forming {
+        return Err(AjoError::InvalidGroupState);
+    }
+
+    // Check if member
+    if !utils::is_member(&group.members, member) {
+        return Err(AjoError::NotMember);
+    }
+
+    // Check if already contributed
+    if storage::has_contributed(env, group.id, group.current_cycle, member) {
+        return Err(AjoError::AlreadyContributed);
+    }
+
+    Ok(())
+}
+
+/// Records a contribution the member has already paid for, updates their
+/// penalty statistics and emits the contribution events.
+fn record_contribution(
+    env: &Env,
+    group: &Group,
+    member: &Address,
+    now: u64,
+    is_late: bool,
+    penalty: i128,
+) {
+    // Cache frequently accessed values
+    let group_id_cached = group.id;
+    let current_cycle = group.current_cycle;
+    let contribution_amount = utils::contribution_due(env, group, member);
+
+    // Record contribution; any installments are now part of it
+    storage::store_contribution(env, group_id_cached, current_cycle, member, true);
+    storage::remove_partial_contribution(env, group_id_cached, current_cycle, member);
+
+    // Penalties go to the current cycle's pool for the next recipient
+    if penalty > 0 {
+        storage::add_to_penalty_pool(env, group_id_cached, current_cycle, penalty);
+    }
+
+    // Record contribution details
+    let record = crate::types::ContributionRecord {
+        group_id: group_id_cached,
+        cycle: current_cycle,
+        member: member.clone(),
+        amount: contribution_amount,
+        timestamp: now,
+        is_late,
+        penalty_amount: penalty,
+    };
+    storage::store_contribution_detail(env, group_id_cached, current_cycle, member, &record);
+
+    let mut position = storage::get_member_position(env, group_id_cached, member);
+    position.contributed += contribution_amount;
+    position.penalties += penalty;
+    storage::store_member_position(env, group_id_cached, member, &position);
+
+    // Update member penalty statistics
+    let mut penalty_record = storage::get_member_penalty(env, group_id_cached, member)
+        .unwrap_or(crate::types::MemberPenaltyRecord {
+            member: member.clone(),
+            group_id: group_id_cached,
+            late_count: 0,
+            on_time_count: 0,
+            total_penalties: 0,
+            reliability_score: 100,
+        });
+    if is_late {
+        penalty_record.late_count += 1;
+        penalty_record.total_penalties += penalty;
+    } else {
+        penalty_record.on_time_count += 1;
+    }
+    penalty_record.reliability_score = utils::calculate_reliability_score(
+        penalty_record.on_time_count,
+        penalty_record.late_count,
+    );
+    storage::store_member_penalty(env, group_id_cached, member, &penalty_record);
+
+    // Emit events
+    if is_late {
+        events::emit_late_contribution(
+            env,
+            group_id_cached,
+            member,
+            current_cycle,
+            contribution_amount,
+            penalty,
+        );
     }
+    events::emit_contribution_made(
+        env,
+        group_id_cached,
+        member,
+        current_cycle,
+        contribution_amount,
+    );
 }
@@ -1,6 +1,9 @@
 use soroban_sdk::contracterror;
 
 /// Error codes for the Ajo contract.
+///
+/// Contract specs allow at most 50 error variants, so prefer reusing an
+/// existing variant over adding a new one.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -124,4 +127,34 @@ pub enum AjoError {
 
     /// Token allowance is insufficient for transfer.
     InsufficientAllowance = 40,
+
+    /// Payout order must contain every member exactly once.
+    InvalidPayoutOrder = 41,
+
+    /// Payout order can no longer be changed once contributions have started.
+    PayoutOrderLocked = 42,
+
+    /// This operation isn't supported by the group's payout strategy.
+    InvalidPayoutStrategy = 43,
+
+    /// Bid discount must be non-negative and less than the pot.
+    InvalidBidAmount = 44,
+
+    /// There is no balance available to claim.
+    NothingToClaim = 45,
+
+    /// Member has not placed a bid for this cycle.
+    NoBid = 46,
+
+    /// No payout has been made for this cycle.
+    NoPayoutRecord = 47,
+
+    /// The group isn't in a state that allows this operation.
+    InvalidGroupState = 48,
+
+    /// The requested record doesn't exist.
+    RecordNotFound = 49,
+
+    /// The share weight is outside the allowed range.
+    InvalidShareWeight = 50,
 }
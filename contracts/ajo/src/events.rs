@@ -1,4 +1,4 @@
-use soroban_sdk::{symbol_short, Address, Env};
+use soroban_sdk::{symbol_short, Address, Env, Vec};
 
 /// Emit an event when a group is created
 pub fn emit_group_created(
@@ -13,12 +13,102 @@ pub fn emit_group_created(
         .publish(topics, (creator, contribution_amount, max_members));
 }
 
+/// Emit an event when a group template is saved
+pub fn emit_template_created(env: &Env, template_id: u64, owner: &Address) {
+    let topics = (symbol_short!("template"), template_id);
+    env.events().publish(topics, owner);
+}
+
+/// Emit an event when a group is created as a copy of a completed group
+pub fn emit_group_cloned(env: &Env, group_id: u64, source_group_id: u64) {
+    let topics = (symbol_short!("cloned"), group_id);
+    env.events().publish(topics, source_group_id);
+}
+
+/// Emit an event when a group starts its first cycle
+pub fn emit_group_started(env: &Env, group_id: u64, member_count: u32, cycle_start_time: u64) {
+    let topics = (symbol_short!("started"), group_id);
+    env.events().publish(topics, (member_count, cycle_start_time));
+}
+
 /// Emit an event when a member joins a group
 pub fn emit_member_joined(env: &Env, group_id: u64, member: &Address) {
     let topics = (symbol_short!("joined"), group_id);
     env.events().publish(topics, member);
 }
 
+/// Emit an event when an existing member takes another payout slot
+pub fn emit_slot_added(env: &Env, group_id: u64, member: &Address, slots: u32) {
+    let topics = (symbol_short!("slot_add"), group_id);
+    env.events().publish(topics, (member, slots));
+}
+
+/// Emit an event when a multi-round group starts its next round
+pub fn emit_round_started(env: &Env, group_id: u64, round: u32, first_cycle: u32) {
+    let topics = (symbol_short!("round"), group_id);
+    env.events().publish(topics, (round, first_cycle));
+}
+
+/// Emit an event when a member chooses their share weight
+pub fn emit_share_weight_set(env: &Env, group_id: u64, member: &Address, weight: u32) {
+    let topics = (symbol_short!("share_set"), group_id);
+    env.events().publish(topics, (member, weight));
+}
+
+/// Emit an event when the creator invites an address to a group
+pub fn emit_member_invited(env: &Env, group_id: u64, invitee: &Address) {
+    let topics = (symbol_short!("invited"), group_id);
+    env.events().publish(topics, invitee);
+}
+
+/// Emit an event when an address requests to join a group
+pub fn emit_join_requested(env: &Env, group_id: u64, member: &Address) {
+    let topics = (symbol_short!("join_req"), group_id);
+    env.events().publish(topics, member);
+}
+
+/// Emit an event when the creator rejects a join request
+pub fn emit_join_rejected(env: &Env, group_id: u64, member: &Address) {
+    let topics = (symbol_short!("join_rej"), group_id);
+    env.events().publish(topics, member);
+}
+
+/// Emit an event when a member leaves a group
+pub fn emit_member_left(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    refunded: i128,
+    forfeited: i128,
+) {
+    let topics = (symbol_short!("left"), group_id);
+    env.events().publish(topics, (member, refunded, forfeited));
+}
+
+/// Emit an event when a member requests to exit a started group
+pub fn emit_exit_requested(env: &Env, group_id: u64, member: &Address) {
+    let topics = (symbol_short!("exit_req"), group_id);
+    env.events().publish(topics, member);
+}
+
+/// Emit an event when the creator rejects an exit request
+pub fn emit_exit_rejected(env: &Env, group_id: u64, member: &Address) {
+    let topics = (symbol_short!("exit_rej"), group_id);
+    env.events().publish(topics, member);
+}
+
+/// Emit an event when a member transfers their slot to another address
+pub fn emit_slot_transferred(env: &Env, group_id: u64, from: &Address, to: &Address) {
+    let topics = (symbol_short!("transfer"), group_id);
+    env.events().publish(topics, (from, to));
+}
+
+/// Emit an event when the creator sets a group's payout order
+pub fn emit_payout_order_set(env: &Env, group_id: u64, order: &Vec<Address>) {
+    let topics = (symbol_short!("order"), group_id);
+    env.events().publish(topics, order.clone());
+}
+
 /// Emit an event when a member contributes
 pub fn emit_contribution_made(
     env: &Env,
@@ -43,6 +133,109 @@ pub fn emit_payout_executed(
     env.events().publish(topics, (recipient, amount));
 }
 
+/// Emit an event when a member places or updates an auction bid
+pub fn emit_bid_placed(env: &Env, group_id: u64, bidder: &Address, cycle: u32, discount: i128) {
+    let topics = (symbol_short!("bid"), group_id, cycle);
+    env.events().publish(topics, (bidder, discount));
+}
+
+/// Emit an event when an auction is settled and its discount redistributed
+pub fn emit_auction_settled(
+    env: &Env,
+    group_id: u64,
+    winner: &Address,
+    cycle: u32,
+    discount: i128,
+    recipients: u32,
+) {
+    let topics = (symbol_short!("auction"), group_id, cycle);
+    env.events()
+        .publish(topics, (winner, discount, recipients));
+}
+
+/// Emit an event when a member withdraws their claimable balance
+pub fn emit_claimed(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("claimed"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member pays an installment towards a cycle's contribution
+pub fn emit_installment_paid(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    cycle: u32,
+    amount: i128,
+    paid_so_far: i128,
+) {
+    let topics = (symbol_short!("install"), group_id, cycle);
+    env.events().publish(topics, (member, amount, paid_so_far));
+}
+
+/// Emit an event when a member pays for future cycles in advance
+pub fn emit_contributed_ahead(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    cycles: u32,
+    amount: i128,
+) {
+    let topics = (symbol_short!("prepaid"), group_id);
+    env.events().publish(topics, (member, cycles, amount));
+}
+
+/// Emit an event when unused prepaid cycles are refunded
+pub fn emit_prepayment_released(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("prep_rel"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member sets how many cycles may be auto-collected
+pub fn emit_auto_contribute_set(env: &Env, group_id: u64, member: &Address, cycles: u32) {
+    let topics = (symbol_short!("auto_set"), group_id);
+    env.events().publish(topics, (member, cycles));
+}
+
+/// Emit an event when a member locks collateral in a group
+pub fn emit_collateral_locked(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("col_lock"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when collateral is slashed to cover a missed contribution
+pub fn emit_collateral_slashed(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    cycle: u32,
+    amount: i128,
+    remaining: i128,
+) {
+    let topics = (symbol_short!("slashed"), group_id, cycle);
+    env.events().publish(topics, (member, amount, remaining));
+}
+
+/// Emit an event when a member is marked as defaulted on a cycle
+pub fn emit_member_defaulted(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    cycle: u32,
+    from_collateral: i128,
+    from_penalty_pool: i128,
+    shortfall: i128,
+) {
+    let topics = (symbol_short!("defaulted"), group_id, cycle);
+    env.events()
+        .publish(topics, (member, from_collateral, from_penalty_pool, shortfall));
+}
+
+/// Emit an event when a member's collateral is released to their claimable balance
+pub fn emit_collateral_released(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("col_rel"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
 /// Emit an event when a group completes all cycles
 pub fn emit_group_completed(env: &Env, group_id: u64) {
     let topics = (symbol_short!("complete"), group_id);
@@ -95,26 +288,64 @@ pub fn emit_penalty_distributed(
         .publish(topics, (recipient, base_amount, penalty_bonus));
 }
 
+/// Emit an event when a keeper is rewarded for advancing a group
+pub fn emit_keeper_paid(env: &Env, group_id: u64, keeper: &Address, fee: i128) {
+    let topics = (symbol_short!("keeper"), group_id);
+    env.events().publish(topics, (keeper, fee));
+}
+
+/// Emit an event when `process_due_groups` skips a group it failed to advance
+pub fn emit_keeper_skipped(env: &Env, group_id: u64, error: u32) {
+    let topics = (symbol_short!("keepskip"), group_id);
+    env.events().publish(topics, error);
+}
+
 /// Emit an event when a refund request is created
 pub fn emit_refund_requested(
     env: &Env,
     group_id: u64,
+    proposal_id: u64,
     requester: &Address,
     voting_deadline: u64,
 ) {
     let topics = (symbol_short!("refreq"), group_id);
-    env.events().publish(topics, (requester, voting_deadline));
+    env.events().publish(topics, (proposal_id, requester, voting_deadline));
 }
 
 /// Emit an event when a member votes on a refund request
 pub fn emit_refund_vote(
     env: &Env,
     group_id: u64,
+    proposal_id: u64,
     voter: &Address,
-    in_favor: bool,
+    choice: u32,
 ) {
     let topics = (symbol_short!("refvote"), group_id);
-    env.events().publish(topics, (voter, in_favor));
+    env.events().publish(topics, (proposal_id, voter, choice));
+}
+
+/// Emit an event when a member makes a governance proposal
+pub fn emit_proposal_created(
+    env: &Env,
+    group_id: u64,
+    proposal_id: u64,
+    proposer: &Address,
+    voting_deadline: u64,
+) {
+    let topics = (symbol_short!("proposal"), group_id);
+    env.events().publish(topics, (proposal_id, proposer, voting_deadline));
+}
+
+/// Emit an event when a member votes on a governance proposal
+pub fn emit_proposal_vote(env: &Env, group_id: u64, proposal_id: u64, voter: &Address, choice: u32) {
+    let topics = (symbol_short!("propvote"), group_id);
+    env.events().publish(topics, (proposal_id, voter, choice));
+}
+
+/// Emit an event when a governance proposal is executed, approved or not
+pub fn emit_proposal_executed(env: &Env, group_id: u64, proposal_id: u64, approved: bool) {
+    let topics = (symbol_short!("propexec"), group_id);
+    env.events().publish(topics, (proposal_id, approved));
 }
 
 /// Emit an event when a refund is processed
@@ -15,6 +15,7 @@
 mod contract;
 mod errors;
 mod events;
+mod migration;
 mod pausable;
 mod security;
 mod storage;
@@ -25,4 +26,10 @@ mod utils;
 pub use contract::AjoContract;
 pub use contract::AjoContractClient;
 pub use errors::AjoError;
-pub use types::{GroupState, RefundReason, RefundRequest, RefundRecord, RefundVote};
+pub use storage::DataKey;
+pub use types::{
+    Bid, DefaultRecord, ExitRequest, GovernanceSettings, GroupEscrow, GroupParams, GroupSettings,
+    GroupState, GroupTemplate, JoinPolicy, JoinRequest, KeeperReport, MemberPosition,
+    PayoutRecord, PayoutStrategy, Proposal, ProposalAction, ProposalVote, RefundReason,
+    RefundRecord, RefundRequest, RefundVote, RoundRecord, VoteChoice,
+};
@@ -0,0 +1,290 @@
+//! Migration of storage entries written by earlier contract versions.
+//!
+//! Before [`DataKey`] was introduced, entries were stored under ad-hoc
+//! symbol tuples such as `("GROUP", group_id)`. The helpers in this module
+//! move those entries to their [`DataKey`] equivalents so that contracts
+//! upgraded in place keep all of their groups.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, IntoVal, TryFromVal, Val, Vec};
+
+use crate::storage::{self, DataKey};
+use crate::types::{
+    ContributionRecord, Group, GroupMetadata, GroupSettings, GroupState, MemberPenaltyRecord,
+    PayoutStrategy, RefundReason, RefundRecord, RefundRequest, RefundVote, VoteChoice,
+};
+
+/// Layout of [`Group`] as stored by contract versions that used symbol-tuple keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyGroup {
+    pub contribution_amount: i128,
+    pub creator: Address,
+    pub token_address: Address,
+    pub members: Vec<Address>,
+    pub id: u64,
+    pub cycle_duration: u64,
+    pub created_at: u64,
+    pub cycle_start_time: u64,
+    pub max_members: u32,
+    pub current_cycle: u32,
+    pub payout_index: u32,
+    pub is_complete: bool,
+    pub grace_period: u64,
+    pub penalty_rate: u32,
+    pub state: GroupState,
+}
+
+impl LegacyGroup {
+    /// Converts a legacy group to the current layout.
+    ///
+    /// Legacy groups always paid members in join order, so they become
+    /// [`PayoutStrategy::Fixed`] groups with `payout_order` equal to `members`.
+    /// They were open to anyone, so they get the default settings.
+    fn into_group(self) -> Group {
+        Group {
+            contribution_amount: self.contribution_amount,
+            creator: self.creator,
+            token_address: self.token_address,
+            payout_order: self.members.clone(),
+            members: self.members,
+            id: self.id,
+            cycle_duration: self.cycle_duration,
+            created_at: self.created_at,
+            cycle_start_time: self.cycle_start_time,
+            max_members: self.max_members,
+            current_cycle: self.current_cycle,
+            payout_index: self.payout_index,
+            round: 1,
+            round_start_cycle: 1,
+            is_complete: self.is_complete,
+            grace_period: self.grace_period,
+            penalty_rate: self.penalty_rate,
+            state: self.state,
+            payout_strategy: PayoutStrategy::Fixed,
+            settings: GroupSettings::default(),
+        }
+    }
+}
+
+/// Layout of [`RefundRecord`] as stored by contract versions that used symbol-tuple keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRefundRecord {
+    pub group_id: u64,
+    pub member: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+    pub reason: RefundReason,
+}
+
+impl LegacyRefundRecord {
+    /// Converts a legacy refund record to the current layout.
+    ///
+    /// Legacy refunds only returned what the member had paid, so the whole
+    /// amount counts as contributions.
+    fn into_refund_record(self) -> RefundRecord {
+        RefundRecord {
+            group_id: self.group_id,
+            member: self.member,
+            amount: self.amount,
+            contributed: self.amount,
+            penalties_paid: 0,
+            received: 0,
+            timestamp: self.timestamp,
+            reason: self.reason,
+        }
+    }
+}
+
+/// Layout of [`RefundRequest`] as stored by contract versions that used symbol-tuple keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRefundRequest {
+    pub group_id: u64,
+    pub requester: Address,
+    pub created_at: u64,
+    pub voting_deadline: u64,
+    pub votes_for: u32,
+    pub votes_against: u32,
+    pub executed: bool,
+    pub approved: bool,
+}
+
+impl LegacyRefundRequest {
+    /// Converts a legacy refund request to the current layout.
+    ///
+    /// Legacy groups had a single request, which becomes proposal 1.
+    /// Members couldn't abstain in legacy versions.
+    fn into_refund_request(self) -> RefundRequest {
+        RefundRequest {
+            group_id: self.group_id,
+            id: 1,
+            requester: self.requester,
+            created_at: self.created_at,
+            voting_deadline: self.voting_deadline,
+            votes_for: self.votes_for,
+            votes_against: self.votes_against,
+            votes_abstain: 0,
+            executed: self.executed,
+            approved: self.approved,
+        }
+    }
+}
+
+/// Layout of [`RefundVote`] as stored by contract versions that used symbol-tuple keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRefundVote {
+    pub group_id: u64,
+    pub voter: Address,
+    pub in_favor: bool,
+    pub timestamp: u64,
+}
+
+impl LegacyRefundVote {
+    /// Converts a legacy refund vote to the current layout, as a vote on
+    /// proposal 1.
+    fn into_refund_vote(self) -> RefundVote {
+        RefundVote {
+            group_id: self.group_id,
+            proposal_id: 1,
+            voter: self.voter,
+            choice: if self.in_favor {
+                VoteChoice::For
+            } else {
+                VoteChoice::Against
+            },
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Removes a legacy persistent entry and returns its value, if present.
+fn take_persistent<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let persistent = env.storage().persistent();
+    let value = persistent.get(key);
+    if value.is_some() {
+        persistent.remove(key);
+    }
+    value
+}
+
+/// Removes a legacy instance entry and returns its value, if present.
+fn take_instance<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let instance = env.storage().instance();
+    let value = instance.get(key);
+    if value.is_some() {
+        instance.remove(key);
+    }
+    value
+}
+
+/// Moves the legacy admin, group counter and pause flag to their [`DataKey`] slots.
+///
+/// Safe to call repeatedly; entries that were already migrated are left untouched.
+///
+/// # Arguments
+/// * `env` - The contract environment
+pub fn migrate_instance(env: &Env) {
+    if let Some(admin) = take_instance::<_, Address>(env, &symbol_short!("ADMIN")) {
+        storage::store_admin(env, &admin);
+    }
+    if let Some(counter) = take_instance::<_, u64>(env, &symbol_short!("GCOUNTER")) {
+        storage::set_group_counter(env, counter);
+    }
+    if let Some(paused) = take_instance::<_, bool>(env, &symbol_short!("PAUSED")) {
+        env.storage().instance().set(&DataKey::Paused, &paused);
+    }
+}
+
+/// Moves every legacy entry belonging to a group to its [`DataKey`] slot.
+///
+/// The group itself is migrated first so that the remaining entries pick up
+/// TTLs sized to the group's lifetime. Per-cycle entries are migrated for
+/// every cycle up to the group's current cycle.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group to migrate
+///
+/// # Returns
+/// `true` if a legacy group was found and migrated, `false` otherwise
+pub fn migrate_group(env: &Env, group_id: u64) -> bool {
+    let group = match take_persistent::<_, LegacyGroup>(env, &(symbol_short!("GROUP"), group_id)) {
+        Some(group) => group.into_group(),
+        None => return false,
+    };
+    storage::store_group(env, group_id, &group);
+
+    if let Some(metadata) =
+        take_persistent::<_, GroupMetadata>(env, &(symbol_short!("METADATA"), group_id))
+    {
+        storage::store_group_metadata(env, group_id, &metadata);
+    }
+
+    if let Some(request) =
+        take_persistent::<_, LegacyRefundRequest>(env, &(symbol_short!("REFREQ"), group_id))
+    {
+        storage::get_next_refund_proposal_id(env, group_id);
+        storage::store_refund_request(env, &request.into_refund_request());
+    }
+
+    for cycle in 1..=group.current_cycle {
+        if let Some(pool) =
+            take_persistent::<_, i128>(env, &(symbol_short!("PENPOOL"), group_id, cycle))
+        {
+            storage::store_cycle_penalty_pool(env, group_id, cycle, pool);
+        }
+    }
+
+    for member in group.members.iter() {
+        for cycle in 1..=group.current_cycle {
+            if let Some(paid) = take_persistent::<_, bool>(
+                env,
+                &(symbol_short!("CONTRIB"), group_id, cycle, member.clone()),
+            ) {
+                storage::store_contribution(env, group_id, cycle, &member, paid);
+            }
+            if let Some(record) = take_persistent::<_, ContributionRecord>(
+                env,
+                &(symbol_short!("CONTREC"), group_id, cycle, member.clone()),
+            ) {
+                storage::store_contribution_detail(env, group_id, cycle, &member, &record);
+            }
+        }
+
+        if let Some(true) =
+            take_persistent::<_, bool>(env, &(symbol_short!("PAYOUT"), group_id, member.clone()))
+        {
+            storage::mark_payout_received(env, group_id, &member);
+        }
+        if let Some(record) = take_persistent::<_, MemberPenaltyRecord>(
+            env,
+            &(symbol_short!("PENALTY"), group_id, member.clone()),
+        ) {
+            storage::store_member_penalty(env, group_id, &member, &record);
+        }
+        if let Some(vote) = take_persistent::<_, LegacyRefundVote>(
+            env,
+            &(symbol_short!("REFVOTE"), group_id, member.clone()),
+        ) {
+            storage::store_refund_vote(env, group_id, 1, &member, &vote.into_refund_vote());
+        }
+        if let Some(record) = take_persistent::<_, LegacyRefundRecord>(
+            env,
+            &(symbol_short!("REFUND"), group_id, member.clone()),
+        ) {
+            storage::store_refund_record(env, group_id, &member, &record.into_refund_record());
+        }
+    }
+
+    true
+}
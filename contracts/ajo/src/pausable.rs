@@ -1,7 +1,7 @@
-use soroban_sdk::{symbol_short, Env};
+use soroban_sdk::Env;
 
 use crate::errors::AjoError;
-use crate::storage;
+use crate::storage::{self, DataKey};
 
 /// Get the current pause state from instance storage.
 ///
@@ -17,15 +17,17 @@ use crate::storage;
 /// `true` if the contract is paused, `false` otherwise
 ///
 /// # Storage Strategy
-/// The pause state is stored in instance storage under the key "PAUSED".
+/// The pause state is stored in instance storage under [`DataKey::Paused`].
 /// Instance storage is appropriate for contract-level configuration because:
 /// - It provides fast O(1) access
 /// - It persists across contract calls and upgrades
 /// - It matches the pattern used for admin storage
 /// - It has lower cost than persistent storage for frequently accessed data
 pub fn is_paused(env: &Env) -> bool {
-    let key = symbol_short!("PAUSED");
-    env.storage().instance().get(&key).unwrap_or(false)
+    let key = DataKey::Paused;
+    let paused = env.storage().instance().get(&key).unwrap_or(false);
+    storage::extend_instance_ttl(env);
+    paused
 }
 
 /// Set the pause state in instance storage.
@@ -38,8 +40,9 @@ pub fn is_paused(env: &Env) -> bool {
 /// * `env` - The contract environment used to access instance storage
 /// * `paused` - The new pause state (`true` to pause, `false` to unpause)
 fn set_paused(env: &Env, paused: bool) {
-    let key = symbol_short!("PAUSED");
+    let key = DataKey::Paused;
     env.storage().instance().set(&key, &paused);
+    storage::extend_instance_ttl(env);
 }
 
 /// Check if the contract is paused and return an error if so.
@@ -75,15 +75,7 @@ pub fn validate_group_params(
     }
     
     // Validate cycle duration
-    if cycle_duration == 0 {
-        return Err(AjoError::CycleDurationZero);
-    }
-    if cycle_duration < limits::MIN_CYCLE_DURATION {
-        return Err(AjoError::CycleDurationZero); // Too short
-    }
-    if cycle_duration > limits::MAX_CYCLE_DURATION {
-        return Err(AjoError::CycleDurationZero); // Too long (reuse error)
-    }
+    validate_cycle_duration(cycle_duration)?;
     
     // Validate max members
     if max_members < limits::MIN_MEMBERS {
@@ -96,6 +88,30 @@ pub fn validate_group_params(
     Ok(())
 }
 
+/// Validates a cycle duration against security limits
+///
+/// Used both when a group is created and when members vote to change the
+/// cycle duration of a running group.
+///
+/// # Arguments
+/// * `cycle_duration` - Duration of each cycle in seconds
+///
+/// # Returns
+/// * `Ok(())` if the duration is within limits
+/// * `Err(AjoError::CycleDurationZero)` if it is zero, too short or too long
+pub fn validate_cycle_duration(cycle_duration: u64) -> Result<(), AjoError> {
+    if cycle_duration == 0 {
+        return Err(AjoError::CycleDurationZero);
+    }
+    if cycle_duration < limits::MIN_CYCLE_DURATION {
+        return Err(AjoError::CycleDurationZero); // Too short
+    }
+    if cycle_duration > limits::MAX_CYCLE_DURATION {
+        return Err(AjoError::CycleDurationZero); // Too long (reuse error)
+    }
+    Ok(())
+}
+
 /// Checks if an address is a member of a group
 ///
 /// # Arguments
@@ -136,13 +152,13 @@ pub fn all_members_contributed(env: &Env, group: &Group) -> bool {
 /// * `group` - The group to calculate payout for
 ///
 /// # Returns
-/// The total payout amount (contribution_amount × number of members)
+/// The total payout amount (contribution_amount × number of slots)
 ///
 /// # Security Note
 /// Uses checked multiplication to prevent overflow
 pub fn calculate_payout_amount(group: &Group) -> i128 {
-    let member_count = group.members.len() as i128;
-    group.contribution_amount.checked_mul(member_count)
+    let slot_count = group.payout_order.len() as i128;
+    group.contribution_amount.checked_mul(slot_count)
         .expect("Payout calculation overflow")
 }
 
@@ -163,7 +179,7 @@ pub fn calculate_payout_amount(group: &Group) -> i128 {
 /// - Large withdrawal amounts
 pub fn check_transaction_pattern(group: &Group) -> bool {
     // Check for reasonable member count
-    if group.members.len() > limits::MAX_MEMBERS as usize {
+    if group.members.len() > limits::MAX_MEMBERS {
         return false;
     }
     
@@ -242,7 +258,7 @@ pub fn audit_group_security(env: &Env, group: &Group) -> (u32, u32, u32, u32) {
     }
     
     // Flag 2: Payout index mismatch
-    if payouts_completed > group.members.len() as u32 {
+    if payouts_completed > group.payout_order.len() {
         flags |= 2;
     }
     
@@ -1,76 +1,369 @@
-use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, Env, Vec};
 
-/// Logical storage key categories used by the Ajo contract.
+/// Storage keys used by the Ajo contract.
 ///
-/// Soroban storage uses raw key values; this enum documents the naming
-/// conventions and composite key structures used throughout the contract.
-/// Each variant maps to a specific `symbol_short!` or tuple key at the
-/// call site.
-pub enum StorageKey {
+/// This enum is the single source of truth for every key the contract reads
+/// or writes. Instance storage holds contract-wide configuration; everything
+/// scoped to a group lives in persistent storage.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
     /// Singleton key for the contract administrator address.
-    /// Stored in instance storage under `"ADMIN"`.
+    /// Stored in instance storage.
     Admin,
 
     /// Monotonically increasing counter used to assign unique group IDs.
-    /// Stored in instance storage under `"GCOUNTER"`.
+    /// Stored in instance storage.
     GroupCounter,
 
+    /// Emergency pause flag. Value is `bool` — `true` means paused.
+    /// Stored in instance storage.
+    Paused,
+
     /// Full group state keyed by its numeric ID.
-    /// Stored in persistent storage under `("GROUP", group_id)`.
     Group(u64),
 
     /// Per-member contribution flag for a specific group and cycle.
-    /// Stored in persistent storage under `("CONTRIB", group_id, cycle, member)`.
     /// Value is `bool` — `true` means the member has contributed.
     Contribution(u64, u32, Address),
 
     /// Records whether a member has received their payout for a group.
-    /// Stored in persistent storage under `("PAYOUT", group_id, member)`.
     /// Value is `bool` — `true` means the payout has been distributed.
     PayoutReceived(u64, Address),
 
     /// Optional metadata for a group.
-    /// Stored in persistent storage under `("METADATA", group_id)`.
     GroupMetadata(u64),
 
-    /// Contribution record with penalty information.
-    /// Stored in persistent storage under `("CONTREC", group_id, cycle, member)`.
+    /// Contribution record with penalty information for a group, cycle and member.
     ContributionDetail(u64, u32, Address),
 
     /// Member penalty statistics for a group.
-    /// Stored in persistent storage under `("PENALTY", group_id, member)`.
     MemberPenalty(u64, Address),
 
-    /// Penalty pool for current cycle.
-    /// Stored in persistent storage under `("PENPOOL", group_id, cycle)`.
+    /// Penalty pool for a group's cycle.
     CyclePenaltyPool(u64, u32),
+
+    /// Number of refund proposals made for a group; the latest has this ID.
+    RefundProposalCount(u64),
+
+    /// A group's refund proposal, keyed by its proposal ID.
+    RefundRequest(u64, u64),
+
+    /// A member's vote on one of a group's refund proposals.
+    RefundVote(u64, u64, Address),
+
+    /// Number of governance proposals made for a group.
+    ProposalCount(u64),
+
+    /// A group's governance proposal, keyed by its proposal ID.
+    Proposal(u64, u64),
+
+    /// A member's vote on one of a group's governance proposals.
+    ProposalVote(u64, u64, Address),
+
+    /// Refund record for a member of a group.
+    RefundRecord(u64, Address),
+
+    /// Payout record for a group's cycle.
+    PayoutRecord(u64, u32),
+
+    /// A member's auction bid for a group's cycle.
+    Bid(u64, u32, Address),
+
+    /// Balance a member of a group can withdraw with `claim`.
+    Claimable(u64, Address),
+
+    /// A member's pending request to exit a group.
+    ExitRequest(u64, Address),
+
+    /// Addresses invited to join a group.
+    Invites(u64),
+
+    /// Pending requests to join a group.
+    JoinRequests(u64),
+
+    /// Collateral a member (or pending applicant) has locked in a group.
+    Collateral(u64, Address),
+
+    /// A member's default on a group's cycle.
+    DefaultRecord(u64, u32, Address),
+
+    /// Groups in which an address has a claimable balance.
+    ClaimableGroups(Address),
+
+    /// Amount a member has paid so far towards a cycle's contribution, while
+    /// the contribution is still incomplete.
+    PartialContribution(u64, u32, Address),
+
+    /// Future cycles a member has paid for in advance with `contribute_ahead`.
+    PrepaidCycles(u64, Address),
+
+    /// A member's share weight, as a percentage of `contribution_amount`.
+    ShareWeight(u64, Address),
+
+    /// Running totals of what a member has paid into and received from a group.
+    MemberPosition(u64, Address),
+
+    /// History of a completed round of a multi-round group.
+    RoundRecord(u64, u32),
+
+    /// Cycles a member has pre-authorized `collect_contribution` to pay for.
+    AutoContribute(u64, Address),
+
+    /// Next group ID `process_due_groups` will look at.
+    /// Stored in instance storage.
+    KeeperCursor,
+
+    /// Ledger of the tokens the contract holds for a group.
+    Escrow(u64),
+
+    /// Counter used to assign unique template IDs.
+    /// Stored in instance storage.
+    TemplateCounter,
+
+    /// A saved group template keyed by its numeric ID.
+    Template(u64),
+
+    /// Number of ledgers a group's entries are extended to, kept up to date
+    /// by [`store_group`] so entry accessors don't have to load the group.
+    GroupTtl(u64),
+}
+
+/// Approximate number of ledgers closed per day, assuming a 5 second close time.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Expected ledger close time in seconds, used to convert durations into ledgers.
+pub const LEDGER_CLOSE_SECONDS: u64 = 5;
+
+/// Number of ledgers instance storage (admin, group counter, pause flag) is
+/// extended to whenever it is accessed (30 days).
+pub const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Instance storage is only re-extended once its TTL drops below this value.
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Minimum TTL for any group entry. This is also how long a finished or
+/// cancelled group's records are kept alive after its last access (30 days).
+pub const MIN_GROUP_TTL: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Extends the TTL of the contract instance (and its instance storage).
+///
+/// Called on every instance storage access so the admin, group counter and
+/// pause flag stay live as long as the contract is being used.
+///
+/// # Arguments
+/// * `env` - The contract environment
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Calculates how many ledgers a group's entries must stay live for.
+///
+/// An active or forming group needs its entries to outlive every remaining cycle
+/// (including grace periods) plus a refund voting period. On top of that,
+/// [`MIN_GROUP_TTL`] is always added so records remain queryable for a while
+/// after the group finishes. The result is capped at the network's maximum TTL.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group` - The group whose remaining lifetime is calculated
+///
+/// # Returns
+/// The number of ledgers group entries should be extended to
+pub fn group_ttl(env: &Env, group: &crate::types::Group) -> u32 {
+    let is_running = matches!(
+        group.state,
+        crate::types::GroupState::Active | crate::types::GroupState::Forming
+    );
+    let remaining_cycles = if group.is_complete || !is_running {
+        0
+    } else {
+        let later_rounds = crate::utils::total_rounds(group).saturating_sub(group.round);
+        group.max_members.saturating_sub(group.payout_index) as u64
+            + later_rounds as u64 * group.max_members as u64
+    };
+    let per_cycle = group.cycle_duration.saturating_add(group.grace_period);
+    let remaining_secs = remaining_cycles
+        .saturating_mul(per_cycle)
+        .saturating_add(crate::utils::voting_period(group));
+    let remaining_ledgers = (remaining_secs / LEDGER_CLOSE_SECONDS).min(u32::MAX as u64) as u32;
+
+    remaining_ledgers
+        .saturating_add(MIN_GROUP_TTL)
+        .min(env.storage().max_ttl())
 }
 
-impl StorageKey {
-    /// Returns the short [`Symbol`] prefix associated with this key variant.
-    ///
-    /// This method extracts the symbol portion of a storage key. Note that composite
-    /// keys (e.g., [`StorageKey::Group`]) pair this symbol with additional fields
-    /// in a tuple at the storage call site; this method returns only the symbol portion.
-    ///
-    /// # Arguments
-    /// * `_env` - The contract environment (reserved for future use)
-    ///
-    /// # Returns
-    /// The [`Symbol`] corresponding to this key variant's prefix
-    pub fn to_symbol(&self, _env: &Env) -> Symbol {
-        match self {
-            StorageKey::Admin => symbol_short!("ADMIN"),
-            StorageKey::GroupCounter => symbol_short!("GCOUNTER"),
-            StorageKey::Group(_) => symbol_short!("GROUP"),
-            StorageKey::Contribution(_, _, _) => symbol_short!("CONTRIB"),
-            StorageKey::PayoutReceived(_, _) => symbol_short!("PAYOUT"),
-            StorageKey::GroupMetadata(_) => symbol_short!("METADATA"),
-            StorageKey::ContributionDetail(_, _, _) => symbol_short!("CONTREC"),
-            StorageKey::MemberPenalty(_, _) => symbol_short!("PENALTY"),
-            StorageKey::CyclePenaltyPool(_, _) => symbol_short!("PENPOOL"),
+/// Extends a persistent entry so it lives for at least `extend_to` ledgers.
+///
+/// The entry is only touched if its TTL has fallen more than a day behind
+/// the target, which keeps repeated reads cheap.
+fn extend_persistent(env: &Env, key: &DataKey, extend_to: u32) {
+    let threshold = extend_to.saturating_sub(DAY_IN_LEDGERS);
+    env.storage().persistent().extend_ttl(key, threshold, extend_to);
+}
+
+/// Extends a persistent entry belonging to `group_id` to the group's remaining lifetime.
+///
+/// Uses the TTL cached by [`store_group`], falling back to [`MIN_GROUP_TTL`]
+/// if the group has none.
+fn extend_group_entry(env: &Env, group_id: u64, key: &DataKey) {
+    let extend_to = env
+        .storage()
+        .persistent()
+        .get::<_, u32>(&DataKey::GroupTtl(group_id))
+        .unwrap_or(MIN_GROUP_TTL)
+        .min(env.storage().max_ttl());
+    extend_persistent(env, key, extend_to);
+}
+
+/// Number of work units [`bump_group`] processes per call. A unit is either a
+/// member's own entries or a member's entries for one live cycle.
+pub const BUMP_BATCH_SIZE: u32 = 10;
+
+/// Number of most recent governance proposals (and the votes on them) that
+/// [`bump_group`] extends. Older proposals are extended whenever they are read.
+pub const BUMP_RECENT_PROPOSALS: u64 = 5;
+
+/// Extends the TTL of the live persistent entries belonging to a group, one
+/// batch at a time.
+///
+/// Every call extends the group itself, its cached TTL, metadata, escrow
+/// ledger, proposal counters, invites and join requests (plus collateral
+/// locked by pending applicants), the latest round record, the latest refund
+/// proposal and the [`BUMP_RECENT_PROPOSALS`] most recent governance proposals.
+///
+/// Per-member and per-cycle entries are walked in batches of
+/// [`BUMP_BATCH_SIZE`] units starting at `cursor`. Units `0..members` cover
+/// each member's own entries (payout flag, penalty statistics, votes on the
+/// latest refund proposal and recent governance proposals, refund record,
+/// claimable balance, exit request, collateral, auto-contribution mandate,
+/// prepaid cycles, share weight and position). The following units cover
+/// each member's entries (contribution, installments, contribution record,
+/// bid, default) for every cycle of the current round, together with that
+/// cycle's penalty pool and payout record. Entries of earlier rounds are
+/// summarised by their round record and are not extended. Entries that don't
+/// exist are skipped.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group` - The group whose entries should be extended
+/// * `cursor` - The first unit to process, `0` to start from the beginning
+///
+/// # Returns
+/// The cursor to pass to the next call, or `None` once every unit is done
+pub fn bump_group(env: &Env, group: &crate::types::Group, cursor: u32) -> Option<u32> {
+    let group_id = group.id;
+    let extend_to = group_ttl(env, group);
+    let persistent = env.storage().persistent();
+
+    let group_key = DataKey::Group(group_id);
+    extend_persistent(env, &group_key, extend_to);
+    let ttl_key = DataKey::GroupTtl(group_id);
+    persistent.set(&ttl_key, &extend_to);
+    extend_persistent(env, &ttl_key, extend_to);
+
+    let latest_refund = get_refund_proposal_count(env, group_id);
+    let latest_proposal = get_proposal_count(env, group_id);
+    let recent_proposals =
+        latest_proposal.saturating_sub(BUMP_RECENT_PROPOSALS) + 1..=latest_proposal;
+
+    let group_keys = [
+        DataKey::GroupMetadata(group_id),
+        DataKey::Escrow(group_id),
+        DataKey::RefundProposalCount(group_id),
+        DataKey::ProposalCount(group_id),
+        DataKey::Invites(group_id),
+        DataKey::JoinRequests(group_id),
+        DataKey::RoundRecord(group_id, group.round.saturating_sub(1)),
+        DataKey::RefundRequest(group_id, latest_refund),
+    ];
+    for key in group_keys.iter() {
+        if persistent.has(key) {
+            extend_persistent(env, key, extend_to);
+        }
+    }
+    for proposal_id in recent_proposals.clone() {
+        extend_persistent(env, &DataKey::Proposal(group_id, proposal_id), extend_to);
+    }
+
+    // Pending applicants may already have locked collateral
+    for request in get_join_requests(env, group_id).iter() {
+        let key = DataKey::Collateral(group_id, request.member);
+        if persistent.has(&key) {
+            extend_persistent(env, &key, extend_to);
+        }
+    }
+
+    let member_count = group.members.len();
+    let live_cycles = group
+        .current_cycle
+        .saturating_sub(group.round_start_cycle)
+        .saturating_add(1);
+    let total_units = member_count.saturating_mul(live_cycles.saturating_add(1));
+    let end = cursor.saturating_add(BUMP_BATCH_SIZE).min(total_units);
+
+    for unit in cursor..end {
+        let member = group.members.get(unit % member_count).unwrap();
+        let part = unit / member_count;
+
+        if part == 0 {
+            let member_keys = [
+                DataKey::PayoutReceived(group_id, member.clone()),
+                DataKey::MemberPenalty(group_id, member.clone()),
+                DataKey::RefundVote(group_id, latest_refund, member.clone()),
+                DataKey::RefundRecord(group_id, member.clone()),
+                DataKey::Claimable(group_id, member.clone()),
+                DataKey::ExitRequest(group_id, member.clone()),
+                DataKey::Collateral(group_id, member.clone()),
+                DataKey::AutoContribute(group_id, member.clone()),
+                DataKey::PrepaidCycles(group_id, member.clone()),
+                DataKey::ShareWeight(group_id, member.clone()),
+                DataKey::MemberPosition(group_id, member.clone()),
+            ];
+            for key in member_keys.iter() {
+                if persistent.has(key) {
+                    extend_persistent(env, key, extend_to);
+                }
+            }
+            for proposal_id in recent_proposals.clone() {
+                let key = DataKey::ProposalVote(group_id, proposal_id, member.clone());
+                if persistent.has(&key) {
+                    extend_persistent(env, &key, extend_to);
+                }
+            }
+            continue;
+        }
+
+        let cycle = group.round_start_cycle + part - 1;
+        if unit % member_count == 0 {
+            let cycle_keys = [
+                DataKey::CyclePenaltyPool(group_id, cycle),
+                DataKey::PayoutRecord(group_id, cycle),
+            ];
+            for key in cycle_keys.iter() {
+                if persistent.has(key) {
+                    extend_persistent(env, key, extend_to);
+                }
+            }
+        }
+
+        let cycle_keys = [
+            DataKey::Contribution(group_id, cycle, member.clone()),
+            DataKey::ContributionDetail(group_id, cycle, member.clone()),
+            DataKey::Bid(group_id, cycle, member.clone()),
+            DataKey::DefaultRecord(group_id, cycle, member.clone()),
+            DataKey::PartialContribution(group_id, cycle, member.clone()),
+        ];
+        for key in cycle_keys.iter() {
+            if persistent.has(key) {
+                extend_persistent(env, key, extend_to);
+            }
         }
     }
+
+    (end < total_units).then_some(end)
 }
 
 /// Returns the next available group ID and atomically increments the counter.
@@ -85,13 +378,108 @@ impl StorageKey {
 /// # Returns
 /// The next available group ID (starting from 1)
 pub fn get_next_group_id(env: &Env) -> u64 {
-    let key = symbol_short!("GCOUNTER");
+    let key = DataKey::GroupCounter;
+    let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().instance().set(&key, &next);
+    extend_instance_ttl(env);
+    next
+}
+
+/// Returns the ID of the most recently created group, or `0` if none exist.
+///
+/// # Arguments
+/// * `env` - The contract environment used to access instance storage
+///
+/// # Returns
+/// The current value of the group counter
+pub fn get_group_counter(env: &Env) -> u64 {
+    let key = DataKey::GroupCounter;
+    env.storage().instance().get(&key).unwrap_or(0)
+}
+
+/// Overwrites the group counter in instance storage.
+///
+/// Only intended for storage migrations; normal group creation should use
+/// [`get_next_group_id`].
+///
+/// # Arguments
+/// * `env` - The contract environment used to access instance storage
+/// * `counter` - The new counter value
+pub fn set_group_counter(env: &Env, counter: u64) {
+    let key = DataKey::GroupCounter;
+    env.storage().instance().set(&key, &counter);
+    extend_instance_ttl(env);
+}
+
+/// Increments and returns the next template ID, starting from `1`.
+///
+/// # Arguments
+/// * `env` - The contract environment used to access instance storage
+///
+/// # Returns
+/// The next available template ID
+pub fn get_next_template_id(env: &Env) -> u64 {
+    let key = DataKey::TemplateCounter;
     let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
     let next = current + 1;
     env.storage().instance().set(&key, &next);
+    extend_instance_ttl(env);
     next
 }
 
+/// Stores a group template.
+///
+/// Templates don't belong to a group, so each read or write keeps them alive
+/// for [`MIN_GROUP_TTL`].
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `template` - The template to store under its ID
+pub fn store_template(env: &Env, template: &crate::types::GroupTemplate) {
+    let key = DataKey::Template(template.id);
+    env.storage().persistent().set(&key, template);
+    extend_persistent(env, &key, MIN_GROUP_TTL.min(env.storage().max_ttl()));
+}
+
+/// Retrieves a group template.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `template_id` - The template ID
+///
+/// # Returns
+/// The template, or `None` if it doesn't exist
+pub fn get_template(env: &Env, template_id: u64) -> Option<crate::types::GroupTemplate> {
+    let key = DataKey::Template(template_id);
+    let value: Option<crate::types::GroupTemplate> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_persistent(env, &key, MIN_GROUP_TTL.min(env.storage().max_ttl()));
+    }
+    value
+}
+
+/// Returns the next group ID `process_due_groups` should look at, or `1`
+/// if the keeper hasn't run yet.
+///
+/// # Arguments
+/// * `env` - The contract environment used to access instance storage
+pub fn get_keeper_cursor(env: &Env) -> u64 {
+    let key = DataKey::KeeperCursor;
+    env.storage().instance().get(&key).unwrap_or(1)
+}
+
+/// Stores the group ID the next `process_due_groups` call starts from.
+///
+/// # Arguments
+/// * `env` - The contract environment used to access instance storage
+/// * `group_id` - The next group ID to process
+pub fn set_keeper_cursor(env: &Env, group_id: u64) {
+    let key = DataKey::KeeperCursor;
+    env.storage().instance().set(&key, &group_id);
+    extend_instance_ttl(env);
+}
+
 /// Persists a [`Group`](crate::types::Group) to persistent ledger storage.
 ///
 /// This function writes or overwrites the group data for the given `group_id`.
@@ -102,8 +490,16 @@ pub fn get_next_group_id(env: &Env) -> u64 {
 /// * `group_id` - The unique identifier for the group
 /// * `group` - The group data to store
 pub fn store_group(env: &Env, group_id: u64, group: &crate::types::Group) {
-    let key = (symbol_short!("GROUP"), group_id);
-    env.storage().persistent().set(&key, group);
+    let key = DataKey::Group(group_id);
+    let ttl_key = DataKey::GroupTtl(group_id);
+    let extend_to = group_ttl(env, group);
+    let persistent = env.storage().persistent();
+    persistent.set(&key, group);
+    if persistent.get::<_, u32>(&ttl_key) != Some(extend_to) {
+        persistent.set(&ttl_key, &extend_to);
+    }
+    extend_persistent(env, &key, extend_to);
+    extend_persistent(env, &ttl_key, extend_to);
 }
 
 /// Retrieves a [`Group`](crate::types::Group) from persistent ledger storage.
@@ -119,8 +515,17 @@ pub fn store_group(env: &Env, group_id: u64, group: &crate::types::Group) {
 /// # Returns
 /// `Some(Group)` if the group exists, `None` otherwise
 pub fn get_group(env: &Env, group_id: u64) -> Option<crate::types::Group> {
-    let key = (symbol_short!("GROUP"), group_id);
-    env.storage().persistent().get(&key)
+    let key = DataKey::Group(group_id);
+    let group: Option<crate::types::Group> = env.storage().persistent().get(&key);
+    if let Some(ref group) = group {
+        let extend_to = group_ttl(env, group);
+        extend_persistent(env, &key, extend_to);
+        let ttl_key = DataKey::GroupTtl(group_id);
+        if env.storage().persistent().has(&ttl_key) {
+            extend_persistent(env, &ttl_key, extend_to);
+        }
+    }
+    group
 }
 
 /// Removes a group's record from persistent storage.
@@ -133,8 +538,9 @@ pub fn get_group(env: &Env, group_id: u64) -> Option<crate::types::Group> {
 /// * `env` - The contract environment used to access persistent storage
 /// * `group_id` - The unique identifier for the group to remove
 pub fn remove_group(env: &Env, group_id: u64) {
-    let key = (symbol_short!("GROUP"), group_id);
+    let key = DataKey::Group(group_id);
     env.storage().persistent().remove(&key);
+    env.storage().persistent().remove(&DataKey::GroupTtl(group_id));
 }
 
 /// Records whether a member has paid their contribution for a given cycle.
@@ -150,8 +556,9 @@ pub fn remove_group(env: &Env, group_id: u64) {
 /// * `member` - The contributing member's address
 /// * `paid` - `true` to mark as paid; `false` to reset (rarely needed)
 pub fn store_contribution(env: &Env, group_id: u64, cycle: u32, member: &Address, paid: bool) {
-    let key = (symbol_short!("CONTRIB"), group_id, cycle, member);
+    let key = DataKey::Contribution(group_id, cycle, member.clone());
     env.storage().persistent().set(&key, &paid);
+    extend_group_entry(env, group_id, &key);
 }
 
 /// Returns `true` if the given member has contributed during the specified cycle.
@@ -167,8 +574,12 @@ pub fn store_contribution(env: &Env, group_id: u64, cycle: u32, member: &Address
 /// # Returns
 /// `true` if the member has contributed, `false` otherwise
 pub fn has_contributed(env: &Env, group_id: u64, cycle: u32, member: &Address) -> bool {
-    let key = (symbol_short!("CONTRIB"), group_id, cycle, member);
-    env.storage().persistent().get(&key).unwrap_or(false)
+    let key = DataKey::Contribution(group_id, cycle, member.clone());
+    let value: Option<bool> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(false)
 }
 
 /// Records that the given member has received their payout for a group.
@@ -181,8 +592,40 @@ pub fn has_contributed(env: &Env, group_id: u64, cycle: u32, member: &Address) -
 /// * `group_id` - The group the payout belongs to
 /// * `member` - The address that received the payout
 pub fn mark_payout_received(env: &Env, group_id: u64, member: &Address) {
-    let key = (symbol_short!("PAYOUT"), group_id, member);
+    let key = DataKey::PayoutReceived(group_id, member.clone());
     env.storage().persistent().set(&key, &true);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Clears a member's payout flag when a new round starts.
+///
+/// # Arguments
+/// * `env` - The contract environment used to access persistent storage
+/// * `group_id` - The group
+/// * `member` - The member whose flag is cleared
+pub fn clear_payout_received(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::PayoutReceived(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Returns `true` if the given member has already received their payout for a group.
+///
+/// Defaults to `false` if no record exists.
+///
+/// # Arguments
+/// * `env` - The contract environment used to access persistent storage
+/// * `group_id` - The group to check
+/// * `member` - The member address to check
+///
+/// # Returns
+/// `true` if the payout has been distributed to the member, `false` otherwise
+pub fn has_received_payout(env: &Env, group_id: u64, member: &Address) -> bool {
+    let key = DataKey::PayoutReceived(group_id, member.clone());
+    let value: Option<bool> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(false)
 }
 
 /// Returns contribution status for every member in a cycle as an ordered vector.
@@ -225,8 +668,9 @@ pub fn get_cycle_contributions(
 /// * `env` - The contract environment used to access instance storage
 /// * `admin` - The address of the contract administrator
 pub fn store_admin(env: &Env, admin: &Address) {
-    let key = symbol_short!("ADMIN");
+    let key = DataKey::Admin;
     env.storage().instance().set(&key, admin);
+    extend_instance_ttl(env);
 }
 
 /// Retrieves the contract administrator address from instance storage.
@@ -240,8 +684,12 @@ pub fn store_admin(env: &Env, admin: &Address) {
 /// # Returns
 /// `Some(Address)` containing the admin address if initialized, `None` otherwise
 pub fn get_admin(env: &Env) -> Option<Address> {
-    let key = symbol_short!("ADMIN");
-    env.storage().instance().get(&key)
+    let key = DataKey::Admin;
+    let admin = env.storage().instance().get(&key);
+    if admin.is_some() {
+        extend_instance_ttl(env);
+    }
+    admin
 }
 
 /// Stores metadata for a group in persistent storage.
@@ -251,8 +699,9 @@ pub fn get_admin(env: &Env) -> Option<Address> {
 /// * `group_id` - The unique identifier for the group
 /// * `metadata` - The metadata struct to store
 pub fn store_group_metadata(env: &Env, group_id: u64, metadata: &crate::types::GroupMetadata) {
-    let key = (symbol_short!("METADATA"), group_id);
+    let key = DataKey::GroupMetadata(group_id);
     env.storage().persistent().set(&key, metadata);
+    extend_group_entry(env, group_id, &key);
 }
 
 /// Retrieves metadata for a group from persistent storage.
@@ -264,8 +713,12 @@ pub fn store_group_metadata(env: &Env, group_id: u64, metadata: &crate::types::G
 /// # Returns
 /// `Some(GroupMetadata)` if it exists, `None` otherwise
 pub fn get_group_metadata(env: &Env, group_id: u64) -> Option<crate::types::GroupMetadata> {
-    let key = (symbol_short!("METADATA"), group_id);
-    env.storage().persistent().get(&key)
+    let key = DataKey::GroupMetadata(group_id);
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
 }
 
 /// Checks if metadata exists for a group.
@@ -277,8 +730,12 @@ pub fn get_group_metadata(env: &Env, group_id: u64) -> Option<crate::types::Grou
 /// # Returns
 /// `true` if metadata exists, `false` otherwise
 pub fn has_group_metadata(env: &Env, group_id: u64) -> bool {
-    let key = (symbol_short!("METADATA"), group_id);
-    env.storage().persistent().has(&key)
+    let key = DataKey::GroupMetadata(group_id);
+    let exists = env.storage().persistent().has(&key);
+    if exists {
+        extend_group_entry(env, group_id, &key);
+    }
+    exists
 }
 
 /// Stores detailed contribution record with penalty information.
@@ -296,8 +753,9 @@ pub fn store_contribution_detail(
     member: &Address,
     record: &crate::types::ContributionRecord,
 ) {
-    let key = (symbol_short!("CONTREC"), group_id, cycle, member);
+    let key = DataKey::ContributionDetail(group_id, cycle, member.clone());
     env.storage().persistent().set(&key, record);
+    extend_group_entry(env, group_id, &key);
 }
 
 /// Retrieves detailed contribution record.
@@ -316,8 +774,12 @@ pub fn get_contribution_detail(
     cycle: u32,
     member: &Address,
 ) -> Option<crate::types::ContributionRecord> {
-    let key = (symbol_short!("CONTREC"), group_id, cycle, member);
-    env.storage().persistent().get(&key)
+    let key = DataKey::ContributionDetail(group_id, cycle, member.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
 }
 
 /// Stores or updates member penalty statistics.
@@ -333,8 +795,9 @@ pub fn store_member_penalty(
     member: &Address,
     record: &crate::types::MemberPenaltyRecord,
 ) {
-    let key = (symbol_short!("PENALTY"), group_id, member);
+    let key = DataKey::MemberPenalty(group_id, member.clone());
     env.storage().persistent().set(&key, record);
+    extend_group_entry(env, group_id, &key);
 }
 
 /// Retrieves member penalty statistics.
@@ -351,8 +814,12 @@ pub fn get_member_penalty(
     group_id: u64,
     member: &Address,
 ) -> Option<crate::types::MemberPenaltyRecord> {
-    let key = (symbol_short!("PENALTY"), group_id, member);
-    env.storage().persistent().get(&key)
+    let key = DataKey::MemberPenalty(group_id, member.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
 }
 
 /// Stores the penalty pool for a cycle.
@@ -363,8 +830,9 @@ pub fn get_member_penalty(
 /// * `cycle` - The cycle number
 /// * `amount` - Total penalties collected in this cycle
 pub fn store_cycle_penalty_pool(env: &Env, group_id: u64, cycle: u32, amount: i128) {
-    let key = (symbol_short!("PENPOOL"), group_id, cycle);
+    let key = DataKey::CyclePenaltyPool(group_id, cycle);
     env.storage().persistent().set(&key, &amount);
+    extend_group_entry(env, group_id, &key);
 }
 
 /// Retrieves the penalty pool for a cycle.
@@ -377,8 +845,12 @@ pub fn store_cycle_penalty_pool(env: &Env, group_id: u64, cycle: u32, amount: i1
 /// # Returns
 /// Total penalties collected, defaults to 0 if not set
 pub fn get_cycle_penalty_pool(env: &Env, group_id: u64, cycle: u32) -> i128 {
-    let key = (symbol_short!("PENPOOL"), group_id, cycle);
-    env.storage().persistent().get(&key).unwrap_or(0)
+    let key = DataKey::CyclePenaltyPool(group_id, cycle);
+    let value: Option<i128> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(0)
 }
 
 /// Adds a penalty amount to the cycle's penalty pool.
@@ -393,96 +865,246 @@ pub fn add_to_penalty_pool(env: &Env, group_id: u64, cycle: u32, penalty: i128)
     store_cycle_penalty_pool(env, group_id, cycle, current + penalty);
 }
 
-/// Stores a refund request for a group.
+/// Returns the next refund proposal ID for a group and records it as the
+/// group's latest proposal.
+///
+/// Proposal IDs start at 1 for every group.
 ///
 /// # Arguments
 /// * `env` - The contract environment
-/// * `group_id` - The group the refund request is for
-/// * `request` - The refund request data
-pub fn store_refund_request(env: &Env, group_id: u64, request: &crate::types::RefundRequest) {
-    let key = (symbol_short!("REFREQ"), group_id);
-    env.storage().persistent().set(&key, request);
+/// * `group_id` - The group the proposal is for
+pub fn get_next_refund_proposal_id(env: &Env, group_id: u64) -> u64 {
+    let key = DataKey::RefundProposalCount(group_id);
+    let next = get_refund_proposal_count(env, group_id) + 1;
+    env.storage().persistent().set(&key, &next);
+    extend_group_entry(env, group_id, &key);
+    next
 }
 
-/// Retrieves a refund request for a group.
+/// Returns how many refund proposals have been made for a group.
+///
+/// This is also the ID of the latest proposal, or 0 if there are none.
+pub fn get_refund_proposal_count(env: &Env, group_id: u64) -> u64 {
+    let key = DataKey::RefundProposalCount(group_id);
+    let value: Option<u64> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(0)
+}
+
+/// Stores a refund proposal under its group and proposal ID.
 ///
 /// # Arguments
 /// * `env` - The contract environment
-/// * `group_id` - The group to check
-///
-/// # Returns
-/// `Some(RefundRequest)` if exists, `None` otherwise
-pub fn get_refund_request(env: &Env, group_id: u64) -> Option<crate::types::RefundRequest> {
-    let key = (symbol_short!("REFREQ"), group_id);
-    env.storage().persistent().get(&key)
+/// * `request` - The refund request data
+pub fn store_refund_request(env: &Env, request: &crate::types::RefundRequest) {
+    let key = DataKey::RefundRequest(request.group_id, request.id);
+    env.storage().persistent().set(&key, request);
+    extend_group_entry(env, request.group_id, &key);
 }
 
-/// Checks if a refund request exists for a group.
+/// Retrieves one of a group's refund proposals.
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `group_id` - The group to check
+/// * `proposal_id` - The proposal ID
 ///
 /// # Returns
-/// `true` if a refund request exists, `false` otherwise
-pub fn has_refund_request(env: &Env, group_id: u64) -> bool {
-    let key = (symbol_short!("REFREQ"), group_id);
-    env.storage().persistent().has(&key)
+/// `Some(RefundRequest)` if exists, `None` otherwise
+pub fn get_refund_request(
+    env: &Env,
+    group_id: u64,
+    proposal_id: u64,
+) -> Option<crate::types::RefundRequest> {
+    let key = DataKey::RefundRequest(group_id, proposal_id);
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
 }
 
-/// Removes a refund request from storage.
+/// Retrieves a group's latest refund proposal.
 ///
-/// # Arguments
-/// * `env` - The contract environment
-/// * `group_id` - The group to remove the request for
-pub fn remove_refund_request(env: &Env, group_id: u64) {
-    let key = (symbol_short!("REFREQ"), group_id);
-    env.storage().persistent().remove(&key);
+/// # Returns
+/// `Some(RefundRequest)` if the group has any proposals, `None` otherwise
+pub fn get_latest_refund_request(env: &Env, group_id: u64) -> Option<crate::types::RefundRequest> {
+    match get_refund_proposal_count(env, group_id) {
+        0 => None,
+        proposal_id => get_refund_request(env, group_id, proposal_id),
+    }
 }
 
-/// Stores a member's vote on a refund request.
+/// Stores a member's vote on a refund proposal.
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `group_id` - The group
+/// * `proposal_id` - The proposal voted on
 /// * `member` - The voting member's address
 /// * `vote` - The vote record
 pub fn store_refund_vote(
     env: &Env,
     group_id: u64,
+    proposal_id: u64,
     member: &Address,
     vote: &crate::types::RefundVote,
 ) {
-    let key = (symbol_short!("REFVOTE"), group_id, member);
+    let key = DataKey::RefundVote(group_id, proposal_id, member.clone());
     env.storage().persistent().set(&key, vote);
+    extend_group_entry(env, group_id, &key);
 }
 
-/// Retrieves a member's vote on a refund request.
+/// Retrieves a member's vote on a refund proposal.
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `group_id` - The group
+/// * `proposal_id` - The proposal voted on
 /// * `member` - The member's address
 ///
 /// # Returns
 /// `Some(RefundVote)` if the member has voted, `None` otherwise
-pub fn get_refund_vote(env: &Env, group_id: u64, member: &Address) -> Option<crate::types::RefundVote> {
-    let key = (symbol_short!("REFVOTE"), group_id, member);
-    env.storage().persistent().get(&key)
+pub fn get_refund_vote(
+    env: &Env,
+    group_id: u64,
+    proposal_id: u64,
+    member: &Address,
+) -> Option<crate::types::RefundVote> {
+    let key = DataKey::RefundVote(group_id, proposal_id, member.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
 }
 
-/// Checks if a member has voted on a refund request.
+/// Checks if a member has voted on a refund proposal.
 ///
 /// # Arguments
 /// * `env` - The contract environment
 /// * `group_id` - The group
+/// * `proposal_id` - The proposal
 /// * `member` - The member's address
 ///
 /// # Returns
 /// `true` if the member has voted, `false` otherwise
-pub fn has_voted(env: &Env, group_id: u64, member: &Address) -> bool {
-    let key = (symbol_short!("REFVOTE"), group_id, member);
-    env.storage().persistent().has(&key)
+pub fn has_voted(env: &Env, group_id: u64, proposal_id: u64, member: &Address) -> bool {
+    let key = DataKey::RefundVote(group_id, proposal_id, member.clone());
+    let exists = env.storage().persistent().has(&key);
+    if exists {
+        extend_group_entry(env, group_id, &key);
+    }
+    exists
+}
+
+/// Removes a member's vote on a refund proposal.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `proposal_id` - The proposal
+/// * `member` - The member's address
+pub fn remove_refund_vote(env: &Env, group_id: u64, proposal_id: u64, member: &Address) {
+    let key = DataKey::RefundVote(group_id, proposal_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Returns the next governance proposal ID for a group.
+///
+/// Proposal IDs start at 1 for every group, separately from refund proposals.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group the proposal is for
+pub fn get_next_proposal_id(env: &Env, group_id: u64) -> u64 {
+    let key = DataKey::ProposalCount(group_id);
+    let next = get_proposal_count(env, group_id) + 1;
+    env.storage().persistent().set(&key, &next);
+    extend_group_entry(env, group_id, &key);
+    next
+}
+
+/// Returns how many governance proposals have been made for a group.
+pub fn get_proposal_count(env: &Env, group_id: u64) -> u64 {
+    let key = DataKey::ProposalCount(group_id);
+    let value: Option<u64> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(0)
+}
+
+/// Stores a governance proposal under its group and proposal ID.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `proposal` - The proposal data
+pub fn store_proposal(env: &Env, proposal: &crate::types::Proposal) {
+    let key = DataKey::Proposal(proposal.group_id, proposal.id);
+    env.storage().persistent().set(&key, proposal);
+    extend_group_entry(env, proposal.group_id, &key);
+}
+
+/// Retrieves one of a group's governance proposals.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `proposal_id` - The proposal ID
+///
+/// # Returns
+/// `Some(Proposal)` if exists, `None` otherwise
+pub fn get_proposal(env: &Env, group_id: u64, proposal_id: u64) -> Option<crate::types::Proposal> {
+    let key = DataKey::Proposal(group_id, proposal_id);
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Stores a member's vote on a governance proposal.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `vote` - The vote record
+pub fn store_proposal_vote(env: &Env, vote: &crate::types::ProposalVote) {
+    let key = DataKey::ProposalVote(vote.group_id, vote.proposal_id, vote.voter.clone());
+    env.storage().persistent().set(&key, vote);
+    extend_group_entry(env, vote.group_id, &key);
+}
+
+/// Retrieves a member's vote on a governance proposal.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `proposal_id` - The proposal voted on
+/// * `member` - The member's address
+///
+/// # Returns
+/// `Some(ProposalVote)` if the member has voted, `None` otherwise
+pub fn get_proposal_vote(
+    env: &Env,
+    group_id: u64,
+    proposal_id: u64,
+    member: &Address,
+) -> Option<crate::types::ProposalVote> {
+    let key = DataKey::ProposalVote(group_id, proposal_id, member.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Removes a member's vote on a governance proposal.
+pub fn remove_proposal_vote(env: &Env, group_id: u64, proposal_id: u64, member: &Address) {
+    let key = DataKey::ProposalVote(group_id, proposal_id, member.clone());
+    env.storage().persistent().remove(&key);
 }
 
 /// Stores a refund record.
@@ -498,8 +1120,9 @@ pub fn store_refund_record(
     member: &Address,
     record: &crate::types::RefundRecord,
 ) {
-    let key = (symbol_short!("REFUND"), group_id, member);
+    let key = DataKey::RefundRecord(group_id, member.clone());
     env.storage().persistent().set(&key, record);
+    extend_group_entry(env, group_id, &key);
 }
 
 /// Retrieves a refund record for a member.
@@ -516,6 +1139,754 @@ pub fn get_refund_record(
     group_id: u64,
     member: &Address,
 ) -> Option<crate::types::RefundRecord> {
-    let key = (symbol_short!("REFUND"), group_id, member);
-    env.storage().persistent().get(&key)
+    let key = DataKey::RefundRecord(group_id, member.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Stores the payout record for a group's cycle.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle the payout was made in
+/// * `record` - The payout record
+pub fn store_payout_record(
+    env: &Env,
+    group_id: u64,
+    cycle: u32,
+    record: &crate::types::PayoutRecord,
+) {
+    let key = DataKey::PayoutRecord(group_id, cycle);
+    env.storage().persistent().set(&key, record);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Retrieves the payout record for a group's cycle.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle number
+///
+/// # Returns
+/// `Some(PayoutRecord)` if a payout was made in that cycle, `None` otherwise
+pub fn get_payout_record(
+    env: &Env,
+    group_id: u64,
+    cycle: u32,
+) -> Option<crate::types::PayoutRecord> {
+    let key = DataKey::PayoutRecord(group_id, cycle);
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Stores a member's auction bid for a cycle, replacing any earlier bid.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle being bid on
+/// * `member` - The bidding member's address
+/// * `bid` - The bid
+pub fn store_bid(env: &Env, group_id: u64, cycle: u32, member: &Address, bid: &crate::types::Bid) {
+    let key = DataKey::Bid(group_id, cycle, member.clone());
+    env.storage().persistent().set(&key, bid);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Retrieves a member's auction bid for a cycle.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle number
+/// * `member` - The member's address
+///
+/// # Returns
+/// `Some(Bid)` if the member has bid in that cycle, `None` otherwise
+pub fn get_bid(
+    env: &Env,
+    group_id: u64,
+    cycle: u32,
+    member: &Address,
+) -> Option<crate::types::Bid> {
+    let key = DataKey::Bid(group_id, cycle, member.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Retrieves the balance a member can claim from a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+///
+/// # Returns
+/// The claimable amount, defaults to 0 if nothing has been credited
+pub fn get_claimable(env: &Env, group_id: u64, member: &Address) -> i128 {
+    let key = DataKey::Claimable(group_id, member.clone());
+    let value: Option<i128> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(0)
+}
+
+/// Adds an amount to a member's claimable balance for a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+/// * `amount` - Amount to credit
+pub fn add_claimable(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let key = DataKey::Claimable(group_id, member.clone());
+    let current = get_claimable(env, group_id, member);
+    env.storage().persistent().set(&key, &(current + amount));
+    extend_group_entry(env, group_id, &key);
+
+    // Index the group so `claim_all` can find the balance
+    let index_key = DataKey::ClaimableGroups(member.clone());
+    let mut groups = get_claimable_groups(env, member);
+    if !groups.contains(group_id) {
+        groups.push_back(group_id);
+        env.storage().persistent().set(&index_key, &groups);
+    }
+    extend_group_entry(env, group_id, &index_key);
+}
+
+/// Removes a member's claimable balance for a group once it has been withdrawn.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+pub fn remove_claimable(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::Claimable(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+
+    let index_key = DataKey::ClaimableGroups(member.clone());
+    let mut groups = get_claimable_groups(env, member);
+    if let Some(index) = groups.first_index_of(group_id) {
+        groups.remove(index);
+        if groups.is_empty() {
+            env.storage().persistent().remove(&index_key);
+        } else {
+            env.storage().persistent().set(&index_key, &groups);
+        }
+    }
+}
+
+/// Retrieves the groups in which an address has a claimable balance.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `member` - The address
+///
+/// # Returns
+/// The group IDs in the order balances were first credited
+pub fn get_claimable_groups(env: &Env, member: &Address) -> Vec<u64> {
+    let key = DataKey::ClaimableGroups(member.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Stores a member's exit request.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+/// * `request` - The exit request
+pub fn store_exit_request(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    request: &crate::types::ExitRequest,
+) {
+    let key = DataKey::ExitRequest(group_id, member.clone());
+    env.storage().persistent().set(&key, request);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Retrieves a member's pending exit request.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+///
+/// # Returns
+/// `Some(ExitRequest)` if the member has a pending request, `None` otherwise
+pub fn get_exit_request(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+) -> Option<crate::types::ExitRequest> {
+    let key = DataKey::ExitRequest(group_id, member.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Removes a member's exit request once it has been approved or rejected.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+pub fn remove_exit_request(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::ExitRequest(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Moves a member's per-slot state in a group to a new address.
+///
+/// Moves the payout flag, share weight, penalty statistics, votes on the
+/// latest refund proposal and on governance proposals, and the contribution
+/// flags, installments, contribution records, bids and defaults for every
+/// cycle up to the group's current one. Address fields inside moved records
+/// are rewritten to `to`. Any pending exit request or auto-contribution
+/// mandate for `from` is dropped, since `to` hasn't authorized either.
+/// Claimable balances and refund records stay with `from`, since they
+/// represent funds owed to that address.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group` - The group the slot belongs to
+/// * `from` - The current holder of the slot
+/// * `to` - The new holder of the slot
+pub fn transfer_member_state(env: &Env, group: &crate::types::Group, from: &Address, to: &Address) {
+    let group_id = group.id;
+    let persistent = env.storage().persistent();
+
+    if has_received_payout(env, group_id, from) {
+        persistent.remove(&DataKey::PayoutReceived(group_id, from.clone()));
+        mark_payout_received(env, group_id, to);
+    }
+
+    let position_key = DataKey::MemberPosition(group_id, from.clone());
+    if let Some(position) = persistent.get::<_, crate::types::MemberPosition>(&position_key) {
+        persistent.remove(&position_key);
+        store_member_position(env, group_id, to, &position);
+    }
+
+    let weight_key = DataKey::ShareWeight(group_id, from.clone());
+    if let Some(weight) = persistent.get::<_, u32>(&weight_key) {
+        persistent.remove(&weight_key);
+        store_share_weight(env, group_id, to, weight);
+    }
+
+    if let Some(mut record) = get_member_penalty(env, group_id, from) {
+        persistent.remove(&DataKey::MemberPenalty(group_id, from.clone()));
+        record.member = to.clone();
+        store_member_penalty(env, group_id, to, &record);
+    }
+
+    // Votes on earlier proposals are removed once they are rejected
+    let latest_proposal = get_refund_proposal_count(env, group_id);
+    if let Some(mut vote) = get_refund_vote(env, group_id, latest_proposal, from) {
+        remove_refund_vote(env, group_id, latest_proposal, from);
+        vote.voter = to.clone();
+        store_refund_vote(env, group_id, latest_proposal, to, &vote);
+    }
+
+    // Votes on closed governance proposals are removed when they close
+    for proposal_id in 1..=get_proposal_count(env, group_id) {
+        if let Some(mut vote) = get_proposal_vote(env, group_id, proposal_id, from) {
+            remove_proposal_vote(env, group_id, proposal_id, from);
+            vote.voter = to.clone();
+            store_proposal_vote(env, &vote);
+
+            if let Some(mut proposal) = get_proposal(env, group_id, proposal_id) {
+                if let Some(index) = proposal.voters.first_index_of(from) {
+                    proposal.voters.set(index, to.clone());
+                    store_proposal(env, &proposal);
+                }
+            }
+        }
+    }
+
+    for cycle in 1..=group.current_cycle {
+        let contribution_key = DataKey::Contribution(group_id, cycle, from.clone());
+        if let Some(paid) = persistent.get::<_, bool>(&contribution_key) {
+            persistent.remove(&contribution_key);
+            store_contribution(env, group_id, cycle, to, paid);
+        }
+
+        let partial = get_partial_contribution(env, group_id, cycle, from);
+        if partial > 0 {
+            remove_partial_contribution(env, group_id, cycle, from);
+            store_partial_contribution(env, group_id, cycle, to, partial);
+        }
+
+        if let Some(mut record) = get_contribution_detail(env, group_id, cycle, from) {
+            persistent.remove(&DataKey::ContributionDetail(group_id, cycle, from.clone()));
+            record.member = to.clone();
+            store_contribution_detail(env, group_id, cycle, to, &record);
+        }
+
+        if let Some(mut bid) = get_bid(env, group_id, cycle, from) {
+            persistent.remove(&DataKey::Bid(group_id, cycle, from.clone()));
+            bid.bidder = to.clone();
+            store_bid(env, group_id, cycle, to, &bid);
+        }
+
+        if let Some(mut record) = get_default_record(env, group_id, cycle, from) {
+            persistent.remove(&DataKey::DefaultRecord(group_id, cycle, from.clone()));
+            record.member = to.clone();
+            store_default_record(env, group_id, cycle, to, &record);
+        }
+    }
+
+    remove_exit_request(env, group_id, from);
+    remove_auto_contribute(env, group_id, from);
+}
+
+/// Retrieves the addresses invited to join a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+///
+/// # Returns
+/// The invited addresses, empty if nobody has been invited
+pub fn get_invites(env: &Env, group_id: u64) -> Vec<Address> {
+    let key = DataKey::Invites(group_id);
+    let value: Option<Vec<Address>> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(Vec::new(env))
+}
+
+/// Stores the addresses invited to join a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `invites` - The invited addresses
+pub fn store_invites(env: &Env, group_id: u64, invites: &Vec<Address>) {
+    let key = DataKey::Invites(group_id);
+    env.storage().persistent().set(&key, invites);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Retrieves the pending requests to join a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+///
+/// # Returns
+/// The pending requests in the order they were made
+pub fn get_join_requests(env: &Env, group_id: u64) -> Vec<crate::types::JoinRequest> {
+    let key = DataKey::JoinRequests(group_id);
+    let value: Option<Vec<crate::types::JoinRequest>> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(Vec::new(env))
+}
+
+/// Stores the pending requests to join a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `requests` - The pending requests
+pub fn store_join_requests(env: &Env, group_id: u64, requests: &Vec<crate::types::JoinRequest>) {
+    let key = DataKey::JoinRequests(group_id);
+    env.storage().persistent().set(&key, requests);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Retrieves the collateral a member has locked in a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+///
+/// # Returns
+/// The locked amount, or 0 if none
+pub fn get_collateral(env: &Env, group_id: u64, member: &Address) -> i128 {
+    let key = DataKey::Collateral(group_id, member.clone());
+    let value: Option<i128> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(0)
+}
+
+/// Stores the collateral a member has locked in a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+/// * `amount` - The locked amount
+pub fn store_collateral(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let key = DataKey::Collateral(group_id, member.clone());
+    env.storage().persistent().set(&key, &amount);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Removes a member's collateral record once it has been released.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+pub fn remove_collateral(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::Collateral(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Stores a member's default on a cycle.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle the member defaulted on
+/// * `member` - The member's address
+/// * `record` - The default record
+pub fn store_default_record(
+    env: &Env,
+    group_id: u64,
+    cycle: u32,
+    member: &Address,
+    record: &crate::types::DefaultRecord,
+) {
+    let key = DataKey::DefaultRecord(group_id, cycle, member.clone());
+    env.storage().persistent().set(&key, record);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Retrieves a member's default on a cycle.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle number
+/// * `member` - The member's address
+///
+/// # Returns
+/// `Some(DefaultRecord)` if the member defaulted on that cycle, `None` otherwise
+pub fn get_default_record(
+    env: &Env,
+    group_id: u64,
+    cycle: u32,
+    member: &Address,
+) -> Option<crate::types::DefaultRecord> {
+    let key = DataKey::DefaultRecord(group_id, cycle, member.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Retrieves how many more cycles `collect_contribution` may pay for a member.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+///
+/// # Returns
+/// The number of remaining cycles, or 0 if auto-contribution is off
+pub fn get_auto_contribute(env: &Env, group_id: u64, member: &Address) -> u32 {
+    let key = DataKey::AutoContribute(group_id, member.clone());
+    let value: Option<u32> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(0)
+}
+
+/// Stores how many more cycles `collect_contribution` may pay for a member.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+/// * `cycles` - The number of remaining cycles
+pub fn store_auto_contribute(env: &Env, group_id: u64, member: &Address, cycles: u32) {
+    let key = DataKey::AutoContribute(group_id, member.clone());
+    env.storage().persistent().set(&key, &cycles);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Turns off auto-contribution for a member.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+pub fn remove_auto_contribute(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::AutoContribute(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Retrieves how much a member has paid towards a cycle's contribution in
+/// installments that don't yet add up to the full amount.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle number
+/// * `member` - The member's address
+///
+/// # Returns
+/// The amount paid so far, or 0 if none
+pub fn get_partial_contribution(env: &Env, group_id: u64, cycle: u32, member: &Address) -> i128 {
+    let key = DataKey::PartialContribution(group_id, cycle, member.clone());
+    let value: Option<i128> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(0)
+}
+
+/// Stores how much a member has paid towards a cycle's contribution so far.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle number
+/// * `member` - The member's address
+/// * `amount` - The amount paid so far
+pub fn store_partial_contribution(
+    env: &Env,
+    group_id: u64,
+    cycle: u32,
+    member: &Address,
+    amount: i128,
+) {
+    let key = DataKey::PartialContribution(group_id, cycle, member.clone());
+    env.storage().persistent().set(&key, &amount);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Removes a member's installments for a cycle once the contribution is
+/// complete.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `cycle` - The cycle number
+/// * `member` - The member's address
+pub fn remove_partial_contribution(env: &Env, group_id: u64, cycle: u32, member: &Address) {
+    let key = DataKey::PartialContribution(group_id, cycle, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Retrieves how many future cycles a member has paid for in advance.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+///
+/// # Returns
+/// The number of prepaid cycles, or 0 if none
+pub fn get_prepaid_cycles(env: &Env, group_id: u64, member: &Address) -> u32 {
+    let key = DataKey::PrepaidCycles(group_id, member.clone());
+    let value: Option<u32> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(0)
+}
+
+/// Stores how many future cycles a member has paid for in advance.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+/// * `cycles` - The number of prepaid cycles
+pub fn store_prepaid_cycles(env: &Env, group_id: u64, member: &Address, cycles: u32) {
+    let key = DataKey::PrepaidCycles(group_id, member.clone());
+    env.storage().persistent().set(&key, &cycles);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Removes a member's prepaid cycles once they are used up or refunded.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+pub fn remove_prepaid_cycles(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::PrepaidCycles(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Retrieves a member's share weight.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+///
+/// # Returns
+/// The share weight, or [`FULL_SHARE_WEIGHT`](crate::types::FULL_SHARE_WEIGHT)
+/// if the member hasn't chosen one
+pub fn get_share_weight(env: &Env, group_id: u64, member: &Address) -> u32 {
+    let key = DataKey::ShareWeight(group_id, member.clone());
+    let value: Option<u32> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or(crate::types::FULL_SHARE_WEIGHT)
+}
+
+/// Stores a member's share weight.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+/// * `weight` - The share weight
+pub fn store_share_weight(env: &Env, group_id: u64, member: &Address, weight: u32) {
+    let key = DataKey::ShareWeight(group_id, member.clone());
+    env.storage().persistent().set(&key, &weight);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Removes a member's share weight when they leave a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+pub fn remove_share_weight(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::ShareWeight(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Retrieves a member's running position in a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+///
+/// # Returns
+/// The position, all zero if the member hasn't paid or received anything
+pub fn get_member_position(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+) -> crate::types::MemberPosition {
+    let key = DataKey::MemberPosition(group_id, member.clone());
+    let value: Option<crate::types::MemberPosition> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value.unwrap_or_default()
+}
+
+/// Stores a member's running position in a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+/// * `position` - The position
+pub fn store_member_position(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    position: &crate::types::MemberPosition,
+) {
+    let key = DataKey::MemberPosition(group_id, member.clone());
+    env.storage().persistent().set(&key, position);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Removes a member's position when they leave a group.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `member` - The member's address
+pub fn remove_member_position(env: &Env, group_id: u64, member: &Address) {
+    let key = DataKey::MemberPosition(group_id, member.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Retrieves the record of a completed round.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `round` - The round number
+///
+/// # Returns
+/// The `RoundRecord`, or `None` if the round hasn't completed
+pub fn get_round_record(env: &Env, group_id: u64, round: u32) -> Option<crate::types::RoundRecord> {
+    let key = DataKey::RoundRecord(group_id, round);
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Stores the record of a completed round.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+/// * `record` - The round's record
+pub fn store_round_record(env: &Env, group_id: u64, record: &crate::types::RoundRecord) {
+    let key = DataKey::RoundRecord(group_id, record.round);
+    env.storage().persistent().set(&key, record);
+    extend_group_entry(env, group_id, &key);
+}
+
+/// Retrieves a group's escrow ledger.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `group_id` - The group
+///
+/// # Returns
+/// The ledger, or `None` for groups created before escrow was tracked
+pub fn get_escrow(env: &Env, group_id: u64) -> Option<crate::types::GroupEscrow> {
+    let key = DataKey::Escrow(group_id);
+    let value: Option<crate::types::GroupEscrow> = env.storage().persistent().get(&key);
+    if value.is_some() {
+        extend_group_entry(env, group_id, &key);
+    }
+    value
+}
+
+/// Stores a group's escrow ledger.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `escrow` - The ledger to store under its group ID
+pub fn store_escrow(env: &Env, escrow: &crate::types::GroupEscrow) {
+    let key = DataKey::Escrow(escrow.group_id);
+    env.storage().persistent().set(&key, escrow);
+    extend_group_entry(env, escrow.group_id, &key);
 }
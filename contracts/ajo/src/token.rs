@@ -31,6 +31,39 @@ pub fn transfer_token(
     Ok(())
 }
 
+/// Transfer tokens on behalf of `from` using an allowance granted to `spender`.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `token_address` - Address of the token contract
+/// * `spender` - Address the allowance was granted to
+/// * `from` - Address to transfer from
+/// * `to` - Address to transfer to
+/// * `amount` - Amount to transfer
+///
+/// # Returns
+/// `Ok(())` on successful transfer
+///
+/// # Errors
+/// * `InsufficientAllowance` - If `spender` may not move `amount` from `from`
+pub fn transfer_token_from(
+    env: &Env,
+    token_address: &Address,
+    spender: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), AjoError> {
+    let client = token::Client::new(env, token_address);
+    if client.allowance(from, spender) < amount {
+        return Err(AjoError::InsufficientAllowance);
+    }
+
+    client.transfer_from(spender, from, to, &amount);
+
+    Ok(())
+}
+
 /// Get the token balance of an address.
 ///
 /// # Arguments
@@ -11,6 +11,145 @@ pub enum GroupState {
     Cancelled = 1,
     /// Group has completed all cycles successfully.
     Complete = 2,
+    /// Group is waiting for members and hasn't started its first cycle.
+    Forming = 3,
+}
+
+/// Strategy used to decide the order in which members receive payouts.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PayoutStrategy {
+    /// Members are paid in the order they joined.
+    Fixed = 0,
+    /// The order is shuffled with the ledger PRNG when the group starts.
+    Randomized = 1,
+    /// Members bid a discount each cycle to receive the pot earlier.
+    /// Falls back to `payout_order` when nobody bids.
+    BidBased = 2,
+    /// The creator sets the order (e.g. by need) before the first contribution.
+    NeedBased = 3,
+}
+
+/// Who may join a group.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum JoinPolicy {
+    /// Any address may join until the group is full.
+    #[default]
+    Open = 0,
+    /// Only addresses invited by the creator may join.
+    InviteOnly = 1,
+    /// Invited addresses join directly; anyone else must request to join
+    /// and be approved by the creator.
+    ApprovalRequired = 2,
+}
+
+/// Optional settings chosen when a group is created.
+///
+/// `GroupSettings::default()` gives an open group that can start with two
+/// members, allows one full-share slot per member, runs a single round,
+/// requires no collateral and pays no keeper fee.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GroupSettings {
+    /// Who may join the group.
+    pub join_policy: JoinPolicy,
+
+    /// Members needed before the creator can start the group. Values below
+    /// 2 use the minimum group size of 2.
+    pub min_members: u32,
+
+    /// Security deposit each member locks when joining, or 0 for none.
+    /// It covers contributions the member misses and is returned once the
+    /// group completes.
+    pub collateral_amount: i128,
+
+    /// Reward credited to whoever advances the group with `poke`, taken from
+    /// the current cycle's penalty pool. Capped at what the pool holds.
+    pub keeper_fee: i128,
+
+    /// Payout slots ("hands") a single member may hold. Each slot has its own
+    /// payout position and adds one `contribution_amount` to the member's
+    /// contribution every cycle. Values below 1 allow a single slot.
+    pub max_slots_per_member: u32,
+
+    /// Whether members may pick their own share weight with
+    /// `set_share_weight` while the group is forming. Otherwise everyone
+    /// contributes [`FULL_SHARE_WEIGHT`].
+    pub variable_shares: bool,
+
+    /// Number of full rotations to run. When a round ends the next one starts
+    /// straight away with the same members, until the last round completes
+    /// the group. Values below 1 run a single round; `u32::MAX` keeps the
+    /// circle going indefinitely.
+    pub rounds: u32,
+
+    /// Quorum, approval threshold and voting period for member votes.
+    pub governance: GovernanceSettings,
+}
+
+/// Rules for member votes on a group: refund requests and [`Proposal`]s.
+///
+/// Zero values use the contract defaults: [`DEFAULT_QUORUM`],
+/// [`REFUND_APPROVAL_THRESHOLD`] and [`VOTING_PERIOD`]. Zero action
+/// thresholds fall back to `threshold` for rule changes and to
+/// [`SUPERMAJORITY_THRESHOLD`] for removing a member or replacing the creator.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GovernanceSettings {
+    /// Percentage of members that must cast a vote, abstentions included,
+    /// for the result to count.
+    pub quorum: u32,
+
+    /// Percentage of the for and against votes that must be in favour.
+    /// Abstentions count towards the quorum only.
+    pub threshold: u32,
+
+    /// Seconds a vote stays open after it is requested.
+    pub voting_period: u64,
+
+    /// Approval threshold for proposals changing the grace period, penalty
+    /// rate, cycle duration or payout order.
+    pub amendment_threshold: u32,
+
+    /// Approval threshold for proposals removing a member.
+    pub removal_threshold: u32,
+
+    /// Approval threshold for proposals replacing the creator.
+    pub creator_threshold: u32,
+}
+
+/// The parameters a group is created with.
+///
+/// Mirrors the arguments of `create_group`, so a parameter set can be saved
+/// as a [`GroupTemplate`] or copied from an existing group.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupParams {
+    pub token_address: Address,
+    pub contribution_amount: i128,
+    pub cycle_duration: u64,
+    pub max_members: u32,
+    pub grace_period: u64,
+    pub penalty_rate: u32,
+    pub payout_strategy: PayoutStrategy,
+    pub settings: GroupSettings,
+}
+
+/// A reusable parameter set saved with `create_template`.
+///
+/// Anyone can create a group from a template with
+/// `create_group_from_template`; the template itself never changes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupTemplate {
+    pub id: u64,
+    /// Address that saved the template.
+    pub owner: Address,
+    pub params: GroupParams,
+    pub created_at: u64,
 }
 
 /// Represents an Ajo group configuration and state.
@@ -41,10 +180,15 @@ pub struct Group {
     /// Supports Stellar Asset Contract (SAC) tokens including XLM, USDC, and custom tokens.
     pub token_address: Address,
 
-    /// Ordered list of member addresses.
-    /// Members receive payouts in the order they appear in this list.
+    /// Member addresses in join order.
     pub members: Vec<Address>,
 
+    /// Order in which members receive payouts, materialized according to
+    /// `payout_strategy`. Holds one entry per slot, so a member holding
+    /// several slots appears once for each. Entries before `payout_index`
+    /// have already been paid.
+    pub payout_order: Vec<Address>,
+
     /// Unique group identifier, auto-incremented from storage counter
     pub id: u64,
 
@@ -57,20 +201,28 @@ pub struct Group {
 
     /// Unix timestamp (seconds) when the current cycle started.
     /// Used together with `cycle_duration` to calculate when the cycle ends.
+    /// Set when the group starts; equals `created_at` while `Forming`.
     pub cycle_start_time: u64,
 
-    /// Maximum number of members allowed in the group.
-    /// Must be between 2 and 100 (inclusive).
+    /// Maximum number of slots in the group, which is also the number of
+    /// cycles in a full rotation. With one slot per member this is the member
+    /// limit. Must be between 2 and 100 (inclusive).
     pub max_members: u32,
 
     /// Current cycle number, starts at 1 and increments after each payout.
     pub current_cycle: u32,
 
-    /// Zero-based index into `members` indicating who receives the next payout.
-    /// When `payout_index == members.len()`, the group is complete.
+    /// Zero-based index into `payout_order` indicating who receives the next payout.
+    /// When `payout_index == payout_order.len()`, the current round is over.
     pub payout_index: u32,
 
-    /// Whether the group has completed all payout cycles.
+    /// Current round (full rotation), starting at 1. See `settings.rounds`.
+    pub round: u32,
+
+    /// The cycle the current round started with.
+    pub round_start_cycle: u32,
+
+    /// Whether the group has completed all of its rounds.
     /// Once `true`, no further contributions or payouts are accepted.
     pub is_complete: bool,
 
@@ -84,8 +236,14 @@ pub struct Group {
     /// Penalties are added to the group pool for the next recipient.
     pub penalty_rate: u32,
 
-    /// Current state of the group (Active, Cancelled, or Complete).
+    /// Current state of the group (Forming, Active, Cancelled, or Complete).
     pub state: GroupState,
+
+    /// How the payout order is determined.
+    pub payout_strategy: PayoutStrategy,
+
+    /// Optional settings chosen at creation.
+    pub settings: GroupSettings,
 }
 
 /// Comprehensive snapshot of a group's current state.
@@ -142,6 +300,16 @@ pub struct GroupStatus {
 
     /// Unix timestamp when grace period ends.
     pub grace_period_end_time: u64,
+
+    /// How the payout order is determined.
+    pub payout_strategy: PayoutStrategy,
+
+    /// Members in the order they will receive payouts.
+    /// Entries before the next recipient have already been paid.
+    pub payout_order: Vec<Address>,
+
+    /// Current state of the group.
+    pub state: GroupState,
 }
 
 /// Optional metadata for a group.
@@ -160,13 +328,19 @@ pub const MAX_NAME_LENGTH: u32 = 50;
 pub const MAX_DESCRIPTION_LENGTH: u32 = 250;
 pub const MAX_RULES_LENGTH: u32 = 1000;
 
-/// Tracks a refund request initiated by a member.
+/// Tracks a refund request (proposal) initiated by a member.
+///
+/// A group can have one open proposal at a time. Earlier proposals stay
+/// stored as the group's proposal history.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RefundRequest {
     /// The group this refund request is for.
     pub group_id: u64,
 
+    /// Proposal ID, counting up from 1 within the group.
+    pub id: u64,
+
     /// Address of the member who initiated the request.
     pub requester: Address,
 
@@ -182,6 +356,9 @@ pub struct RefundRequest {
     /// Number of votes against the refund.
     pub votes_against: u32,
 
+    /// Number of members who abstained.
+    pub votes_abstain: u32,
+
     /// Whether the request has been executed.
     pub executed: bool,
 
@@ -189,6 +366,17 @@ pub struct RefundRequest {
     pub approved: bool,
 }
 
+/// A member's choice in a vote.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VoteChoice {
+    Against = 0,
+    For = 1,
+    /// Counts towards the quorum without taking a side.
+    Abstain = 2,
+}
+
 /// Records a member's vote on a refund request.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -196,13 +384,99 @@ pub struct RefundVote {
     /// The group this vote is for.
     pub group_id: u64,
 
+    /// The refund proposal voted on.
+    pub proposal_id: u64,
+
     /// Address of the member who voted.
     pub voter: Address,
 
-    /// Whether the vote is in favor (true) or against (false).
-    pub in_favor: bool,
+    /// How the member voted.
+    pub choice: VoteChoice,
 
-    /// Unix timestamp when the vote was cast.
+    /// Unix timestamp when the vote was cast or last changed.
+    pub timestamp: u64,
+}
+
+/// A change to a group that members can propose and vote on.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalAction {
+    /// Sets the grace period after each cycle, in seconds.
+    SetGracePeriod(u64),
+    /// Sets the penalty rate for late contributions, as a percentage.
+    SetPenaltyRate(u32),
+    /// Sets the cycle duration in seconds, starting with the current cycle.
+    SetCycleDuration(u64),
+    /// Reorders the slots not yet paid out in the current round.
+    SetPayoutOrder(Vec<Address>),
+    /// Removes a member who hasn't been paid out, as with an approved exit.
+    RemoveMember(Address),
+    /// Hands the creator role to another member.
+    ReplaceCreator(Address),
+}
+
+/// A governance proposal made by a member with `propose`.
+///
+/// Any number of proposals can be open at once; each is executed on its own
+/// once its voting period ends.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    /// The group this proposal is for.
+    pub group_id: u64,
+
+    /// Proposal ID, counting up from 1 within the group.
+    pub id: u64,
+
+    /// Address of the member who made the proposal.
+    pub proposer: Address,
+
+    /// The change to apply if the proposal is approved.
+    pub action: ProposalAction,
+
+    /// Unix timestamp when the proposal was created.
+    pub created_at: u64,
+
+    /// Unix timestamp when voting ends.
+    pub voting_deadline: u64,
+
+    /// Number of votes in favor of the proposal.
+    pub votes_for: u32,
+
+    /// Number of votes against the proposal.
+    pub votes_against: u32,
+
+    /// Number of members who abstained.
+    pub votes_abstain: u32,
+
+    /// Members who have voted, so their votes can be cleared once the
+    /// proposal closes even if they have since left the group.
+    pub voters: Vec<Address>,
+
+    /// Whether the proposal has been executed.
+    pub executed: bool,
+
+    /// Whether the proposal was approved and its action applied.
+    pub approved: bool,
+}
+
+/// Records a member's vote on a proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalVote {
+    /// The group this vote is for.
+    pub group_id: u64,
+
+    /// The proposal voted on.
+    pub proposal_id: u64,
+
+    /// Address of the member who voted.
+    pub voter: Address,
+
+    /// How the member voted.
+    pub choice: VoteChoice,
+
+    /// Unix timestamp when the vote was cast or last changed.
     pub timestamp: u64,
 }
 
@@ -219,6 +493,11 @@ pub struct MemberPenaltyRecord {
 }
 
 /// Records a refund transaction.
+///
+/// Refunds settle each member's net position: what they paid in
+/// contributions and penalties minus what they received. The breakdown of
+/// the position is kept alongside the amount refunded. Collateral and unused
+/// prepayments are released separately and aren't included.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RefundRecord {
@@ -231,6 +510,16 @@ pub struct RefundRecord {
     /// Amount refunded in stroops.
     pub amount: i128,
 
+    /// Contributions the member had paid, including installments towards
+    /// the current cycle.
+    pub contributed: i128,
+
+    /// Late penalties the member had paid.
+    pub penalties_paid: i128,
+
+    /// Payouts and auction discount shares the member had received.
+    pub received: i128,
+
     /// Unix timestamp when the refund was processed.
     pub timestamp: u64,
 
@@ -238,6 +527,28 @@ pub struct RefundRecord {
     pub reason: RefundReason,
 }
 
+/// Running totals of what a member has paid into and received from a group.
+///
+/// Used to settle the group if it is refunded before it completes.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MemberPosition {
+    /// Contributions paid, including prepaid cycles once they are used and
+    /// installments or collateral that covered a default.
+    pub contributed: i128,
+    /// Late penalties paid.
+    pub penalties: i128,
+    /// Payouts and auction discount shares received.
+    pub received: i128,
+}
+
+impl MemberPosition {
+    /// Returns what the member has paid in minus what they have received.
+    pub fn net(&self) -> i128 {
+        self.contributed + self.penalties - self.received
+    }
+}
+
 /// Reason for a refund.
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -251,12 +562,32 @@ pub enum RefundReason {
     EmergencyRefund = 2,
 }
 
-/// Voting period duration in seconds (7 days).
+/// Share weight of a member paying exactly `contribution_amount` per slot (1x).
+/// Weights are percentages of `contribution_amount`.
+pub const FULL_SHARE_WEIGHT: u32 = 100;
+
+/// Smallest share weight a member can choose (0.1x).
+pub const MIN_SHARE_WEIGHT: u32 = 10;
+
+/// Largest share weight a member can choose (10x).
+pub const MAX_SHARE_WEIGHT: u32 = 1_000;
+
+/// Default voting period duration in seconds (7 days).
 pub const VOTING_PERIOD: u64 = 604_800;
 
-/// Minimum approval percentage required for refund (51%).
+/// Default minimum approval percentage required for refund (51%).
 pub const REFUND_APPROVAL_THRESHOLD: u32 = 51;
 
+/// Default percentage of members that must vote (50%).
+pub const DEFAULT_QUORUM: u32 = 50;
+
+/// Default approval percentage for removing a member or replacing the
+/// creator (two thirds).
+pub const SUPERMAJORITY_THRESHOLD: u32 = 67;
+
+/// Longest voting period a group can choose (30 days).
+pub const MAX_VOTING_PERIOD: u64 = 2_592_000;
+
 /// Detailed record of a member's contribution for a specific cycle.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -275,7 +606,156 @@ pub struct ContributionRecord {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PayoutRecord {
     pub group_id: u64,
+    pub cycle: u32,
     pub member: Address,
+    /// Amount transferred to the recipient, after any auction discount and
+    /// including the cycle's penalty bonus.
     pub amount: i128,
+    /// Auction discount given up by the recipient and redistributed to the
+    /// other members. Always `0` outside [`PayoutStrategy::BidBased`] groups.
+    pub discount: i128,
     pub timestamp: u64,
 }
+
+/// Record of a completed round of a multi-round group.
+///
+/// Cycle numbers keep counting up across rounds, so each round's
+/// contributions and payouts are found under cycles `first_cycle` to
+/// `last_cycle`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundRecord {
+    pub group_id: u64,
+    pub round: u32,
+    pub first_cycle: u32,
+    pub last_cycle: u32,
+    /// The payout order the round was paid out in.
+    pub payout_order: Vec<Address>,
+    pub completed_at: u64,
+}
+
+/// Record of a member defaulting on a cycle's contribution.
+///
+/// Whatever the member paid in installments counts towards the contribution.
+/// The rest is covered from the member's collateral first, then from the
+/// cycle's penalty pool. Whatever is left over is the `shortfall`, which
+/// reduces that cycle's payout.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultRecord {
+    pub group_id: u64,
+    pub cycle: u32,
+    pub member: Address,
+    pub from_installments: i128,
+    pub from_collateral: i128,
+    pub from_penalty_pool: i128,
+    pub shortfall: i128,
+    pub timestamp: u64,
+}
+
+/// Ledger of the tokens the contract holds for one group.
+///
+/// Every token paid into the contract for a group is recorded here, and
+/// payouts and refunds are only credited out of what the group itself has
+/// paid in, so one group can never be paid from another group's deposits.
+/// Credited amounts stay in escrow until members withdraw them with `claim`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupEscrow {
+    pub group_id: u64,
+    /// Contributions, installments, prepayments and collateral paid in.
+    pub deposits: i128,
+    /// Late penalties paid in.
+    pub penalties: i128,
+    /// Payouts, auction discount shares and keeper fees credited to members.
+    pub payouts: i128,
+    /// Cancellation and vote refunds, exit refunds, released collateral and
+    /// unused prepayments credited back to members.
+    pub refunds: i128,
+    /// Credited amounts members have withdrawn.
+    pub withdrawn: i128,
+}
+
+impl GroupEscrow {
+    /// Returns what has been paid in but not yet credited to anyone.
+    pub fn available(&self) -> i128 {
+        self.deposits + self.penalties - self.payouts - self.refunds
+    }
+
+    /// Returns the tokens the contract currently holds for the group.
+    pub fn balance(&self) -> i128 {
+        self.deposits + self.penalties - self.withdrawn
+    }
+}
+
+/// What a call to `poke` did to a group.
+///
+/// A report with nothing marked, paid out or finalised means the group had
+/// nothing due.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperReport {
+    pub group_id: u64,
+    /// Members marked as defaulted because their grace period ran out.
+    pub defaults_marked: u32,
+    /// Whether the cycle's payout was executed.
+    pub payout_executed: bool,
+    /// Whether a refund request whose voting period ended was settled,
+    /// either by refunding the group or by rejecting the request.
+    pub refund_finalized: bool,
+    /// Keeper fee credited to the caller's claimable balance.
+    pub fee: i128,
+}
+
+impl KeeperReport {
+    /// Returns `true` if `poke` advanced the group in any way.
+    pub fn did_work(&self) -> bool {
+        self.defaults_marked > 0 || self.payout_executed || self.refund_finalized
+    }
+}
+
+/// A member's bid in a [`PayoutStrategy::BidBased`] group auction.
+///
+/// The unpaid member offering the largest discount receives the cycle's pot
+/// minus their discount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bid {
+    pub group_id: u64,
+    pub cycle: u32,
+    pub bidder: Address,
+    /// Amount the bidder is willing to give up from the pot, in stroops.
+    pub discount: i128,
+    pub timestamp: u64,
+}
+
+/// A member's request to exit a group after its rotation has started.
+///
+/// The creator approves or rejects the request. Members who have already
+/// received a payout cannot exit this way.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExitRequest {
+    /// The group the member wants to leave.
+    pub group_id: u64,
+
+    /// Address of the member requesting to exit.
+    pub member: Address,
+
+    /// Timestamp when the request was made.
+    pub requested_at: u64,
+}
+
+/// A pending request to join an [`JoinPolicy::ApprovalRequired`] group.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JoinRequest {
+    /// The group the address wants to join.
+    pub group_id: u64,
+
+    /// Address requesting to join.
+    pub member: Address,
+
+    /// Timestamp when the request was made.
+    pub requested_at: u64,
+}
@@ -20,7 +20,8 @@ pub fn is_member(members: &Vec<Address>, address: &Address) -> bool {
 
 /// Returns `true` if every member of the group has contributed in the current cycle.
 ///
-/// Iterates over all members and short-circuits on the first missing contribution.
+/// Members marked as defaulted for the cycle count as settled. Iterates over
+/// all members and short-circuits on the first missing contribution.
 /// This is called by [`execute_payout`](crate::contract::AjoContract::execute_payout)
 /// to gate payout execution — a payout cannot proceed until this returns `true`.
 ///
@@ -37,23 +38,41 @@ pub fn all_members_contributed(env: &Env, group: &Group) -> bool {
     
     group.members.iter().all(|member| {
         crate::storage::has_contributed(env, group_id, cycle, &member)
+            || crate::storage::get_default_record(env, group_id, cycle, &member).is_some()
     })
 }
 
+/// Returns the part of the current cycle's pot that defaulted members left
+/// uncovered.
+pub fn cycle_shortfall(env: &Env, group: &Group) -> i128 {
+    group
+        .members
+        .iter()
+        .filter_map(|member| {
+            crate::storage::get_default_record(env, group.id, group.current_cycle, &member)
+        })
+        .map(|record| record.shortfall)
+        .sum()
+}
+
 /// Calculates the total payout amount for a single cycle.
 ///
-/// The payout equals each member's fixed contribution multiplied by the total
-/// number of members. This ensures the recipient receives the full pool of contributions.
+/// The payout equals the sum of every member's [`contribution_due`], so it
+/// grows in proportion to the members' slots and share weights. This ensures
+/// the recipient receives the full pool of contributions.
 ///
 /// # Arguments
+/// * `env` - The contract environment
 /// * `group` - The group whose payout is being calculated
 ///
 /// # Returns
-/// Total payout in stroops (`contribution_amount × member_count`)
-#[inline]
-pub fn calculate_payout_amount(group: &Group) -> i128 {
-    let member_count = group.members.len() as i128;
-    group.contribution_amount * member_count
+/// Total payout in stroops
+pub fn calculate_payout_amount(env: &Env, group: &Group) -> i128 {
+    group
+        .members
+        .iter()
+        .map(|member| contribution_due(env, group, &member))
+        .sum()
 }
 
 /// Returns the current ledger timestamp in seconds since Unix epoch.
@@ -162,3 +181,336 @@ pub fn is_within_grace_period(group: &crate::types::Group, current_time: u64) ->
     let grace_end = get_grace_period_end(group);
     current_time > cycle_end && current_time <= grace_end
 }
+
+/// Validates the governance settings used when creating a group.
+///
+/// * `quorum` and every threshold - must be between 0 and 100 inclusive
+/// * `voting_period` - must be <= [`MAX_VOTING_PERIOD`]
+///
+/// [`MAX_VOTING_PERIOD`]: crate::types::MAX_VOTING_PERIOD
+pub fn validate_governance_params(
+    governance: &crate::types::GovernanceSettings,
+) -> Result<(), crate::errors::AjoError> {
+    let percentages = [
+        governance.quorum,
+        governance.threshold,
+        governance.amendment_threshold,
+        governance.removal_threshold,
+        governance.creator_threshold,
+    ];
+    if percentages.iter().any(|percentage| *percentage > 100)
+        || governance.voting_period > crate::types::MAX_VOTING_PERIOD
+    {
+        return Err(crate::errors::AjoError::InvalidGroupState);
+    }
+    Ok(())
+}
+
+/// Returns the percentage of members that must vote, or
+/// [`DEFAULT_QUORUM`] if the group didn't set one.
+///
+/// [`DEFAULT_QUORUM`]: crate::types::DEFAULT_QUORUM
+pub fn quorum(group: &Group) -> u32 {
+    match group.settings.governance.quorum {
+        0 => crate::types::DEFAULT_QUORUM,
+        quorum => quorum,
+    }
+}
+
+/// Returns the approval percentage a vote needs, or
+/// [`REFUND_APPROVAL_THRESHOLD`] if the group didn't set one.
+///
+/// [`REFUND_APPROVAL_THRESHOLD`]: crate::types::REFUND_APPROVAL_THRESHOLD
+pub fn approval_threshold(group: &Group) -> u32 {
+    match group.settings.governance.threshold {
+        0 => crate::types::REFUND_APPROVAL_THRESHOLD,
+        threshold => threshold,
+    }
+}
+
+/// Returns how long a vote stays open, or [`VOTING_PERIOD`] if the group
+/// didn't set a period.
+///
+/// [`VOTING_PERIOD`]: crate::types::VOTING_PERIOD
+pub fn voting_period(group: &Group) -> u64 {
+    match group.settings.governance.voting_period {
+        0 => crate::types::VOTING_PERIOD,
+        period => period,
+    }
+}
+
+/// Returns the approval percentage a proposal for `action` needs.
+///
+/// Zero action thresholds fall back to [`approval_threshold`] for rule
+/// changes and to [`SUPERMAJORITY_THRESHOLD`] for removing a member or
+/// replacing the creator.
+///
+/// [`SUPERMAJORITY_THRESHOLD`]: crate::types::SUPERMAJORITY_THRESHOLD
+pub fn action_threshold(group: &Group, action: &crate::types::ProposalAction) -> u32 {
+    use crate::types::ProposalAction;
+
+    let governance = &group.settings.governance;
+    let (threshold, default) = match action {
+        ProposalAction::RemoveMember(_) => (
+            governance.removal_threshold,
+            crate::types::SUPERMAJORITY_THRESHOLD,
+        ),
+        ProposalAction::ReplaceCreator(_) => (
+            governance.creator_threshold,
+            crate::types::SUPERMAJORITY_THRESHOLD,
+        ),
+        _ => (governance.amendment_threshold, approval_threshold(group)),
+    };
+    match threshold {
+        0 => default,
+        threshold => threshold,
+    }
+}
+
+/// Returns `true` if enough members voted to meet the group's quorum and at
+/// least `threshold` percent of the for and against votes were in favour.
+pub fn is_vote_passed(
+    group: &Group,
+    votes_for: u32,
+    votes_against: u32,
+    votes_abstain: u32,
+    threshold: u32,
+) -> bool {
+    let votes_cast = (votes_for + votes_against + votes_abstain) as u64;
+    if votes_cast * 100 < quorum(group) as u64 * group.members.len() as u64 {
+        return false;
+    }
+
+    let total_votes = (votes_for + votes_against) as u64;
+    if total_votes == 0 {
+        return false;
+    }
+    votes_for as u64 * 100 >= threshold as u64 * total_votes
+}
+
+/// Returns `true` if a refund request passed under the group's quorum and
+/// approval threshold.
+pub fn is_refund_approved(group: &Group, request: &crate::types::RefundRequest) -> bool {
+    is_vote_passed(
+        group,
+        request.votes_for,
+        request.votes_against,
+        request.votes_abstain,
+        approval_threshold(group),
+    )
+}
+
+/// Returns `true` if a proposal passed under the group's quorum and the
+/// threshold for its action.
+pub fn is_proposal_approved(group: &Group, proposal: &crate::types::Proposal) -> bool {
+    is_vote_passed(
+        group,
+        proposal.votes_for,
+        proposal.votes_against,
+        proposal.votes_abstain,
+        action_threshold(group, &proposal.action),
+    )
+}
+
+/// Returns how many payout slots `member` holds in the group.
+pub fn slot_count(group: &Group, member: &Address) -> u32 {
+    group
+        .payout_order
+        .iter()
+        .filter(|holder| holder == member)
+        .count() as u32
+}
+
+/// Returns the number of slots a single member may hold.
+///
+/// `settings.max_slots_per_member` values below 1 are raised to 1.
+pub fn max_slots_per_member(group: &Group) -> u32 {
+    group.settings.max_slots_per_member.max(1)
+}
+
+/// Returns the number of rounds a group runs for.
+///
+/// `settings.rounds` values below 1 are raised to 1.
+pub fn total_rounds(group: &Group) -> u32 {
+    group.settings.rounds.max(1)
+}
+
+/// Returns `true` if `member` holds a slot that hasn't been paid out yet.
+pub fn has_unpaid_slot(group: &Group, member: &Address) -> bool {
+    group
+        .payout_order
+        .slice(group.payout_index..group.payout_order.len())
+        .contains(member)
+}
+
+/// Returns what `member` owes each cycle: `contribution_amount` scaled by
+/// their share weight, for every slot they hold.
+pub fn contribution_due(env: &Env, group: &Group, member: &Address) -> i128 {
+    let weight = crate::storage::get_share_weight(env, group.id, member);
+    group.contribution_amount * weight as i128 / crate::types::FULL_SHARE_WEIGHT as i128
+        * slot_count(group, member) as i128
+}
+
+/// Returns how much `member` has paid towards `cycle`'s contribution: their
+/// full [`contribution_due`] once the contribution is complete, otherwise the
+/// sum of their installments so far.
+pub fn amount_paid(env: &Env, group: &Group, cycle: u32, member: &Address) -> i128 {
+    if crate::storage::has_contributed(env, group.id, cycle, member) {
+        contribution_due(env, group, member)
+    } else {
+        crate::storage::get_partial_contribution(env, group.id, cycle, member)
+    }
+}
+
+/// Returns the penalty `member` owes for a late contribution to the given group.
+///
+/// Calculated as `contribution_due × penalty_rate / 100`, rounded down.
+pub fn calculate_penalty(env: &Env, group: &Group, member: &Address) -> i128 {
+    contribution_due(env, group, member) * group.penalty_rate as i128 / 100
+}
+
+/// Returns a member's reliability score as the percentage (0-100) of
+/// contributions that were made on time.
+///
+/// Members with no contribution history are considered fully reliable.
+pub fn calculate_reliability_score(on_time_count: u32, late_count: u32) -> u32 {
+    let total = on_time_count + late_count;
+    if total == 0 {
+        return 100;
+    }
+    on_time_count * 100 / total
+}
+
+/// Returns `true` if `order` holds exactly the same slots as `current`, i.e.
+/// every member appears as many times as the slots they hold.
+///
+/// Used to validate creator-defined payout orders.
+pub fn is_valid_payout_order(current: &Vec<Address>, order: &Vec<Address>) -> bool {
+    if order.len() != current.len() {
+        return false;
+    }
+    let count = |slots: &Vec<Address>, address: &Address| {
+        slots.iter().filter(|holder| holder == address).count()
+    };
+    order
+        .iter()
+        .all(|address| count(order, &address) == count(current, &address))
+}
+
+/// Returns the number of members a group needs before it can start.
+///
+/// `settings.min_members` values below the minimum group size of 2 are raised to 2.
+pub fn min_members(group: &Group) -> u32 {
+    group.settings.min_members.max(2)
+}
+
+/// Returns `true` once any contribution has been made to the group.
+///
+/// A group is considered started after its first payout, after advancing past
+/// the first cycle, or as soon as any member has contributed in the current cycle.
+pub fn has_contributions_started(env: &Env, group: &Group) -> bool {
+    if group.payout_index > 0 || group.current_cycle > 1 {
+        return true;
+    }
+    group
+        .members
+        .iter()
+        .any(|member| crate::storage::has_contributed(env, group.id, group.current_cycle, &member))
+}
+
+/// Shuffles the part of `payout_order` that hasn't been paid out yet.
+///
+/// Members before `payout_index` have already received their payout and keep
+/// their position. The shuffle is seeded by the ledger PRNG.
+pub fn shuffle_unpaid_payout_order(env: &Env, group: &mut Group) {
+    let len = group.payout_order.len();
+    let paid = group.payout_index.min(len);
+    let mut unpaid = group.payout_order.slice(paid..len);
+    env.prng().shuffle(&mut unpaid);
+
+    let mut order = group.payout_order.slice(0..paid);
+    order.append(&unpaid);
+    group.payout_order = order;
+}
+
+/// Finds the highest auction bid among members still waiting for a payout.
+///
+/// Only members at or after `payout_index` in `payout_order` who haven't
+/// defaulted on the current cycle are eligible. Ties go to the member
+/// earlier in the payout order.
+///
+/// # Returns
+/// The winner's position in `payout_order` and their bid, or `None` if no
+/// eligible member has bid in the current cycle
+pub fn find_winning_bid(env: &Env, group: &Group) -> Option<(u32, crate::types::Bid)> {
+    let mut winner: Option<(u32, crate::types::Bid)> = None;
+    for index in group.payout_index..group.payout_order.len() {
+        let member = group.payout_order.get(index).unwrap();
+        if crate::storage::get_default_record(env, group.id, group.current_cycle, &member).is_some()
+        {
+            continue;
+        }
+        if let Some(bid) = crate::storage::get_bid(env, group.id, group.current_cycle, &member) {
+            let beats_current = match &winner {
+                Some((_, best)) => bid.discount > best.discount,
+                None => true,
+            };
+            if beats_current {
+                winner = Some((index, bid));
+            }
+        }
+    }
+    winner
+}
+
+/// Moves the member at `index` in `payout_order` to the `payout_index` slot.
+///
+/// Members between the two positions shift back by one, keeping their
+/// relative order.
+pub fn promote_in_payout_order(group: &mut Group, index: u32) {
+    if index <= group.payout_index {
+        return;
+    }
+    if let Some(member) = group.payout_order.get(index) {
+        group.payout_order.remove(index);
+        group.payout_order.insert(group.payout_index, member);
+    }
+}
+
+/// Moves a member's next unpaid slot to the end of the payout order.
+///
+/// Slots already paid (before `payout_index`) keep their position.
+pub fn demote_in_payout_order(group: &mut Group, member: &Address) {
+    for index in group.payout_index..group.payout_order.len() {
+        if group.payout_order.get(index).as_ref() == Some(member) {
+            group.payout_order.remove(index);
+            group.payout_order.push_back(member.clone());
+            return;
+        }
+    }
+}
+
+/// Removes a member from the group's member list and every slot they hold
+/// from the payout order.
+///
+/// The member must not have been paid yet, so paid positions before
+/// `payout_index` are unaffected.
+pub fn remove_member(group: &mut Group, member: &Address) {
+    if let Some(index) = group.members.first_index_of(member) {
+        group.members.remove(index);
+    }
+    while let Some(index) = group.payout_order.first_index_of(member) {
+        group.payout_order.remove(index);
+    }
+}
+
+/// Returns the position of `member`'s request in a group's join requests.
+pub fn find_join_request(
+    requests: &Vec<crate::types::JoinRequest>,
+    member: &Address,
+) -> Option<u32> {
+    requests
+        .iter()
+        .position(|request| request.member == *member)
+        .map(|index| index as u32)
+}
@@ -0,0 +1,384 @@
+#![cfg(test)]
+
+//! Auction tests
+//!
+//! These tests verify bidding in `BidBased` groups, winner selection in
+//! `execute_payout`, and redistribution of the winning discount.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment, a bid-based group and funded members
+fn setup_test_env(
+    member_count: u32,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::BidBased,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_id, members, group_id)
+}
+
+/// Has every member contribute, then waits out the grace period.
+fn contribute_all(env: &Env, client: &AjoContractClient, members: &Vec<Address>, group_id: u64) {
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+}
+
+#[test]
+fn test_highest_bidder_wins_discounted_pot() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_id);
+    let (m1, m2, m3) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    client.place_bid(&m2, &group_id, &10_000_000i128);
+    client.place_bid(&m3, &group_id, &30_000_000i128);
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+
+    // m3 receives the pot minus their discount
+    let pot = CONTRIBUTION * 3;
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &m3),
+        pot - 30_000_000
+    );
+    client.claim(&m3, &group_id);
+    assert_eq!(
+        token_client.balance(&m3),
+        FUNDING - CONTRIBUTION + pot - 30_000_000
+    );
+
+    // The discount is split between the other two members
+    assert_eq!(client.get_claimable_balance(&group_id, &m1), 15_000_000);
+    assert_eq!(client.get_claimable_balance(&group_id, &m2), 15_000_000);
+
+    let record = client.get_payout_record(&group_id, &1u32);
+    assert_eq!(record.member, m3);
+    assert_eq!(record.cycle, 1);
+    assert_eq!(record.amount, pot - 30_000_000);
+    assert_eq!(record.discount, 30_000_000);
+
+    // The winner moves to the front of the payout order
+    let order = client.get_group(&group_id).payout_order;
+    assert_eq!(order.get(0).unwrap(), m3);
+    assert_eq!(order.get(1).unwrap(), m1);
+    assert_eq!(order.get(2).unwrap(), m2);
+}
+
+#[test]
+fn test_claim_transfers_discount_share() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_id);
+    let m1 = members.get(0).unwrap();
+
+    client.place_bid(&members.get(2).unwrap(), &group_id, &30_000_000i128);
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+
+    let before = token_client.balance(&m1);
+    assert_eq!(client.claim(&m1, &group_id), 15_000_000);
+    assert_eq!(token_client.balance(&m1), before + 15_000_000);
+    assert_eq!(client.get_claimable_balance(&group_id, &m1), 0);
+
+    assert_eq!(
+        client.try_claim(&m1, &group_id),
+        Err(Ok(AjoError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_discount_remainder_stays_with_winner() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_id);
+    let winner = members.get(1).unwrap();
+
+    // 11 doesn't split evenly between two members
+    client.place_bid(&winner, &group_id, &11i128);
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+
+    client.claim(&winner, &group_id);
+    assert_eq!(
+        token_client.balance(&winner),
+        FUNDING - CONTRIBUTION + CONTRIBUTION * 3 - 10
+    );
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &members.get(0).unwrap()),
+        5
+    );
+    assert_eq!(client.get_payout_record(&group_id, &1u32).discount, 10);
+}
+
+#[test]
+fn test_discount_split_by_slots() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let (creator, double, winner) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    for member in [&creator, &double, &winner] {
+        token_admin_client.mint(member, &FUNDING);
+    }
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &4u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::BidBased,
+        &GroupSettings {
+            max_slots_per_member: 2,
+            ..Default::default()
+        },
+    );
+    client.join_group(&double, &group_id);
+    client.join_group(&double, &group_id);
+    client.join_group(&winner, &group_id);
+
+    client.place_bid(&winner, &group_id, &30_000_000i128);
+    let mut members = Vec::new(&env);
+    for member in [&creator, &double, &winner] {
+        members.push_back(member.clone());
+    }
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+
+    // The member holding two slots gets twice the share
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &creator),
+        10_000_000
+    );
+    assert_eq!(client.get_claimable_balance(&group_id, &double), 20_000_000);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &winner),
+        CONTRIBUTION * 4 - 30_000_000
+    );
+}
+
+#[test]
+fn test_discount_capped_after_default() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+    let (creator, winner, defaulter) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    // The bid is valid against the full pot, but the default shrinks it
+    client.place_bid(&winner, &group_id, &(CONTRIBUTION * 5 / 2));
+    client.contribute(&creator, &group_id);
+    client.contribute(&winner, &group_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.mark_default(&group_id, &defaulter);
+    client.execute_payout(&group_id);
+
+    // The discount is capped at what was paid in, and the defaulter gets no share
+    let record = client.get_payout_record(&group_id, &1u32);
+    assert_eq!(record.member, winner);
+    assert_eq!(record.amount, 0);
+    assert_eq!(record.discount, CONTRIBUTION * 2);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &creator),
+        CONTRIBUTION * 2
+    );
+    assert_eq!(client.get_claimable_balance(&group_id, &winner), 0);
+    assert_eq!(client.get_claimable_balance(&group_id, &defaulter), 0);
+}
+
+#[test]
+fn test_tied_bids_go_to_earlier_payout_position() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+
+    client.place_bid(&members.get(2).unwrap(), &group_id, &5_000_000i128);
+    client.place_bid(&members.get(1).unwrap(), &group_id, &5_000_000i128);
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+
+    assert_eq!(
+        client.get_payout_record(&group_id, &1u32).member,
+        members.get(1).unwrap()
+    );
+}
+
+#[test]
+fn test_bid_can_be_updated() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+    let (m2, m3) = (members.get(1).unwrap(), members.get(2).unwrap());
+
+    client.place_bid(&m2, &group_id, &20_000_000i128);
+    client.place_bid(&m3, &group_id, &10_000_000i128);
+    client.place_bid(&m2, &group_id, &1_000_000i128);
+    assert_eq!(client.get_bid(&group_id, &1u32, &m2).discount, 1_000_000);
+
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+
+    assert_eq!(client.get_payout_record(&group_id, &1u32).member, m3);
+}
+
+#[test]
+fn test_bids_reset_each_cycle() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+    let (m1, m2, m3) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    client.place_bid(&m3, &group_id, &10_000_000i128);
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+
+    // No bids in cycle 2, so the next member in payout order is paid
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+    let record = client.get_payout_record(&group_id, &2u32);
+    assert_eq!(record.member, m1);
+    assert_eq!(record.discount, 0);
+
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+    assert_eq!(client.get_payout_record(&group_id, &3u32).member, m2);
+    assert!(client.is_complete(&group_id));
+}
+
+#[test]
+fn test_paid_member_cannot_bid() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+    let m1 = members.get(0).unwrap();
+
+    contribute_all(&env, &client, &members, group_id);
+    client.execute_payout(&group_id);
+
+    assert_eq!(
+        client.try_place_bid(&m1, &group_id, &1i128),
+        Err(Ok(AjoError::AlreadyReceivedPayout))
+    );
+}
+
+#[test]
+fn test_bid_rejected_outside_cycle_window() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION;
+    });
+
+    assert_eq!(
+        client.try_place_bid(&members.get(1).unwrap(), &group_id, &1i128),
+        Err(Ok(AjoError::OutsideCycleWindow))
+    );
+}
+
+#[test]
+fn test_invalid_bids_rejected() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+    let m2 = members.get(1).unwrap();
+
+    assert_eq!(
+        client.try_place_bid(&m2, &group_id, &-1i128),
+        Err(Ok(AjoError::InvalidBidAmount))
+    );
+    assert_eq!(
+        client.try_place_bid(&m2, &group_id, &(CONTRIBUTION * 3)),
+        Err(Ok(AjoError::InvalidBidAmount))
+    );
+    assert_eq!(
+        client.try_place_bid(&Address::generate(&env), &group_id, &1i128),
+        Err(Ok(AjoError::NotMember))
+    );
+    assert_eq!(
+        client.try_get_bid(&group_id, &1u32, &m2),
+        Err(Ok(AjoError::NoBid))
+    );
+}
+
+#[test]
+fn test_bid_requires_bid_based_group() {
+    let (env, client, token_id, members, _) = setup_test_env(2);
+
+    let fixed = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &2u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&members.get(1).unwrap(), &fixed);
+
+    assert_eq!(
+        client.try_place_bid(&members.get(1).unwrap(), &fixed, &1i128),
+        Err(Ok(AjoError::InvalidPayoutStrategy))
+    );
+
+    // Fixed groups still get payout records, without a discount
+    contribute_all(&env, &client, &members, fixed);
+    client.execute_payout(&fixed);
+    let record = client.get_payout_record(&fixed, &1u32);
+    assert_eq!(record.amount, CONTRIBUTION * 2);
+    assert_eq!(record.discount, 0);
+}
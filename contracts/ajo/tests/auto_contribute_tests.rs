@@ -0,0 +1,198 @@
+#![cfg(test)]
+
+//! Auto-contribution tests
+//!
+//! These tests verify that members can pre-authorize contributions with
+//! `enable_auto_contribute` and a token allowance, and that
+//! `collect_contribution` pulls them within the cycle window.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 10;
+
+/// Helper function to create a test environment, a started group and funded members
+fn setup_test_env(
+    member_count: u32,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &(member_count + 1),
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+    client.start_group(&members.get(0).unwrap(), &group_id);
+
+    (env, client, token_id, members, group_id)
+}
+
+/// Lets the contract spend `amount` of `member`'s tokens.
+fn approve(
+    env: &Env,
+    client: &AjoContractClient,
+    token_id: &Address,
+    member: &Address,
+    amount: i128,
+) {
+    let expiration_ledger = env.ledger().sequence() + 100_000;
+    token::Client::new(env, token_id).approve(member, &client.address, &amount, &expiration_ledger);
+}
+
+#[test]
+fn test_collect_contribution_uses_allowance() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let token_client = token::Client::new(&env, &token_id);
+    let member = members.get(1).unwrap();
+
+    client.enable_auto_contribute(&member, &group_id, &3u32);
+    approve(&env, &client, &token_id, &member, CONTRIBUTION * 3);
+    assert_eq!(client.get_auto_contribute(&group_id, &member), 3);
+
+    client.collect_contribution(&group_id, &member);
+
+    assert_eq!(token_client.balance(&member), FUNDING - CONTRIBUTION);
+    assert_eq!(
+        token_client.allowance(&member, &client.address),
+        CONTRIBUTION * 2
+    );
+    assert_eq!(client.get_auto_contribute(&group_id, &member), 2);
+
+    let detail = client.get_contribution_detail(&group_id, &1u32, &member);
+    assert!(!detail.is_late);
+    assert_eq!(detail.amount, CONTRIBUTION);
+
+    assert_eq!(
+        client.try_collect_contribution(&group_id, &member),
+        Err(Ok(AjoError::AlreadyContributed))
+    );
+}
+
+#[test]
+fn test_collect_requires_allowance() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let member = members.get(1).unwrap();
+
+    client.enable_auto_contribute(&member, &group_id, &3u32);
+    approve(&env, &client, &token_id, &member, CONTRIBUTION - 1);
+
+    assert_eq!(
+        client.try_collect_contribution(&group_id, &member),
+        Err(Ok(AjoError::InsufficientAllowance))
+    );
+    assert_eq!(client.get_auto_contribute(&group_id, &member), 3);
+}
+
+#[test]
+fn test_collect_requires_opt_in() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let member = members.get(1).unwrap();
+
+    approve(&env, &client, &token_id, &member, CONTRIBUTION);
+
+    assert_eq!(
+        client.try_collect_contribution(&group_id, &member),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_collect_only_within_cycle_window() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let member = members.get(1).unwrap();
+
+    client.enable_auto_contribute(&member, &group_id, &1u32);
+    approve(&env, &client, &token_id, &member, CONTRIBUTION * 2);
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION;
+    });
+
+    assert_eq!(
+        client.try_collect_contribution(&group_id, &member),
+        Err(Ok(AjoError::OutsideCycleWindow))
+    );
+
+    // The member can still pay late by hand
+    client.contribute(&member, &group_id);
+}
+
+#[test]
+fn test_mandate_runs_out() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let (creator, member) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.enable_auto_contribute(&member, &group_id, &1u32);
+    approve(&env, &client, &token_id, &member, CONTRIBUTION * 5);
+
+    client.contribute(&creator, &group_id);
+    client.collect_contribution(&group_id, &member);
+    assert_eq!(client.get_auto_contribute(&group_id, &member), 0);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+
+    assert_eq!(
+        client.try_collect_contribution(&group_id, &member),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_zero_cycles_turns_auto_contribute_off() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let member = members.get(1).unwrap();
+
+    client.enable_auto_contribute(&member, &group_id, &3u32);
+    approve(&env, &client, &token_id, &member, CONTRIBUTION * 3);
+    client.enable_auto_contribute(&member, &group_id, &0u32);
+
+    assert_eq!(client.get_auto_contribute(&group_id, &member), 0);
+    assert_eq!(
+        client.try_collect_contribution(&group_id, &member),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_enable_requires_membership() {
+    let (env, client, _token_id, _members, group_id) = setup_test_env(2);
+
+    assert_eq!(
+        client.try_enable_auto_contribute(&Address::generate(&env), &group_id, &1u32),
+        Err(Ok(AjoError::NotMember))
+    );
+}
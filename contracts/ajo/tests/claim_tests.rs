@@ -0,0 +1,175 @@
+#![cfg(test)]
+
+//! Claim tests
+//!
+//! These tests verify that payouts and refunds are credited to claimable
+//! balances and withdrawn with `claim` and `claim_all`.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment, contract and two funded members
+fn setup_test_env() -> (Env, AjoContractClient<'static>, Vec<Address>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..2 {
+        members.push_back(Address::generate(&env));
+    }
+
+    (env, client, members)
+}
+
+/// Registers a token and funds every member with it.
+fn create_token(env: &Env, members: &Vec<Address>) -> Address {
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(env, &token_id);
+    for member in members.iter() {
+        token_admin_client.mint(&member, &FUNDING);
+    }
+    token_id
+}
+
+/// Creates a full two-member group paying in `token_id`.
+fn create_group(client: &AjoContractClient, token_id: &Address, members: &Vec<Address>) -> u64 {
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &2u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&members.get(1).unwrap(), &group_id);
+    group_id
+}
+
+/// Has both members contribute to every group, then pays each group out.
+fn run_cycle(env: &Env, client: &AjoContractClient, members: &Vec<Address>, groups: &[u64]) {
+    for group_id in groups {
+        for member in members.iter() {
+            client.contribute(&member, group_id);
+        }
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    for group_id in groups {
+        client.execute_payout(group_id);
+    }
+}
+
+#[test]
+fn test_payout_credited_to_claimable_balance() {
+    let (env, client, members) = setup_test_env();
+    let token_id = create_token(&env, &members);
+    let token_client = token::Client::new(&env, &token_id);
+    let group_id = create_group(&client, &token_id, &members);
+    let recipient = members.get(0).unwrap();
+
+    run_cycle(&env, &client, &members, &[group_id]);
+
+    // Nothing is pushed to the recipient
+    assert_eq!(token_client.balance(&recipient), FUNDING - CONTRIBUTION);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &recipient),
+        CONTRIBUTION * 2
+    );
+
+    assert_eq!(client.claim(&recipient, &group_id), CONTRIBUTION * 2);
+    assert_eq!(token_client.balance(&recipient), FUNDING + CONTRIBUTION);
+}
+
+#[test]
+fn test_cancel_credits_refunds() {
+    let (env, client, members) = setup_test_env();
+    let token_id = create_token(&env, &members);
+    let token_client = token::Client::new(&env, &token_id);
+    let group_id = create_group(&client, &token_id, &members);
+    let (creator, member) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.contribute(&member, &group_id);
+    client.cancel_group(&creator, &group_id);
+
+    assert_eq!(token_client.balance(&member), FUNDING - CONTRIBUTION);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &member),
+        CONTRIBUTION
+    );
+    assert_eq!(client.get_claimable_balance(&group_id, &creator), 0);
+
+    client.claim(&member, &group_id);
+    assert_eq!(token_client.balance(&member), FUNDING);
+}
+
+#[test]
+fn test_claim_all_totals_per_token() {
+    let (env, client, members) = setup_test_env();
+    let first_token = create_token(&env, &members);
+    let second_token = create_token(&env, &members);
+    let recipient = members.get(0).unwrap();
+
+    let groups = [
+        create_group(&client, &first_token, &members),
+        create_group(&client, &first_token, &members),
+        create_group(&client, &second_token, &members),
+    ];
+    run_cycle(&env, &client, &members, &groups);
+
+    let claimed = client.claim_all(&recipient);
+    assert_eq!(claimed.len(), 2);
+    assert_eq!(claimed.get(first_token.clone()).unwrap(), CONTRIBUTION * 4);
+    assert_eq!(claimed.get(second_token.clone()).unwrap(), CONTRIBUTION * 2);
+
+    for group_id in groups.iter() {
+        assert_eq!(client.get_claimable_balance(group_id, &recipient), 0);
+    }
+    assert_eq!(
+        token::Client::new(&env, &first_token).balance(&recipient),
+        FUNDING - CONTRIBUTION * 2 + CONTRIBUTION * 4
+    );
+    assert_eq!(
+        token::Client::new(&env, &second_token).balance(&recipient),
+        FUNDING - CONTRIBUTION + CONTRIBUTION * 2
+    );
+
+    assert_eq!(
+        client.try_claim_all(&recipient),
+        Err(Ok(AjoError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_claim_all_skips_already_claimed_groups() {
+    let (env, client, members) = setup_test_env();
+    let token_id = create_token(&env, &members);
+    let recipient = members.get(0).unwrap();
+
+    let groups = [
+        create_group(&client, &token_id, &members),
+        create_group(&client, &token_id, &members),
+    ];
+    run_cycle(&env, &client, &members, &groups);
+
+    client.claim(&recipient, &groups[0]);
+
+    let claimed = client.claim_all(&recipient);
+    assert_eq!(claimed.get(token_id).unwrap(), CONTRIBUTION * 2);
+}
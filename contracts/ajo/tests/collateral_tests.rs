@@ -0,0 +1,434 @@
+#![cfg(test)]
+
+//! Collateral tests
+//!
+//! These tests verify that members lock a group's `collateral_amount`, that it
+//! covers missed contributions once the grace period expires, and that it is
+//! released when the group completes or members leave.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, DataKey, GroupSettings, JoinPolicy, JoinRequest,
+    PayoutStrategy,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const COLLATERAL: i128 = CONTRIBUTION * 2;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment, contract, token and funded members
+fn setup_test_env(
+    member_count: u32,
+) -> (
+    Env,
+    AjoContractClient<'static>,
+    token::StellarAssetClient<'static>,
+    Vec<Address>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    (env, client, token_admin_client, members)
+}
+
+/// Creates a collateralized group with the given policy and has every other member join.
+fn create_group(
+    client: &AjoContractClient,
+    token_id: &Address,
+    members: &Vec<Address>,
+    max_members: u32,
+    join_policy: JoinPolicy,
+) -> u64 {
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &max_members,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            join_policy,
+            collateral_amount: COLLATERAL,
+            ..Default::default()
+        },
+    );
+    if join_policy == JoinPolicy::Open {
+        for member in members.iter().skip(1) {
+            client.join_group(&member, &group_id);
+        }
+    }
+    group_id
+}
+
+/// Has the given members contribute, waits out the grace period, marks everyone
+/// else as defaulted and pays out.
+fn run_cycle(env: &Env, client: &AjoContractClient, contributors: &Vec<Address>, group_id: u64) {
+    for member in contributors.iter() {
+        client.contribute(&member, &group_id);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    for member in client.get_group(&group_id).members.iter() {
+        if !contributors.contains(&member) {
+            client.mark_default(&group_id, &member);
+        }
+    }
+    client.execute_payout(&group_id);
+}
+
+#[test]
+fn test_members_lock_collateral_on_join() {
+    let (env, client, token_admin_client, members) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_admin_client.address);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        3,
+        JoinPolicy::Open,
+    );
+
+    for member in members.iter() {
+        assert_eq!(client.get_member_collateral(&group_id, &member), COLLATERAL);
+        assert_eq!(token_client.balance(&member), FUNDING - COLLATERAL);
+    }
+    assert_eq!(
+        client.get_contract_balance(&token_admin_client.address),
+        COLLATERAL * 3
+    );
+}
+
+#[test]
+fn test_join_requires_collateral_balance() {
+    let (env, client, token_admin_client, members) = setup_test_env(1);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        3,
+        JoinPolicy::Open,
+    );
+
+    let poor = Address::generate(&env);
+    token_admin_client.mint(&poor, &(COLLATERAL - 1));
+    assert_eq!(
+        client.try_join_group(&poor, &group_id),
+        Err(Ok(AjoError::InsufficientBalance))
+    );
+    assert!(!client.is_member(&group_id, &poor));
+}
+
+#[test]
+fn test_negative_collateral_rejected() {
+    let (_env, client, token_admin_client, members) = setup_test_env(1);
+
+    let result = client.try_create_group(
+        &members.get(0).unwrap(),
+        &token_admin_client.address,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &3u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            collateral_amount: -1,
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(AjoError::ContributionAmountNegative)));
+}
+
+#[test]
+fn test_missed_contribution_slashed_after_grace_period() {
+    let (env, client, token_admin_client, members) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_admin_client.address);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        3,
+        JoinPolicy::Open,
+    );
+    let (creator, defaulter) = (members.get(0).unwrap(), members.get(2).unwrap());
+
+    // The defaulter never pays and can't be marked until the grace period is over
+    client.contribute(&creator, &group_id);
+    client.contribute(&members.get(1).unwrap(), &group_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD;
+    });
+    assert_eq!(
+        client.try_mark_default(&group_id, &defaulter),
+        Err(Ok(AjoError::CycleNotExpired))
+    );
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += 1;
+    });
+    let record = client.mark_default(&group_id, &defaulter);
+    assert_eq!(record.from_collateral, CONTRIBUTION);
+    assert_eq!(record.shortfall, 0);
+
+    client.execute_payout(&group_id);
+
+    // The creator still receives the full pot
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &creator),
+        CONTRIBUTION * 3
+    );
+    assert_eq!(
+        client.get_member_collateral(&group_id, &defaulter),
+        COLLATERAL - CONTRIBUTION
+    );
+    assert_eq!(token_client.balance(&defaulter), FUNDING - COLLATERAL);
+}
+
+#[test]
+fn test_exhausted_collateral_leaves_shortfall() {
+    let (env, client, token_admin_client, members) = setup_test_env(4);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        4,
+        JoinPolicy::Open,
+    );
+    let defaulter = members.get(3).unwrap();
+    let mut payers = members.clone();
+    payers.pop_back();
+
+    // Collateral covers two missed cycles, but not a third
+    run_cycle(&env, &client, &payers, group_id);
+    run_cycle(&env, &client, &payers, group_id);
+    assert_eq!(client.get_member_collateral(&group_id, &defaulter), 0);
+
+    for member in payers.iter() {
+        client.contribute(&member, &group_id);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    let record = client.mark_default(&group_id, &defaulter);
+    assert_eq!(record.from_collateral, 0);
+    assert_eq!(record.shortfall, CONTRIBUTION);
+}
+
+#[test]
+fn test_collateral_released_on_completion() {
+    let (env, client, token_admin_client, members) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_admin_client.address);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        3,
+        JoinPolicy::Open,
+    );
+    let defaulter = members.get(2).unwrap();
+    let mut payers = members.clone();
+    payers.pop_back();
+
+    run_cycle(&env, &client, &payers, group_id);
+    assert_eq!(
+        client.get_member_collateral(&group_id, &members.get(0).unwrap()),
+        COLLATERAL
+    );
+    run_cycle(&env, &client, &members, group_id);
+    run_cycle(&env, &client, &members, group_id);
+    assert!(client.is_complete(&group_id));
+
+    // Full collateral comes back, alongside the payout, to members who paid
+    // every cycle...
+    let payer = members.get(1).unwrap();
+    assert_eq!(client.get_member_collateral(&group_id, &payer), 0);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &payer),
+        CONTRIBUTION * 3 + COLLATERAL
+    );
+    client.claim(&payer, &group_id);
+    assert_eq!(token_client.balance(&payer), FUNDING);
+
+    // ...and what's left of it to the member whose missed cycle it covered
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &defaulter),
+        CONTRIBUTION * 3 + COLLATERAL - CONTRIBUTION
+    );
+}
+
+#[test]
+fn test_leave_group_releases_collateral() {
+    let (_env, client, token_admin_client, members) = setup_test_env(2);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        3,
+        JoinPolicy::Open,
+    );
+    let leaver = members.get(1).unwrap();
+
+    client.leave_group(&leaver, &group_id);
+
+    assert_eq!(client.get_member_collateral(&group_id, &leaver), 0);
+    assert_eq!(client.get_claimable_balance(&group_id, &leaver), COLLATERAL);
+}
+
+#[test]
+fn test_join_request_locks_collateral_until_decided() {
+    let (env, client, token_admin_client, members) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_admin_client.address);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        3,
+        JoinPolicy::ApprovalRequired,
+    );
+    let creator = members.get(0).unwrap();
+    let (accepted, rejected) = (members.get(1).unwrap(), members.get(2).unwrap());
+
+    client.request_join(&accepted, &group_id);
+    client.request_join(&rejected, &group_id);
+    // Requesting again doesn't lock more
+    client.request_join(&accepted, &group_id);
+    assert_eq!(token_client.balance(&accepted), FUNDING - COLLATERAL);
+
+    client.approve_join(&creator, &group_id, &accepted);
+    assert_eq!(
+        client.get_member_collateral(&group_id, &accepted),
+        COLLATERAL
+    );
+
+    client.reject_join(&creator, &group_id, &rejected);
+    assert_eq!(client.get_member_collateral(&group_id, &rejected), 0);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &rejected),
+        COLLATERAL
+    );
+}
+
+#[test]
+fn test_joining_by_invite_closes_join_request() {
+    let (env, client, token_admin_client, members) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_admin_client.address);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        3,
+        JoinPolicy::ApprovalRequired,
+    );
+    let (creator, applicant) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.request_join(&applicant, &group_id);
+    client.invite_member(&creator, &group_id, &applicant);
+    client.join_group(&applicant, &group_id);
+
+    // The request's collateral now backs the slot
+    assert!(client.get_join_requests(&group_id).is_empty());
+    assert_eq!(
+        client.get_member_collateral(&group_id, &applicant),
+        COLLATERAL
+    );
+    assert_eq!(token_client.balance(&applicant), FUNDING - COLLATERAL);
+    assert_eq!(client.get_member_slots(&group_id, &applicant), 1);
+
+    assert_eq!(
+        client.try_approve_join(&creator, &group_id, &applicant),
+        Err(Ok(AjoError::RecordNotFound))
+    );
+    assert_eq!(
+        client.try_reject_join(&creator, &group_id, &applicant),
+        Err(Ok(AjoError::RecordNotFound))
+    );
+}
+
+#[test]
+fn test_approving_request_of_member_adds_no_slot() {
+    let (env, client, token_admin_client, members) = setup_test_env(2);
+    let token_id = token_admin_client.address.clone();
+    let (creator, applicant) = (members.get(0).unwrap(), members.get(1).unwrap());
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &4u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            join_policy: JoinPolicy::ApprovalRequired,
+            collateral_amount: COLLATERAL,
+            max_slots_per_member: 2,
+            ..Default::default()
+        },
+    );
+    client.invite_member(&creator, &group_id, &applicant);
+    client.join_group(&applicant, &group_id);
+
+    // A request left over from before the applicant joined
+    env.as_contract(&client.address, || {
+        let request = JoinRequest {
+            group_id,
+            member: applicant.clone(),
+            requested_at: 0,
+        };
+        env.storage().persistent().set(
+            &DataKey::JoinRequests(group_id),
+            &Vec::from_array(&env, [request]),
+        );
+    });
+
+    client.approve_join(&creator, &group_id, &applicant);
+    assert!(client.get_join_requests(&group_id).is_empty());
+    assert_eq!(client.get_member_slots(&group_id, &applicant), 1);
+    assert_eq!(
+        client.get_member_collateral(&group_id, &applicant),
+        COLLATERAL
+    );
+}
+
+#[test]
+fn test_cancel_releases_collateral() {
+    let (_env, client, token_admin_client, members) = setup_test_env(2);
+    let group_id = create_group(
+        &client,
+        &token_admin_client.address,
+        &members,
+        3,
+        JoinPolicy::Open,
+    );
+
+    client.cancel_group(&members.get(0).unwrap(), &group_id);
+
+    for member in members.iter() {
+        assert_eq!(client.get_member_collateral(&group_id, &member), 0);
+        assert_eq!(client.get_claimable_balance(&group_id, &member), COLLATERAL);
+    }
+}
@@ -0,0 +1,245 @@
+#![cfg(test)]
+
+//! Default handling tests
+//!
+//! These tests verify `mark_default`: how a missed contribution is covered,
+//! how the payout is reduced by any shortfall, and how defaulters are
+//! moved down the payout order.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment, a group with the given strategy and funded members
+fn setup_test_env(
+    member_count: u32,
+    strategy: PayoutStrategy,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &10u32,
+        &strategy,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_id, members, group_id)
+}
+
+fn expire_grace_period(env: &Env) {
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+}
+
+#[test]
+fn test_shortfall_reduces_payout() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3, PayoutStrategy::Fixed);
+    let token_client = token::Client::new(&env, &token_id);
+    let (creator, defaulter) = (members.get(0).unwrap(), members.get(2).unwrap());
+
+    client.contribute(&creator, &group_id);
+    client.contribute(&members.get(1).unwrap(), &group_id);
+    expire_grace_period(&env);
+
+    assert_eq!(
+        client.try_execute_payout(&group_id),
+        Err(Ok(AjoError::IncompleteContributions))
+    );
+
+    let record = client.mark_default(&group_id, &defaulter);
+    assert_eq!(record.cycle, 1);
+    assert_eq!(record.member, defaulter);
+    assert_eq!(record.from_collateral, 0);
+    assert_eq!(record.from_penalty_pool, 0);
+    assert_eq!(record.shortfall, CONTRIBUTION);
+    assert_eq!(
+        client.get_default_record(&group_id, &1u32, &defaulter),
+        record
+    );
+
+    // The rotation continues with a smaller pot
+    let before = token_client.balance(&creator);
+    client.execute_payout(&group_id);
+    client.claim(&creator, &group_id);
+    assert_eq!(token_client.balance(&creator), before + CONTRIBUTION * 2);
+    assert_eq!(
+        client.get_payout_record(&group_id, &1u32).amount,
+        CONTRIBUTION * 2
+    );
+    assert_eq!(client.get_group(&group_id).current_cycle, 2);
+}
+
+#[test]
+fn test_penalty_pool_covers_shortfall() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3, PayoutStrategy::Fixed);
+    let token_client = token::Client::new(&env, &token_id);
+    let (creator, late, defaulter) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+    let penalty = CONTRIBUTION / 10;
+
+    client.contribute(&creator, &group_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + 3600;
+    });
+    client.contribute(&late, &group_id);
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), penalty);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += GRACE_PERIOD;
+    });
+    let record = client.mark_default(&group_id, &defaulter);
+    assert_eq!(record.from_penalty_pool, penalty);
+    assert_eq!(record.shortfall, CONTRIBUTION - penalty);
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), 0);
+
+    // The penalty went towards the pot instead of a bonus
+    let before = token_client.balance(&creator);
+    client.execute_payout(&group_id);
+    client.claim(&creator, &group_id);
+    assert_eq!(
+        token_client.balance(&creator),
+        before + CONTRIBUTION * 2 + penalty
+    );
+}
+
+#[test]
+fn test_defaulter_moves_to_end_of_payout_order() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3, PayoutStrategy::Fixed);
+    let (creator, second, third) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    // The creator is due first but doesn't pay
+    client.contribute(&second, &group_id);
+    client.contribute(&third, &group_id);
+    expire_grace_period(&env);
+    client.mark_default(&group_id, &creator);
+
+    let order = client.get_group(&group_id).payout_order;
+    assert_eq!(order.get(0).unwrap(), second);
+    assert_eq!(order.get(1).unwrap(), third);
+    assert_eq!(order.get(2).unwrap(), creator);
+
+    client.execute_payout(&group_id);
+    assert_eq!(client.get_payout_record(&group_id, &1u32).member, second);
+}
+
+#[test]
+fn test_defaulter_cannot_win_auction() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3, PayoutStrategy::BidBased);
+    let (creator, bidder, defaulter) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    client.place_bid(&defaulter, &group_id, &20_000_000i128);
+    client.place_bid(&bidder, &group_id, &10_000_000i128);
+    client.contribute(&creator, &group_id);
+    client.contribute(&bidder, &group_id);
+    expire_grace_period(&env);
+    client.mark_default(&group_id, &defaulter);
+
+    client.execute_payout(&group_id);
+    let record = client.get_payout_record(&group_id, &1u32);
+    assert_eq!(record.member, bidder);
+    assert_eq!(record.discount, 10_000_000);
+}
+
+#[test]
+fn test_mark_default_validation() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3, PayoutStrategy::Fixed);
+    let (payer, defaulter) = (members.get(0).unwrap(), members.get(2).unwrap());
+
+    client.contribute(&payer, &group_id);
+    expire_grace_period(&env);
+
+    assert_eq!(
+        client.try_mark_default(&group_id, &payer),
+        Err(Ok(AjoError::AlreadyContributed))
+    );
+    assert_eq!(
+        client.try_mark_default(&group_id, &Address::generate(&env)),
+        Err(Ok(AjoError::NotMember))
+    );
+    assert_eq!(
+        client.try_get_default_record(&group_id, &1u32, &defaulter),
+        Err(Ok(AjoError::RecordNotFound))
+    );
+
+    client.mark_default(&group_id, &defaulter);
+    assert_eq!(
+        client.try_mark_default(&group_id, &defaulter),
+        Err(Ok(AjoError::AlreadyContributed))
+    );
+}
+
+#[test]
+fn test_mark_default_requires_started_group() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let creator = Address::generate(&env);
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &3u32,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    expire_grace_period(&env);
+
+    assert_eq!(
+        client.try_mark_default(&group_id, &creator),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+}
@@ -0,0 +1,244 @@
+#![cfg(test)]
+
+//! Escrow tests
+//!
+//! These tests verify the per-group escrow ledger: recording deposits and
+//! penalties, crediting payouts and refunds only out of the group's own
+//! escrow, and recording withdrawals.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, DataKey, GroupEscrow, GroupSettings, PayoutStrategy,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment and a token with
+/// `member_count` funded members
+fn setup_test_env(
+    member_count: u32,
+) -> (
+    Env,
+    AjoContractClient<'static>,
+    Address,
+    Address,
+    Vec<Address>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    (env, client, contract_id, token_id, members)
+}
+
+/// Creates a full group of `members` with the given collateral.
+fn create_group(
+    client: &AjoContractClient,
+    token_id: &Address,
+    members: &Vec<Address>,
+    collateral_amount: i128,
+) -> u64 {
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &members.len(),
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            collateral_amount,
+            ..Default::default()
+        },
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+    group_id
+}
+
+/// Moves the ledger past the current cycle's grace period.
+fn end_cycle(env: &Env) {
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+}
+
+#[test]
+fn test_new_group_starts_with_empty_escrow() {
+    let (_env, client, _contract_id, token_id, members) = setup_test_env(1);
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &3u32,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+
+    let escrow = client.get_group_escrow(&group_id);
+    assert_eq!(
+        escrow,
+        GroupEscrow {
+            group_id,
+            deposits: 0,
+            penalties: 0,
+            payouts: 0,
+            refunds: 0,
+            withdrawn: 0,
+        }
+    );
+    assert_eq!(escrow.balance(), 0);
+
+    let result = client.try_get_group_escrow(&99);
+    assert_eq!(result, Err(Ok(AjoError::GroupNotFound)));
+}
+
+#[test]
+fn test_contributions_collateral_and_penalties_are_deposited() {
+    let (env, client, _contract_id, token_id, members) = setup_test_env(2);
+    let collateral = CONTRIBUTION / 2;
+    let group_id = create_group(&client, &token_id, &members, collateral);
+
+    client.contribute(&members.get(0).unwrap(), &group_id);
+
+    // A late contribution pays a 10% penalty on top
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + 1;
+    });
+    client.contribute(&members.get(1).unwrap(), &group_id);
+
+    let escrow = client.get_group_escrow(&group_id);
+    assert_eq!(escrow.deposits, collateral * 2 + CONTRIBUTION * 2);
+    assert_eq!(escrow.penalties, CONTRIBUTION / 10);
+    assert_eq!(escrow.available(), escrow.balance());
+}
+
+#[test]
+fn test_payouts_and_withdrawals_are_recorded() {
+    let (env, client, _contract_id, token_id, members) = setup_test_env(2);
+    let group_id = create_group(&client, &token_id, &members, 0);
+    let recipient = members.get(0).unwrap();
+
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    end_cycle(&env);
+    client.execute_payout(&group_id);
+
+    let escrow = client.get_group_escrow(&group_id);
+    assert_eq!(escrow.payouts, CONTRIBUTION * 2);
+    assert_eq!(escrow.available(), 0);
+    assert_eq!(escrow.balance(), CONTRIBUTION * 2);
+
+    client.claim(&recipient, &group_id);
+
+    let escrow = client.get_group_escrow(&group_id);
+    assert_eq!(escrow.withdrawn, CONTRIBUTION * 2);
+    assert_eq!(escrow.balance(), 0);
+}
+
+#[test]
+fn test_cancellation_refunds_are_recorded() {
+    let (_env, client, _contract_id, token_id, members) = setup_test_env(2);
+    let collateral = CONTRIBUTION / 2;
+    let group_id = create_group(&client, &token_id, &members, collateral);
+
+    client.contribute(&members.get(0).unwrap(), &group_id);
+    client.cancel_group(&members.get(0).unwrap(), &group_id);
+
+    let escrow = client.get_group_escrow(&group_id);
+    assert_eq!(escrow.refunds, CONTRIBUTION + collateral * 2);
+    assert_eq!(escrow.payouts, 0);
+    assert_eq!(escrow.available(), 0);
+
+    for member in members.iter() {
+        client.claim(&member, &group_id);
+    }
+    assert_eq!(client.get_group_escrow(&group_id).balance(), 0);
+}
+
+#[test]
+fn test_payout_cannot_use_another_groups_deposits() {
+    let (env, client, contract_id, token_id, members) = setup_test_env(4);
+    let first = members.slice(0..2);
+    let second = members.slice(2..4);
+    let group_a = create_group(&client, &token_id, &first, 0);
+    let group_b = create_group(&client, &token_id, &second, 0);
+
+    for member in members.iter() {
+        let group_id = if first.contains(&member) {
+            group_a
+        } else {
+            group_b
+        };
+        client.contribute(&member, &group_id);
+    }
+
+    // Simulate group A's escrow falling short, e.g. through a bug; the
+    // contract still holds group B's deposits
+    env.as_contract(&contract_id, || {
+        let key = DataKey::Escrow(group_a);
+        let mut escrow: GroupEscrow = env.storage().persistent().get(&key).unwrap();
+        escrow.deposits -= CONTRIBUTION;
+        env.storage().persistent().set(&key, &escrow);
+    });
+
+    end_cycle(&env);
+    let result = client.try_execute_payout(&group_a);
+    assert_eq!(result, Err(Ok(AjoError::InsufficientContractBalance)));
+
+    client.execute_payout(&group_b);
+    assert_eq!(client.get_group_escrow(&group_b).payouts, CONTRIBUTION * 2);
+}
+
+#[test]
+fn test_group_without_ledger_falls_back_to_contract_balance() {
+    let (env, client, contract_id, token_id, members) = setup_test_env(2);
+    let group_id = create_group(&client, &token_id, &members, 0);
+
+    // Groups created before escrow was tracked have no ledger
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Escrow(group_id));
+    });
+    let result = client.try_get_group_escrow(&group_id);
+    assert_eq!(result, Err(Ok(AjoError::RecordNotFound)));
+
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    end_cycle(&env);
+    client.execute_payout(&group_id);
+
+    assert_eq!(
+        client.claim(&members.get(0).unwrap(), &group_id),
+        CONTRIBUTION * 2
+    );
+}
@@ -0,0 +1,257 @@
+#![cfg(test)]
+
+//! Member exit tests
+//!
+//! These tests verify `leave_group` before a rotation starts and the
+//! creator-approved `request_exit` flow afterwards.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment, a fixed-order group and funded members
+fn setup_test_env(
+    member_count: u32,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_id, members, group_id)
+}
+
+/// Has every current member contribute, waits out the grace period and pays out.
+fn run_cycle(env: &Env, client: &AjoContractClient, group_id: u64) {
+    for member in client.get_group(&group_id).members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+}
+
+#[test]
+fn test_leave_before_start() {
+    let (env, client, token_id, members, _) = setup_test_env(3);
+    let leaver = members.get(1).unwrap();
+
+    // A group with a free slot stays forming, so members may still leave
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &4u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&leaver, &group_id);
+    client.join_group(&Address::generate(&env), &group_id);
+
+    client.leave_group(&leaver, &group_id);
+
+    let group = client.get_group(&group_id);
+    assert!(!group.members.contains(&leaver));
+    assert!(!group.payout_order.contains(&leaver));
+    assert!(!client.is_member(&group_id, &leaver));
+
+    // The freed slot can be taken again
+    client.join_group(&leaver, &group_id);
+    assert_eq!(client.get_group(&group_id).members.len(), 3);
+}
+
+#[test]
+fn test_leave_after_start_rejected() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(3);
+
+    assert_eq!(
+        client.try_leave_group(&members.get(1).unwrap(), &group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+}
+
+#[test]
+fn test_creator_cannot_leave() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(3);
+    let creator = members.get(0).unwrap();
+
+    assert_eq!(
+        client.try_leave_group(&creator, &group_id),
+        Err(Ok(AjoError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_request_exit(&creator, &group_id),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_approved_exit_refunds_current_cycle() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3);
+    let token_client = token::Client::new(&env, &token_id);
+    let (creator, leaver) = (members.get(0).unwrap(), members.get(2).unwrap());
+
+    // Cycle 1 pays the creator; the leaver then contributes to cycle 2
+    run_cycle(&env, &client, group_id);
+    client.contribute(&leaver, &group_id);
+
+    client.request_exit(&leaver, &group_id);
+    assert_eq!(client.get_exit_request(&group_id, &leaver).member, leaver);
+
+    client.approve_exit(&creator, &group_id, &leaver);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.members.len(), 2);
+    assert!(!group.payout_order.contains(&leaver));
+    assert_eq!(
+        client.try_get_exit_request(&group_id, &leaver),
+        Err(Ok(AjoError::RecordNotFound))
+    );
+
+    // The cycle 2 contribution is returned; cycle 1's was paid out
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &leaver),
+        CONTRIBUTION
+    );
+    client.claim(&leaver, &group_id);
+    assert_eq!(token_client.balance(&leaver), FUNDING - CONTRIBUTION);
+
+    // The rotation finishes with the remaining member
+    let remaining = members.get(1).unwrap();
+    let before = token_client.balance(&remaining);
+    run_cycle(&env, &client, group_id);
+    client.claim(&remaining, &group_id);
+    assert_eq!(
+        token_client.balance(&remaining),
+        before - CONTRIBUTION + CONTRIBUTION * 2
+    );
+    assert!(client.is_complete(&group_id));
+}
+
+#[test]
+fn test_exit_of_last_unpaid_member_completes_group() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+    let leaver = members.get(2).unwrap();
+
+    run_cycle(&env, &client, group_id);
+    run_cycle(&env, &client, group_id);
+
+    client.request_exit(&leaver, &group_id);
+    client.approve_exit(&members.get(0).unwrap(), &group_id, &leaver);
+
+    assert!(client.is_complete(&group_id));
+}
+
+#[test]
+fn test_exit_completing_group_refunds_current_cycle() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+    let (creator, second, leaver) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    run_cycle(&env, &client, group_id);
+    run_cycle(&env, &client, group_id);
+    client.claim(&creator, &group_id);
+    client.claim(&second, &group_id);
+
+    // The others pay into a cycle that will never be paid out
+    client.contribute(&creator, &group_id);
+    client.contribute(&second, &group_id);
+    client.request_exit(&leaver, &group_id);
+    client.approve_exit(&creator, &group_id, &leaver);
+    assert!(client.is_complete(&group_id));
+
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &creator),
+        CONTRIBUTION
+    );
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &second),
+        CONTRIBUTION
+    );
+    assert_eq!(client.get_group_escrow(&group_id).available(), 0);
+}
+
+#[test]
+fn test_paid_member_cannot_request_exit() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3);
+
+    // Fixed order pays the creator, then the second member
+    run_cycle(&env, &client, group_id);
+    run_cycle(&env, &client, group_id);
+
+    assert_eq!(
+        client.try_request_exit(&members.get(1).unwrap(), &group_id),
+        Err(Ok(AjoError::AlreadyReceivedPayout))
+    );
+}
+
+#[test]
+fn test_reject_exit() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(3);
+    let (creator, member) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.request_exit(&member, &group_id);
+    client.reject_exit(&creator, &group_id, &member);
+
+    assert!(client.is_member(&group_id, &member));
+    assert_eq!(
+        client.try_approve_exit(&creator, &group_id, &member),
+        Err(Ok(AjoError::RecordNotFound))
+    );
+}
+
+#[test]
+fn test_only_creator_approves_exit() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(3);
+    let member = members.get(1).unwrap();
+
+    client.request_exit(&member, &group_id);
+
+    assert_eq!(
+        client.try_approve_exit(&members.get(2).unwrap(), &group_id, &member),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
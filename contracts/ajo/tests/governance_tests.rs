@@ -0,0 +1,367 @@
+#![cfg(test)]
+
+//! Governance tests
+//!
+//! These tests verify the per-group voting rules for refund requests: the
+//! quorum, approval threshold and voting period chosen at creation, abstain
+//! votes, changing a vote before the deadline, closing a vote when the group
+//! ends first, and the history of refund proposals made for a group.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, GovernanceSettings, GroupSettings, GroupState,
+    PayoutStrategy, VoteChoice,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const VOTING_PERIOD: u64 = 604_800;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment with a full group of
+/// `member_count` funded members using `governance`, and an open refund
+/// request by the creator
+fn setup_test_env(
+    member_count: u32,
+    governance: GovernanceSettings,
+) -> (Env, AjoContractClient<'static>, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let creator = members.get(0).unwrap();
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            governance,
+            ..Default::default()
+        },
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    client.contribute(&creator, &group_id);
+    advance(&env, CYCLE_DURATION + GRACE_PERIOD + 1);
+    client.request_refund(&creator, &group_id);
+
+    (env, client, members, group_id)
+}
+
+/// Moves the ledger forward by `seconds`.
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp += seconds;
+    });
+}
+
+#[test]
+fn test_lone_vote_does_not_meet_default_quorum() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let requester = members.get(0).unwrap();
+
+    // A single "yes" from the requester is 100% in favour, but only a third
+    // of the members voted
+    client.vote_refund(&requester, &group_id, &VoteChoice::For);
+    advance(&env, VOTING_PERIOD + 1);
+
+    assert!(!client.execute_refund(&requester, &group_id));
+
+    // The proposal is closed as rejected and its votes cleared
+    let request = client.get_refund_request(&group_id);
+    assert!(request.executed);
+    assert!(!request.approved);
+    let result = client.try_get_refund_vote(&group_id, &request.id, &requester);
+    assert_eq!(result, Err(Ok(AjoError::RecordNotFound)));
+    let result = client.try_execute_refund(&requester, &group_id);
+    assert_eq!(result, Err(Ok(AjoError::RefundAlreadyExecuted)));
+}
+
+#[test]
+fn test_refund_closed_when_group_cancelled_during_vote() {
+    let (env, client, members, group_id) = setup_test_env(2, GovernanceSettings::default());
+    let requester = members.get(0).unwrap();
+
+    for member in members.iter() {
+        client.vote_refund(&member, &group_id, &VoteChoice::For);
+    }
+    client.cancel_group(&requester, &group_id);
+    let refunded = client.get_claimable_balance(&group_id, &requester);
+
+    let request = client.get_refund_request(&group_id);
+    assert!(request.executed);
+    assert!(!request.approved);
+
+    advance(&env, VOTING_PERIOD + 1);
+    let result = client.try_execute_refund(&requester, &group_id);
+    assert_eq!(result, Err(Ok(AjoError::GroupCancelled)));
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &requester),
+        refunded
+    );
+}
+
+#[test]
+fn test_refund_closed_when_group_completes_during_vote() {
+    let governance = GovernanceSettings {
+        voting_period: VOTING_PERIOD * 3,
+        ..Default::default()
+    };
+    let (env, client, members, group_id) = setup_test_env(2, governance);
+    let (requester, late) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    for member in members.iter() {
+        client.vote_refund(&member, &group_id, &VoteChoice::For);
+    }
+
+    // Both cycles are paid out before the vote closes
+    client.mark_default(&group_id, &late);
+    client.execute_payout(&group_id);
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    advance(&env, CYCLE_DURATION + GRACE_PERIOD + 1);
+    client.execute_payout(&group_id);
+    assert!(client.is_complete(&group_id));
+
+    let request = client.get_refund_request(&group_id);
+    assert!(request.executed);
+    assert!(!request.approved);
+
+    advance(&env, VOTING_PERIOD * 3);
+    let result = client.try_execute_refund(&requester, &group_id);
+    assert_eq!(result, Err(Ok(AjoError::GroupComplete)));
+    assert_ne!(client.get_group(&group_id).state, GroupState::Cancelled);
+}
+
+#[test]
+fn test_abstain_counts_towards_quorum_only() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let requester = members.get(0).unwrap();
+
+    client.vote_refund(&requester, &group_id, &VoteChoice::For);
+    client.vote_refund(&members.get(1).unwrap(), &group_id, &VoteChoice::Abstain);
+
+    let request = client.get_refund_request(&group_id);
+    assert_eq!(request.votes_for, 1);
+    assert_eq!(request.votes_against, 0);
+    assert_eq!(request.votes_abstain, 1);
+
+    advance(&env, VOTING_PERIOD + 1);
+    client.execute_refund(&requester, &group_id);
+    assert!(client.get_refund_request(&group_id).approved);
+}
+
+#[test]
+fn test_vote_can_change_before_deadline() {
+    let (env, client, members, group_id) = setup_test_env(2, GovernanceSettings::default());
+    let voter = members.get(1).unwrap();
+
+    client.vote_refund(&voter, &group_id, &VoteChoice::Against);
+    client.vote_refund(&voter, &group_id, &VoteChoice::For);
+
+    let request = client.get_refund_request(&group_id);
+    assert_eq!(request.votes_for, 1);
+    assert_eq!(request.votes_against, 0);
+    assert_eq!(
+        client
+            .get_refund_vote(&group_id, &request.id, &voter)
+            .choice,
+        VoteChoice::For
+    );
+
+    // Casting the same vote again changes nothing
+    let result = client.try_vote_refund(&voter, &group_id, &VoteChoice::For);
+    assert_eq!(result, Err(Ok(AjoError::AlreadyVoted)));
+
+    advance(&env, VOTING_PERIOD + 1);
+    let result = client.try_vote_refund(&voter, &group_id, &VoteChoice::Against);
+    assert_eq!(result, Err(Ok(AjoError::VotingPeriodEnded)));
+}
+
+#[test]
+fn test_custom_threshold_and_voting_period() {
+    let governance = GovernanceSettings {
+        quorum: 100,
+        threshold: 75,
+        voting_period: 86_400,
+        ..Default::default()
+    };
+    let (env, client, members, group_id) = setup_test_env(4, governance);
+    let requester = members.get(0).unwrap();
+
+    let request = client.get_refund_request(&group_id);
+    assert_eq!(request.voting_deadline, request.created_at + 86_400);
+
+    // Two thirds in favour falls short of a 75% threshold
+    client.vote_refund(&requester, &group_id, &VoteChoice::For);
+    client.vote_refund(&members.get(1).unwrap(), &group_id, &VoteChoice::For);
+    client.vote_refund(&members.get(2).unwrap(), &group_id, &VoteChoice::Against);
+    client.vote_refund(&members.get(3).unwrap(), &group_id, &VoteChoice::Abstain);
+
+    advance(&env, 86_400 + 1);
+    assert!(!client.execute_refund(&requester, &group_id));
+}
+
+#[test]
+fn test_full_quorum_requires_every_member() {
+    let governance = GovernanceSettings {
+        quorum: 100,
+        ..Default::default()
+    };
+    let (env, client, members, group_id) = setup_test_env(3, governance);
+    let requester = members.get(0).unwrap();
+
+    client.vote_refund(&requester, &group_id, &VoteChoice::For);
+    client.vote_refund(&members.get(1).unwrap(), &group_id, &VoteChoice::For);
+    advance(&env, VOTING_PERIOD + 1);
+
+    assert!(!client.execute_refund(&requester, &group_id));
+}
+
+#[test]
+fn test_invalid_governance_settings_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    for governance in [
+        GovernanceSettings {
+            quorum: 101,
+            ..Default::default()
+        },
+        GovernanceSettings {
+            threshold: 101,
+            ..Default::default()
+        },
+        GovernanceSettings {
+            voting_period: 31 * 86_400,
+            ..Default::default()
+        },
+        GovernanceSettings {
+            removal_threshold: 101,
+            ..Default::default()
+        },
+    ] {
+        let result = client.try_create_group(
+            &creator,
+            &token_id,
+            &CONTRIBUTION,
+            &CYCLE_DURATION,
+            &3u32,
+            &GRACE_PERIOD,
+            &10u32,
+            &PayoutStrategy::Fixed,
+            &GroupSettings {
+                governance,
+                ..Default::default()
+            },
+        );
+        assert_eq!(result, Err(Ok(AjoError::InvalidGroupState)));
+    }
+}
+
+#[test]
+fn test_open_proposal_blocks_new_one() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let second = members.get(1).unwrap();
+
+    let result = client.try_request_refund(&second, &group_id);
+    assert_eq!(result, Err(Ok(AjoError::RefundRequestExists)));
+
+    // Approved but not yet executed proposals still block new ones
+    client.vote_refund(&members.get(0).unwrap(), &group_id, &VoteChoice::For);
+    client.vote_refund(&second, &group_id, &VoteChoice::For);
+    advance(&env, VOTING_PERIOD + 1);
+
+    let result = client.try_request_refund(&second, &group_id);
+    assert_eq!(result, Err(Ok(AjoError::RefundRequestExists)));
+}
+
+#[test]
+fn test_new_proposal_after_rejection() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let (creator, second) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.vote_refund(&creator, &group_id, &VoteChoice::For);
+    client.vote_refund(&second, &group_id, &VoteChoice::Against);
+    advance(&env, VOTING_PERIOD + 1);
+
+    // The expired proposal is closed as rejected and its votes cleared, so
+    // they don't carry over to the new proposal
+    let proposal_id = client.request_refund(&second, &group_id);
+    assert_eq!(proposal_id, 2);
+
+    let rejected = client.get_refund_proposal(&group_id, &1);
+    assert!(rejected.executed);
+    assert!(!rejected.approved);
+    assert_eq!(rejected.votes_for, 1);
+    assert_eq!(rejected.votes_against, 1);
+    let result = client.try_get_refund_vote(&group_id, &1, &second);
+    assert_eq!(result, Err(Ok(AjoError::RecordNotFound)));
+
+    client.vote_refund(&creator, &group_id, &VoteChoice::For);
+    client.vote_refund(&second, &group_id, &VoteChoice::For);
+    let request = client.get_refund_request(&group_id);
+    assert_eq!(request.id, proposal_id);
+    assert_eq!(request.requester, second);
+    assert_eq!(request.votes_for, 2);
+    assert_eq!(request.votes_against, 0);
+
+    advance(&env, VOTING_PERIOD + 1);
+    client.execute_refund(&second, &group_id);
+    assert!(client.get_refund_proposal(&group_id, &proposal_id).approved);
+}
+
+#[test]
+fn test_list_refund_proposals() {
+    let (env, client, members, group_id) = setup_test_env(2, GovernanceSettings::default());
+    let creator = members.get(0).unwrap();
+
+    advance(&env, VOTING_PERIOD + 1);
+    client.request_refund(&creator, &group_id);
+
+    let proposals = client.list_refund_proposals(&group_id);
+    assert_eq!(proposals.len(), 2);
+    assert_eq!(proposals.get(0).unwrap().id, 1);
+    assert!(proposals.get(0).unwrap().executed);
+    assert_eq!(proposals.get(1).unwrap().id, 2);
+    assert!(!proposals.get(1).unwrap().executed);
+
+    let result = client.try_get_refund_proposal(&group_id, &3);
+    assert_eq!(result, Err(Ok(AjoError::NoRefundRequest)));
+    let result = client.try_list_refund_proposals(&99);
+    assert_eq!(result, Err(Ok(AjoError::GroupNotFound)));
+}
@@ -0,0 +1,176 @@
+#![cfg(test)]
+
+//! Group start tests
+//!
+//! These tests verify the `Forming` state, the `min_members` threshold and
+//! starting a group either with `start_group` or by filling it up.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, GroupSettings, GroupState, PayoutStrategy,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+
+/// Helper function to create a test environment, a group and funded members
+fn setup_test_env(
+    member_count: u32,
+    max_members: u32,
+    min_members: u32,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &(CONTRIBUTION * 10));
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &max_members,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            min_members,
+            ..Default::default()
+        },
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_id, members, group_id)
+}
+
+#[test]
+fn test_group_starts_forming() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(2, 5, 2);
+
+    assert_eq!(client.get_group(&group_id).state, GroupState::Forming);
+
+    let status = client.get_group_status(&group_id);
+    assert_eq!(status.state, GroupState::Forming);
+    assert!(!status.is_cycle_active);
+
+    assert_eq!(
+        client.try_contribute(&members.get(0).unwrap(), &group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+    assert_eq!(
+        client.try_execute_payout(&group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+}
+
+#[test]
+fn test_start_group_begins_first_cycle() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3, 5, 3);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += 3600;
+    });
+    let now = env.ledger().timestamp();
+    client.start_group(&members.get(0).unwrap(), &group_id);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.state, GroupState::Active);
+    assert_eq!(group.cycle_start_time, now);
+    assert!(client.get_group_status(&group_id).is_cycle_active);
+
+    client.contribute(&members.get(1).unwrap(), &group_id);
+}
+
+#[test]
+fn test_start_group_requires_min_members() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(2, 5, 3);
+    let creator = members.get(0).unwrap();
+
+    assert_eq!(
+        client.try_start_group(&creator, &group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+    assert_eq!(client.get_group(&group_id).state, GroupState::Forming);
+}
+
+#[test]
+fn test_only_creator_can_start() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(2, 5, 2);
+
+    assert_eq!(
+        client.try_start_group(&members.get(1).unwrap(), &group_id),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_full_group_starts_automatically() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3, 3, 2);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.state, GroupState::Active);
+    assert_eq!(group.cycle_start_time, env.ledger().timestamp());
+
+    // Already started
+    assert_eq!(
+        client.try_start_group(&members.get(0).unwrap(), &group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+}
+
+#[test]
+fn test_membership_locked_after_start() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(2, 5, 2);
+
+    client.start_group(&members.get(0).unwrap(), &group_id);
+
+    assert_eq!(
+        client.try_join_group(&Address::generate(&env), &group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+    assert_eq!(
+        client.try_leave_group(&members.get(1).unwrap(), &group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+}
+
+#[test]
+fn test_min_members_above_max_rejected() {
+    let (env, client, token_id, _members, _) = setup_test_env(1, 5, 2);
+
+    let result = client.try_create_group(
+        &Address::generate(&env),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &3u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            min_members: 4,
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(AjoError::MaxMembersBelowMinimum)));
+}
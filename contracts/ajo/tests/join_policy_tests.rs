@@ -0,0 +1,196 @@
+#![cfg(test)]
+
+//! Join policy tests
+//!
+//! These tests verify invite-only and approval-required groups, along with
+//! the invite and join-request entrypoints.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, GroupSettings, JoinPolicy, PayoutStrategy,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+/// Helper function to create a test environment, contract and a group with the given policy
+fn setup_test_env(join_policy: JoinPolicy) -> (Env, AjoContractClient<'static>, Address, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+
+    let creator = Address::generate(&env);
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &100_000_000i128,
+        &604_800u64,
+        &3u32,
+        &86_400u64,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            join_policy,
+            ..Default::default()
+        },
+    );
+
+    (env, client, creator, group_id)
+}
+
+#[test]
+fn test_open_group_accepts_anyone() {
+    let (env, client, _creator, group_id) = setup_test_env(JoinPolicy::Open);
+    let member = Address::generate(&env);
+
+    client.join_group(&member, &group_id);
+    assert!(client.is_member(&group_id, &member));
+
+    // Open groups don't take join requests
+    assert_eq!(
+        client.try_request_join(&Address::generate(&env), &group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+}
+
+#[test]
+fn test_invite_only_requires_invite() {
+    let (env, client, creator, group_id) = setup_test_env(JoinPolicy::InviteOnly);
+    let invitee = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(
+        client.try_join_group(&stranger, &group_id),
+        Err(Ok(AjoError::Unauthorized))
+    );
+
+    client.invite_member(&creator, &group_id, &invitee);
+    assert!(client.get_invites(&group_id).contains(&invitee));
+
+    client.join_group(&invitee, &group_id);
+    assert!(client.is_member(&group_id, &invitee));
+
+    // The invite is used up
+    assert!(client.get_invites(&group_id).is_empty());
+    assert_eq!(
+        client.try_request_join(&stranger, &group_id),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+}
+
+#[test]
+fn test_only_creator_can_invite() {
+    let (env, client, _creator, group_id) = setup_test_env(JoinPolicy::InviteOnly);
+
+    assert_eq!(
+        client.try_invite_member(
+            &Address::generate(&env),
+            &group_id,
+            &Address::generate(&env)
+        ),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_approval_required_flow() {
+    let (env, client, creator, group_id) = setup_test_env(JoinPolicy::ApprovalRequired);
+    let applicant = Address::generate(&env);
+
+    assert_eq!(
+        client.try_join_group(&applicant, &group_id),
+        Err(Ok(AjoError::Unauthorized))
+    );
+
+    client.request_join(&applicant, &group_id);
+    let requests = client.get_join_requests(&group_id);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests.get(0).unwrap().member, applicant);
+
+    // Requesting again doesn't duplicate the request
+    client.request_join(&applicant, &group_id);
+    assert_eq!(client.get_join_requests(&group_id).len(), 1);
+
+    client.approve_join(&creator, &group_id, &applicant);
+    assert!(client.is_member(&group_id, &applicant));
+    assert!(client.get_join_requests(&group_id).is_empty());
+}
+
+#[test]
+fn test_approval_required_admits_invitees_directly() {
+    let (env, client, creator, group_id) = setup_test_env(JoinPolicy::ApprovalRequired);
+    let invitee = Address::generate(&env);
+
+    client.invite_member(&creator, &group_id, &invitee);
+    client.join_group(&invitee, &group_id);
+
+    assert!(client.is_member(&group_id, &invitee));
+}
+
+#[test]
+fn test_request_join_to_cancelled_group() {
+    let (env, client, creator, group_id) = setup_test_env(JoinPolicy::ApprovalRequired);
+
+    client.cancel_group(&creator, &group_id);
+    assert_eq!(
+        client.try_request_join(&Address::generate(&env), &group_id),
+        Err(Ok(AjoError::GroupCancelled))
+    );
+}
+
+#[test]
+fn test_reject_join() {
+    let (env, client, creator, group_id) = setup_test_env(JoinPolicy::ApprovalRequired);
+    let applicant = Address::generate(&env);
+
+    client.request_join(&applicant, &group_id);
+    client.reject_join(&creator, &group_id, &applicant);
+
+    assert!(!client.is_member(&group_id, &applicant));
+    assert_eq!(
+        client.try_approve_join(&creator, &group_id, &applicant),
+        Err(Ok(AjoError::RecordNotFound))
+    );
+}
+
+#[test]
+fn test_approve_join_respects_capacity() {
+    let (env, client, creator, group_id) = setup_test_env(JoinPolicy::ApprovalRequired);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+
+    client.request_join(&first, &group_id);
+    client.request_join(&second, &group_id);
+    client.request_join(&third, &group_id);
+    client.approve_join(&creator, &group_id, &first);
+    client.approve_join(&creator, &group_id, &second);
+
+    assert_eq!(
+        client.try_approve_join(&creator, &group_id, &third),
+        Err(Ok(AjoError::MaxMembersExceeded))
+    );
+}
+
+#[test]
+fn test_restricted_slot_transfer_requires_invite() {
+    let (env, client, creator, group_id) = setup_test_env(JoinPolicy::InviteOnly);
+    let member = Address::generate(&env);
+    let relative = Address::generate(&env);
+
+    client.invite_member(&creator, &group_id, &member);
+    client.join_group(&member, &group_id);
+
+    assert_eq!(
+        client.try_transfer_slot(&member, &relative, &group_id),
+        Err(Ok(AjoError::Unauthorized))
+    );
+
+    client.invite_member(&creator, &group_id, &relative);
+    client.transfer_slot(&member, &relative, &group_id);
+    assert!(client.is_member(&group_id, &relative));
+}
@@ -0,0 +1,447 @@
+#![cfg(test)]
+
+//! Keeper tests
+//!
+//! These tests verify `poke` and `process_due_groups`: which state
+//! transitions they perform once they are due and how the keeper fee is paid
+//! from the penalty pool.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, DataKey, GroupSettings, PayoutStrategy, VoteChoice,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const PENALTY: i128 = CONTRIBUTION / 10;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const VOTING_PERIOD: u64 = 604_800;
+
+/// Helper function to create a test environment, contract, token and funded members
+fn setup_test_env(member_count: u32) -> (Env, AjoContractClient<'static>, Address, Vec<Address>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &(CONTRIBUTION * 10));
+        members.push_back(member);
+    }
+
+    (env, client, token_id, members)
+}
+
+/// Creates a full group of `members` with the given keeper fee.
+fn create_group(
+    client: &AjoContractClient,
+    token_id: &Address,
+    members: &Vec<Address>,
+    keeper_fee: i128,
+) -> u64 {
+    create_group_with(
+        client,
+        token_id,
+        members,
+        PayoutStrategy::Fixed,
+        GroupSettings {
+            keeper_fee,
+            ..Default::default()
+        },
+    )
+}
+
+/// Creates a full group of `members` with the given strategy and settings.
+fn create_group_with(
+    client: &AjoContractClient,
+    token_id: &Address,
+    members: &Vec<Address>,
+    strategy: PayoutStrategy,
+    settings: GroupSettings,
+) -> u64 {
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &members.len(),
+        &GRACE_PERIOD,
+        &10u32,
+        &strategy,
+        &settings,
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+    group_id
+}
+
+/// Overwrites a group's escrow ledger so it holds only `deposits` and
+/// `penalties`.
+fn set_escrow(
+    env: &Env,
+    client: &AjoContractClient,
+    group_id: u64,
+    deposits: i128,
+    penalties: i128,
+) {
+    let mut escrow = client.get_group_escrow(&group_id);
+    escrow.deposits = deposits;
+    escrow.penalties = penalties;
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(group_id), &escrow);
+    });
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp += seconds;
+    });
+}
+
+#[test]
+fn test_poke_does_nothing_before_grace_period_ends() {
+    let (env, client, token_id, members) = setup_test_env(2);
+    let group_id = create_group(&client, &token_id, &members, PENALTY);
+    let keeper = Address::generate(&env);
+
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    advance(&env, CYCLE_DURATION + GRACE_PERIOD);
+
+    let report = client.poke(&keeper, &group_id);
+    assert!(!report.did_work());
+    assert_eq!(report.fee, 0);
+    assert_eq!(client.get_group(&group_id).current_cycle, 1);
+}
+
+#[test]
+fn test_poke_marks_defaults_and_pays_out() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group(&client, &token_id, &members, PENALTY / 2);
+    let (creator, late, defaulter) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+    let keeper = Address::generate(&env);
+
+    client.contribute(&creator, &group_id);
+    advance(&env, CYCLE_DURATION + 3600);
+    client.contribute(&late, &group_id);
+    advance(&env, GRACE_PERIOD);
+
+    let report = client.poke(&keeper, &group_id);
+    assert_eq!(report.defaults_marked, 1);
+    assert!(report.payout_executed);
+    assert!(!report.refund_finalized);
+    // The penalty pool first went towards the default, leaving nothing for the keeper
+    assert_eq!(report.fee, 0);
+    assert_eq!(
+        client
+            .get_default_record(&group_id, &1u32, &defaulter)
+            .from_penalty_pool,
+        PENALTY
+    );
+    assert_eq!(client.get_payout_record(&group_id, &1u32).member, creator);
+    assert_eq!(client.get_group(&group_id).current_cycle, 2);
+}
+
+#[test]
+fn test_keeper_fee_taken_from_penalty_pool() {
+    let (env, client, token_id, members) = setup_test_env(2);
+    let group_id = create_group(&client, &token_id, &members, PENALTY / 2);
+    let (creator, late) = (members.get(0).unwrap(), members.get(1).unwrap());
+    let keeper = Address::generate(&env);
+
+    client.contribute(&creator, &group_id);
+    advance(&env, CYCLE_DURATION + 3600);
+    client.contribute(&late, &group_id);
+    advance(&env, GRACE_PERIOD);
+
+    let report = client.poke(&keeper, &group_id);
+    assert_eq!(report.defaults_marked, 0);
+    assert!(report.payout_executed);
+    assert_eq!(report.fee, PENALTY / 2);
+
+    // The recipient's penalty bonus is reduced by the fee
+    assert_eq!(
+        client.get_payout_record(&group_id, &1u32).amount,
+        CONTRIBUTION * 2 + PENALTY - PENALTY / 2
+    );
+    assert_eq!(client.claim(&keeper, &group_id), PENALTY / 2);
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&keeper),
+        PENALTY / 2
+    );
+}
+
+#[test]
+fn test_poke_waits_for_refund_vote_then_executes_it() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group(&client, &token_id, &members, PENALTY);
+    let (creator, second) = (members.get(0).unwrap(), members.get(1).unwrap());
+    let keeper = Address::generate(&env);
+
+    client.contribute(&creator, &group_id);
+    advance(&env, CYCLE_DURATION + GRACE_PERIOD + 1);
+    client.request_refund(&creator, &group_id);
+    client.vote_refund(&creator, &group_id, &VoteChoice::For);
+    client.vote_refund(&second, &group_id, &VoteChoice::For);
+
+    // Members are still voting, so no defaults or payout
+    let report = client.poke(&keeper, &group_id);
+    assert!(!report.did_work());
+
+    advance(&env, VOTING_PERIOD + 1);
+    let report = client.poke(&keeper, &group_id);
+    assert!(report.refund_finalized);
+    assert!(!report.payout_executed);
+
+    let request = client.get_refund_request(&group_id);
+    assert!(request.executed);
+    assert!(request.approved);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &creator),
+        CONTRIBUTION
+    );
+    assert_eq!(
+        client.try_contribute(&second, &group_id),
+        Err(Ok(AjoError::GroupCancelled))
+    );
+}
+
+#[test]
+fn test_poke_rejects_failed_refund_vote() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group(&client, &token_id, &members, 0);
+    let (creator, second) = (members.get(0).unwrap(), members.get(1).unwrap());
+    let keeper = Address::generate(&env);
+
+    client.contribute(&creator, &group_id);
+    advance(&env, CYCLE_DURATION + GRACE_PERIOD + 1);
+    client.request_refund(&creator, &group_id);
+    client.vote_refund(&second, &group_id, &VoteChoice::Against);
+    advance(&env, VOTING_PERIOD + 1);
+
+    let report = client.poke(&keeper, &group_id);
+    assert!(report.refund_finalized);
+
+    let request = client.get_refund_request(&group_id);
+    assert!(request.executed);
+    assert!(!request.approved);
+
+    // With the request settled, the next poke moves the group on
+    let report = client.poke(&keeper, &group_id);
+    assert_eq!(report.defaults_marked, 2);
+    assert!(report.payout_executed);
+}
+
+#[test]
+fn test_process_due_groups_resumes_where_it_left_off() {
+    let (env, client, token_id, members) = setup_test_env(2);
+    let keeper = Address::generate(&env);
+
+    let due = create_group(&client, &token_id, &members, 0);
+    let partly_paid = create_group(&client, &token_id, &members, 0);
+    let also_due = create_group(&client, &token_id, &members, 0);
+    for member in members.iter() {
+        client.contribute(&member, &due);
+        client.contribute(&member, &also_due);
+    }
+    client.contribute(&members.get(0).unwrap(), &partly_paid);
+    advance(&env, CYCLE_DURATION + GRACE_PERIOD + 1);
+
+    let reports = client.process_due_groups(&keeper, &2u32);
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports.get(0).unwrap().group_id, due);
+    assert_eq!(reports.get(1).unwrap().group_id, partly_paid);
+    assert_eq!(reports.get(1).unwrap().defaults_marked, 1);
+
+    let reports = client.process_due_groups(&keeper, &2u32);
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports.get(0).unwrap().group_id, also_due);
+
+    for group_id in [due, partly_paid, also_due] {
+        assert_eq!(client.get_group(&group_id).current_cycle, 2);
+    }
+}
+
+#[test]
+fn test_process_due_groups_skips_failing_group() {
+    let (env, client, token_id, members) = setup_test_env(2);
+    let keeper = Address::generate(&env);
+
+    let due = create_group(&client, &token_id, &members, 0);
+    let broken = create_group(&client, &token_id, &members, 0);
+    let also_due = create_group(&client, &token_id, &members, 0);
+    for group_id in [due, broken, also_due] {
+        for member in members.iter() {
+            client.contribute(&member, &group_id);
+        }
+    }
+    advance(&env, CYCLE_DURATION + GRACE_PERIOD + 1);
+
+    // Leave the middle group's escrow unable to cover its payout
+    set_escrow(&env, &client, broken, 0, 0);
+    assert_eq!(
+        client.try_poke(&keeper, &broken),
+        Err(Ok(AjoError::InsufficientContractBalance))
+    );
+
+    let reports = client.process_due_groups(&keeper, &2u32);
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports.get(0).unwrap().group_id, due);
+    assert_eq!(client.get_group(&broken).current_cycle, 1);
+
+    // The sweep moved past the failing group rather than stopping at it
+    let reports = client.process_due_groups(&keeper, &1u32);
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports.get(0).unwrap().group_id, also_due);
+}
+
+#[test]
+fn test_skipped_auction_payout_credits_nobody() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group_with(
+        &client,
+        &token_id,
+        &members,
+        PayoutStrategy::BidBased,
+        GroupSettings {
+            keeper_fee: PENALTY / 2,
+            ..Default::default()
+        },
+    );
+    let (creator, bidder, late) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+    let keeper = Address::generate(&env);
+    let discount = CONTRIBUTION * 3 / 10;
+
+    client.place_bid(&bidder, &group_id, &discount);
+    client.contribute(&creator, &group_id);
+    client.contribute(&bidder, &group_id);
+    advance(&env, CYCLE_DURATION + 3600);
+    client.contribute(&late, &group_id);
+    advance(&env, GRACE_PERIOD);
+
+    // The escrow covers the winner's pot and the keeper fee, but not the
+    // discount shares owed to the other members
+    set_escrow(
+        &env,
+        &client,
+        group_id,
+        CONTRIBUTION * 3 - discount,
+        PENALTY,
+    );
+
+    let reports = client.process_due_groups(&keeper, &1u32);
+    assert_eq!(reports.len(), 0);
+
+    assert_eq!(client.get_group(&group_id).current_cycle, 1);
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), PENALTY);
+    assert_eq!(
+        client.try_get_payout_record(&group_id, &1u32),
+        Err(Ok(AjoError::NoPayoutRecord))
+    );
+    assert_eq!(client.get_claimable_balance(&group_id, &keeper), 0);
+    for member in members.iter() {
+        assert_eq!(client.get_claimable_balance(&group_id, &member), 0);
+        assert_eq!(client.get_member_position(&group_id, &member).received, 0);
+    }
+    let paid_out = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .has(&DataKey::PayoutReceived(group_id, bidder.clone()))
+    });
+    assert!(!paid_out);
+}
+
+#[test]
+fn test_skipped_refund_settlement_pays_no_keeper_fee() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group_with(
+        &client,
+        &token_id,
+        &members,
+        PayoutStrategy::Fixed,
+        GroupSettings {
+            keeper_fee: PENALTY,
+            collateral_amount: CONTRIBUTION / 2,
+            ..Default::default()
+        },
+    );
+    let (creator, late) = (members.get(0).unwrap(), members.get(1).unwrap());
+    let keeper = Address::generate(&env);
+
+    client.contribute(&creator, &group_id);
+    advance(&env, CYCLE_DURATION + 3600);
+    client.contribute(&late, &group_id);
+    advance(&env, GRACE_PERIOD);
+    client.request_refund(&creator, &group_id);
+    for member in members.iter() {
+        client.vote_refund(&member, &group_id, &VoteChoice::For);
+    }
+    advance(&env, VOTING_PERIOD + 1);
+
+    // The escrow covers the keeper fee but not all of the collateral the
+    // refund releases
+    set_escrow(&env, &client, group_id, CONTRIBUTION, PENALTY);
+
+    // Every sweep skips the group without paying for it
+    for _ in 0..2 {
+        let reports = client.process_due_groups(&keeper, &1u32);
+        assert_eq!(reports.len(), 0);
+    }
+
+    assert_eq!(client.get_claimable_balance(&group_id, &keeper), 0);
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), PENALTY);
+    assert!(!client.get_refund_request(&group_id).executed);
+    assert!(!client.get_group(&group_id).is_complete);
+    for member in members.iter() {
+        assert_eq!(client.get_claimable_balance(&group_id, &member), 0);
+        assert_eq!(
+            client.get_member_collateral(&group_id, &member),
+            CONTRIBUTION / 2
+        );
+    }
+}
+
+#[test]
+fn test_negative_keeper_fee_rejected() {
+    let (_env, client, token_id, members) = setup_test_env(1);
+
+    let result = client.try_create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &2u32,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            keeper_fee: -1,
+            ..Default::default()
+        },
+    );
+    assert_eq!(result, Err(Ok(AjoError::ContributionAmountNegative)));
+}
@@ -0,0 +1,371 @@
+#![cfg(test)]
+
+//! Storage migration tests
+//!
+//! These tests move entries back to the legacy symbol-tuple layout and verify
+//! that `migrate_storage` restores them under `DataKey`.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, DataKey, GroupSettings, PayoutStrategy, VoteChoice,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, Address, Env, IntoVal, Map, Symbol, Val,
+};
+
+/// `Group` fields that did not exist in the legacy layout.
+const NEW_GROUP_FIELDS: [&str; 5] = [
+    "payout_order",
+    "payout_strategy",
+    "round",
+    "round_start_cycle",
+    "settings",
+];
+
+/// `RefundRecord` fields that did not exist in the legacy layout.
+const NEW_REFUND_RECORD_FIELDS: [&str; 3] = ["contributed", "penalties_paid", "received"];
+
+/// Helper function to create a test environment, contract and funded token
+fn setup_test_env() -> (
+    Env,
+    AjoContractClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+
+    let creator = Address::generate(&env);
+    let member2 = Address::generate(&env);
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+    token_admin_client.mint(&creator, &1_000_000_000i128);
+    token_admin_client.mint(&member2, &1_000_000_000i128);
+
+    (env, client, contract_id, token_id, creator, member2)
+}
+
+/// Moves a persistent entry from its `DataKey` slot to a legacy tuple key.
+fn downgrade_persistent<K: IntoVal<Env, Val>>(env: &Env, new_key: &DataKey, legacy_key: &K) {
+    let persistent = env.storage().persistent();
+    let value: Val = persistent.get(new_key).unwrap();
+    persistent.remove(new_key);
+    persistent.set(legacy_key, &value);
+}
+
+/// Moves a group to its legacy tuple key, dropping fields the legacy layout lacked.
+fn downgrade_group(env: &Env, group_id: u64) {
+    let persistent = env.storage().persistent();
+    let new_key = DataKey::Group(group_id);
+    let mut group: Map<Symbol, Val> = persistent.get(&new_key).unwrap();
+    for field in NEW_GROUP_FIELDS {
+        group.remove(Symbol::new(env, field));
+    }
+    persistent.remove(&new_key);
+    persistent.set(&(symbol_short!("GROUP"), group_id), &group);
+}
+
+/// Moves an instance entry from its `DataKey` slot to a legacy symbol key.
+fn downgrade_instance<K: IntoVal<Env, Val>>(env: &Env, new_key: &DataKey, legacy_key: &K) {
+    let instance = env.storage().instance();
+    let value: Val = instance.get(new_key).unwrap();
+    instance.remove(new_key);
+    instance.set(legacy_key, &value);
+}
+
+/// Creates a group with one payout and one late contribution, then rewrites
+/// its storage in the legacy layout.
+fn setup_legacy_group(
+    env: &Env,
+    client: &AjoContractClient,
+    contract_id: &Address,
+    token_id: &Address,
+    creator: &Address,
+    member2: &Address,
+) -> u64 {
+    let cycle_duration = 604_800u64;
+    let grace_period = 86_400u64;
+    let group_id = client.create_group(
+        creator,
+        token_id,
+        &100_000_000i128,
+        &cycle_duration,
+        &2u32,
+        &grace_period,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(member2, &group_id);
+    client.contribute(creator, &group_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp += cycle_duration + 3600;
+    });
+    client.contribute(member2, &group_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp += grace_period;
+    });
+    client.execute_payout(&group_id);
+    client.pause();
+
+    env.as_contract(contract_id, || {
+        downgrade_instance(env, &DataKey::Admin, &symbol_short!("ADMIN"));
+        downgrade_instance(env, &DataKey::GroupCounter, &symbol_short!("GCOUNTER"));
+        downgrade_instance(env, &DataKey::Paused, &symbol_short!("PAUSED"));
+
+        downgrade_group(env, group_id);
+        downgrade_persistent(
+            env,
+            &DataKey::CyclePenaltyPool(group_id, 1),
+            &(symbol_short!("PENPOOL"), group_id, 1u32),
+        );
+        downgrade_persistent(
+            env,
+            &DataKey::PayoutReceived(group_id, creator.clone()),
+            &(symbol_short!("PAYOUT"), group_id, creator.clone()),
+        );
+        for member in [creator, member2] {
+            downgrade_persistent(
+                env,
+                &DataKey::Contribution(group_id, 1, member.clone()),
+                &(symbol_short!("CONTRIB"), group_id, 1u32, member.clone()),
+            );
+            downgrade_persistent(
+                env,
+                &DataKey::ContributionDetail(group_id, 1, member.clone()),
+                &(symbol_short!("CONTREC"), group_id, 1u32, member.clone()),
+            );
+            downgrade_persistent(
+                env,
+                &DataKey::MemberPenalty(group_id, member.clone()),
+                &(symbol_short!("PENALTY"), group_id, member.clone()),
+            );
+        }
+    });
+
+    group_id
+}
+
+#[test]
+fn test_migrate_storage_restores_group() {
+    let (env, client, contract_id, token_id, creator, member2) = setup_test_env();
+    let group_id = setup_legacy_group(&env, &client, &contract_id, &token_id, &creator, &member2);
+
+    // Legacy entries are invisible to the current layout
+    assert_eq!(
+        client.try_get_group(&group_id),
+        Err(Ok(AjoError::GroupNotFound))
+    );
+
+    let next = client.migrate_storage(&1u64, &10u32);
+    assert_eq!(next, 2);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.current_cycle, 2);
+    assert_eq!(group.payout_index, 1);
+    assert_eq!(group.payout_strategy, PayoutStrategy::Fixed);
+    assert_eq!(group.payout_order, group.members);
+
+    let status = client.get_contribution_status(&group_id, &1u32);
+    assert!(status.iter().all(|(_, paid)| paid));
+    assert_eq!(
+        client.get_cycle_penalty_pool(&group_id, &1u32),
+        5_000_000i128
+    );
+
+    let detail = client.get_contribution_detail(&group_id, &1u32, &member2);
+    assert!(detail.is_late);
+
+    let record = client.get_member_penalty_record(&group_id, &member2);
+    assert_eq!(record.late_count, 1);
+
+    // Legacy entries were removed
+    env.as_contract(&contract_id, || {
+        let persistent = env.storage().persistent();
+        assert!(!persistent.has(&(symbol_short!("GROUP"), group_id)));
+        assert!(!persistent.has(&(symbol_short!("CONTRIB"), group_id, 1u32, creator.clone())));
+        assert!(persistent.has(&DataKey::PayoutReceived(group_id, creator.clone())));
+    });
+}
+
+#[test]
+fn test_migrate_storage_restores_instance_state() {
+    let (env, client, contract_id, token_id, creator, member2) = setup_test_env();
+    setup_legacy_group(&env, &client, &contract_id, &token_id, &creator, &member2);
+
+    client.migrate_storage(&1u64, &10u32);
+
+    // Pause flag survived, and the admin can still unpause
+    assert_eq!(
+        client.try_contribute(&creator, &1u64),
+        Err(Ok(AjoError::ContractPaused))
+    );
+    client.unpause();
+
+    // Group counter survived, so new IDs don't collide
+    let new_group_id = client.create_group(
+        &creator,
+        &token_id,
+        &100_000_000i128,
+        &604_800u64,
+        &2u32,
+        &86_400u64,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    assert_eq!(new_group_id, 2);
+}
+
+#[test]
+fn test_migrate_storage_is_idempotent() {
+    let (env, client, contract_id, token_id, creator, member2) = setup_test_env();
+    let group_id = setup_legacy_group(&env, &client, &contract_id, &token_id, &creator, &member2);
+
+    client.migrate_storage(&1u64, &10u32);
+    client.migrate_storage(&1u64, &10u32);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.payout_index, 1);
+}
+
+#[test]
+fn test_migrate_storage_in_batches() {
+    let (env, client, contract_id, token_id, creator, member2) = setup_test_env();
+    let group_id = setup_legacy_group(&env, &client, &contract_id, &token_id, &creator, &member2);
+
+    // An empty batch only migrates instance storage
+    let next = client.migrate_storage(&1u64, &0u32);
+    assert_eq!(next, 1);
+    assert_eq!(
+        client.try_get_group(&group_id),
+        Err(Ok(AjoError::GroupNotFound))
+    );
+
+    let next = client.migrate_storage(&next, &1u32);
+    assert_eq!(next, 2);
+    client.get_group(&group_id);
+}
+
+#[test]
+fn test_migrate_storage_restores_refund_records() {
+    let (env, client, contract_id, token_id, creator, member2) = setup_test_env();
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &100_000_000i128,
+        &604_800u64,
+        &2u32,
+        &86_400u64,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&member2, &group_id);
+    client.contribute(&creator, &group_id);
+
+    let admin: Address = env.as_contract(&contract_id, || {
+        env.storage().instance().get(&DataKey::Admin).unwrap()
+    });
+    client.emergency_refund(&admin, &group_id);
+
+    env.as_contract(&contract_id, || {
+        downgrade_group(&env, group_id);
+
+        let persistent = env.storage().persistent();
+        for member in [&creator, &member2] {
+            let new_key = DataKey::RefundRecord(group_id, member.clone());
+            let mut record: Map<Symbol, Val> = persistent.get(&new_key).unwrap();
+            for field in NEW_REFUND_RECORD_FIELDS {
+                record.remove(Symbol::new(&env, field));
+            }
+            persistent.remove(&new_key);
+            persistent.set(
+                &(symbol_short!("REFUND"), group_id, member.clone()),
+                &record,
+            );
+        }
+    });
+
+    client.migrate_storage(&1u64, &10u32);
+
+    // Legacy refunds only returned contributions
+    let record = client.get_refund_record(&group_id, &creator);
+    assert_eq!(record.amount, 100_000_000i128);
+    assert_eq!(record.contributed, 100_000_000i128);
+    assert_eq!(record.penalties_paid, 0);
+    assert_eq!(record.received, 0);
+}
+
+#[test]
+fn test_migrate_storage_restores_refund_votes() {
+    let (env, client, contract_id, token_id, creator, member2) = setup_test_env();
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &100_000_000i128,
+        &604_800u64,
+        &2u32,
+        &86_400u64,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&member2, &group_id);
+    client.contribute(&creator, &group_id);
+    env.ledger()
+        .with_mut(|li| li.timestamp += 604_800 + 86_400 + 1);
+    client.request_refund(&creator, &group_id);
+    client.vote_refund(&creator, &group_id, &VoteChoice::For);
+
+    env.as_contract(&contract_id, || {
+        downgrade_group(&env, group_id);
+
+        // Legacy groups had a single request with no ID or abstain count,
+        // and votes were a bool
+        let persistent = env.storage().persistent();
+        let request_key = DataKey::RefundRequest(group_id, 1);
+        let mut request: Map<Symbol, Val> = persistent.get(&request_key).unwrap();
+        request.remove(Symbol::new(&env, "id"));
+        request.remove(Symbol::new(&env, "votes_abstain"));
+        persistent.remove(&request_key);
+        persistent.remove(&DataKey::RefundProposalCount(group_id));
+        persistent.set(&(symbol_short!("REFREQ"), group_id), &request);
+
+        let vote_key = DataKey::RefundVote(group_id, 1, creator.clone());
+        let mut vote: Map<Symbol, Val> = persistent.get(&vote_key).unwrap();
+        vote.remove(Symbol::new(&env, "proposal_id"));
+        vote.remove(Symbol::new(&env, "choice"));
+        vote.set(Symbol::new(&env, "in_favor"), true.into_val(&env));
+        persistent.remove(&vote_key);
+        persistent.set(
+            &(symbol_short!("REFVOTE"), group_id, creator.clone()),
+            &vote,
+        );
+    });
+
+    client.migrate_storage(&1u64, &10u32);
+
+    let request = client.get_refund_request(&group_id);
+    assert_eq!(request.id, 1);
+    assert_eq!(request.votes_for, 1);
+    assert_eq!(request.votes_abstain, 0);
+    assert_eq!(
+        client.get_refund_vote(&group_id, &1, &creator).choice,
+        VoteChoice::For
+    );
+    assert_eq!(client.list_refund_proposals(&group_id).len(), 1);
+}
@@ -0,0 +1,208 @@
+#![cfg(test)]
+
+//! Multi-round tests
+//!
+//! These tests verify groups created with `rounds` above 1: starting the next
+//! round after the last payout, keeping a record of each round, and only
+//! completing after the final round.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment and a full group of
+/// `member_count` funded members running `rounds` rounds
+fn setup_test_env(
+    member_count: u32,
+    rounds: u32,
+    collateral_amount: i128,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            rounds,
+            collateral_amount,
+            ..Default::default()
+        },
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_id, members, group_id)
+}
+
+/// Pays the current cycle for every member who hasn't, then executes the payout.
+fn finish_cycle(env: &Env, client: &AjoContractClient, group_id: u64) {
+    let cycle = client.get_group(&group_id).current_cycle;
+    for (member, paid) in client.get_contribution_status(&group_id, &cycle).iter() {
+        if !paid {
+            client.contribute(&member, &group_id);
+        }
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+}
+
+#[test]
+fn test_next_round_starts_after_last_payout() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(2, 2, 0);
+
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+
+    let group = client.get_group(&group_id);
+    assert!(!group.is_complete);
+    assert_eq!(group.round, 2);
+    assert_eq!(group.round_start_cycle, 3);
+    assert_eq!(group.current_cycle, 3);
+    assert_eq!(group.payout_index, 0);
+
+    // The first recipient is paid again in the new round
+    finish_cycle(&env, &client, group_id);
+    assert_eq!(
+        client.get_payout_record(&group_id, &3u32).member,
+        members.get(0).unwrap()
+    );
+    finish_cycle(&env, &client, group_id);
+    assert!(client.is_complete(&group_id));
+    assert_eq!(
+        client.try_contribute(&members.get(0).unwrap(), &group_id),
+        Err(Ok(AjoError::GroupComplete))
+    );
+}
+
+#[test]
+fn test_round_history_is_kept() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(2, 3, 0);
+
+    assert_eq!(
+        client.try_get_round_record(&group_id, &1u32),
+        Err(Ok(AjoError::RecordNotFound))
+    );
+
+    for _ in 0..4 {
+        finish_cycle(&env, &client, group_id);
+    }
+
+    let first = client.get_round_record(&group_id, &1u32);
+    assert_eq!((first.first_cycle, first.last_cycle), (1, 2));
+    assert_eq!(first.payout_order, members);
+    let second = client.get_round_record(&group_id, &2u32);
+    assert_eq!((second.first_cycle, second.last_cycle), (3, 4));
+    assert_eq!(client.get_group(&group_id).round, 3);
+}
+
+#[test]
+fn test_single_round_by_default() {
+    let (env, client, _token_id, _members, group_id) = setup_test_env(2, 0, 0);
+
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+
+    assert!(client.is_complete(&group_id));
+    assert_eq!(client.get_round_record(&group_id, &1u32).last_cycle, 2);
+}
+
+#[test]
+fn test_collateral_held_until_last_round() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(2, 2, CONTRIBUTION);
+    let member = members.get(1).unwrap();
+
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+    assert_eq!(
+        client.get_member_collateral(&group_id, &member),
+        CONTRIBUTION
+    );
+
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+    assert_eq!(client.get_member_collateral(&group_id, &member), 0);
+}
+
+#[test]
+fn test_cannot_cancel_between_rounds() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(2, 2, 0);
+
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+    assert_eq!(client.get_group(&group_id).payout_index, 0);
+
+    assert_eq!(
+        client.try_cancel_group(&members.get(0).unwrap(), &group_id),
+        Err(Ok(AjoError::CannotCancelAfterPayout))
+    );
+}
+
+#[test]
+fn test_exit_allowed_again_in_new_round() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3, 2, 0);
+    let (creator, paid) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+    assert_eq!(
+        client.try_request_exit(&paid, &group_id),
+        Err(Ok(AjoError::AlreadyReceivedPayout))
+    );
+
+    finish_cycle(&env, &client, group_id);
+    assert_eq!(client.get_group(&group_id).round, 2);
+
+    // Everyone was paid in the first round, so members can leave between rounds
+    client.request_exit(&paid, &group_id);
+    client.approve_exit(&creator, &group_id, &paid);
+    assert_eq!(
+        client.get_group(&group_id).payout_order,
+        vec![&env, creator.clone(), members.get(2).unwrap()]
+    );
+}
+
+#[test]
+fn test_prepayment_spans_rounds() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(2, 2, 0);
+    let member = members.get(1).unwrap();
+
+    client.contribute(&member, &group_id);
+    assert_eq!(client.contribute_ahead(&member, &group_id, &5u32), 3);
+
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+    assert_eq!(client.get_prepaid_cycles(&group_id, &member), 1);
+}
@@ -0,0 +1,214 @@
+#![cfg(test)]
+
+//! Partial contribution tests
+//!
+//! These tests verify `contribute_partial`: installments accumulating towards
+//! a cycle's contribution, how they interact with `contribute`, penalties and
+//! defaults, and that they are refunded when a group is cancelled.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 10;
+
+/// Helper function to create a test environment, a full group and funded members
+fn setup_test_env(
+    member_count: u32,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_id, members, group_id)
+}
+
+fn contributed(client: &AjoContractClient, group_id: u64, member: &Address) -> bool {
+    client
+        .get_contribution_status(&group_id, &client.get_group(&group_id).current_cycle)
+        .iter()
+        .any(|(address, paid)| address == *member && paid)
+}
+
+#[test]
+fn test_installments_accumulate_to_full_contribution() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let token_client = token::Client::new(&env, &token_id);
+    let member = members.get(1).unwrap();
+
+    assert_eq!(
+        client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 4)),
+        CONTRIBUTION / 4
+    );
+    assert_eq!(
+        client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 2)),
+        CONTRIBUTION * 3 / 4
+    );
+    assert!(!contributed(&client, group_id, &member));
+    assert_eq!(
+        client.get_amount_paid(&group_id, &1u32, &member),
+        CONTRIBUTION * 3 / 4
+    );
+
+    client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 4));
+    assert!(contributed(&client, group_id, &member));
+    assert_eq!(
+        client.get_amount_paid(&group_id, &1u32, &member),
+        CONTRIBUTION
+    );
+    assert_eq!(token_client.balance(&member), FUNDING - CONTRIBUTION);
+
+    assert_eq!(
+        client.try_contribute_partial(&member, &group_id, &1i128),
+        Err(Ok(AjoError::AlreadyContributed))
+    );
+}
+
+#[test]
+fn test_overpayment_is_not_taken() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let member = members.get(1).unwrap();
+
+    client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 2));
+    assert_eq!(
+        client.contribute_partial(&member, &group_id, &CONTRIBUTION),
+        CONTRIBUTION
+    );
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&member),
+        FUNDING - CONTRIBUTION
+    );
+}
+
+#[test]
+fn test_contribute_pays_the_remainder() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let member = members.get(1).unwrap();
+
+    client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 4));
+    client.contribute(&member, &group_id);
+
+    assert!(contributed(&client, group_id, &member));
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&member),
+        FUNDING - CONTRIBUTION
+    );
+}
+
+#[test]
+fn test_late_final_installment_pays_penalty() {
+    let (env, client, token_id, members, group_id) = setup_test_env(2);
+    let member = members.get(1).unwrap();
+    let penalty = CONTRIBUTION / 10;
+
+    client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 2));
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + 3600;
+    });
+
+    // Installments that don't complete the contribution aren't penalised
+    client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 4));
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), 0);
+
+    client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 4));
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), penalty);
+    assert!(
+        client
+            .get_contribution_detail(&group_id, &1u32, &member)
+            .is_late
+    );
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&member),
+        FUNDING - CONTRIBUTION - penalty
+    );
+}
+
+#[test]
+fn test_installments_count_towards_default() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(2);
+    let (creator, member) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.contribute(&creator, &group_id);
+    client.contribute_partial(&member, &group_id, &(CONTRIBUTION * 3 / 4));
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+
+    let record = client.mark_default(&group_id, &member);
+    assert_eq!(record.from_installments, CONTRIBUTION * 3 / 4);
+    assert_eq!(record.shortfall, CONTRIBUTION / 4);
+
+    client.execute_payout(&group_id);
+    assert_eq!(
+        client.get_payout_record(&group_id, &1u32).amount,
+        CONTRIBUTION * 2 - CONTRIBUTION / 4
+    );
+}
+
+#[test]
+fn test_cancel_refunds_installments() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(3);
+    let (creator, member) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.contribute_partial(&member, &group_id, &(CONTRIBUTION / 4));
+    client.cancel_group(&creator, &group_id);
+
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &member),
+        CONTRIBUTION / 4
+    );
+    assert_eq!(
+        client.get_refund_record(&group_id, &member).amount,
+        CONTRIBUTION / 4
+    );
+}
+
+#[test]
+fn test_installment_amount_validation() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(2);
+    let member = members.get(1).unwrap();
+
+    assert_eq!(
+        client.try_contribute_partial(&member, &group_id, &0i128),
+        Err(Ok(AjoError::ContributionAmountZero))
+    );
+    assert_eq!(
+        client.try_contribute_partial(&member, &group_id, &-1i128),
+        Err(Ok(AjoError::ContributionAmountNegative))
+    );
+}
@@ -0,0 +1,280 @@
+#![cfg(test)]
+
+//! Payout strategy tests
+//!
+//! These tests verify how each `PayoutStrategy` materializes `payout_order`
+//! and that `execute_payout` follows it.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+
+/// Helper function to create a test environment, contract, token and funded members
+fn setup_test_env(member_count: u32) -> (Env, AjoContractClient<'static>, Address, Vec<Address>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &(CONTRIBUTION * 20));
+        members.push_back(member);
+    }
+
+    (env, client, token_id, members)
+}
+
+/// Creates a group with the given strategy and has every other member join.
+fn create_group(
+    client: &AjoContractClient,
+    token_id: &Address,
+    members: &Vec<Address>,
+    max_members: u32,
+    strategy: PayoutStrategy,
+) -> u64 {
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &max_members,
+        &GRACE_PERIOD,
+        &5u32,
+        &strategy,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+    group_id
+}
+
+/// Has every member contribute, waits out the grace period and executes the payout.
+/// Returns the address credited with the payout.
+fn run_cycle(env: &Env, client: &AjoContractClient, group_id: u64) -> Address {
+    let group = client.get_group(&group_id);
+    for member in group.members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+
+    client.execute_payout(&group_id);
+
+    let record = client.get_payout_record(&group_id, &group.current_cycle);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &record.member),
+        CONTRIBUTION * group.members.len() as i128
+    );
+    client.claim(&record.member, &group_id);
+    record.member
+}
+
+fn is_permutation(order: &Vec<Address>, members: &Vec<Address>) -> bool {
+    order.len() == members.len()
+        && members
+            .iter()
+            .all(|m| order.first_index_of(&m) == order.last_index_of(&m))
+        && members.iter().all(|m| order.contains(&m))
+}
+
+#[test]
+fn test_fixed_strategy_pays_in_join_order() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group(&client, &token_id, &members, 3, PayoutStrategy::Fixed);
+
+    let status = client.get_group_status(&group_id);
+    assert_eq!(status.payout_strategy, PayoutStrategy::Fixed);
+    assert_eq!(status.payout_order, members);
+
+    for expected in members.iter() {
+        assert_eq!(run_cycle(&env, &client, group_id), expected);
+    }
+    assert!(client.is_complete(&group_id));
+}
+
+#[test]
+fn test_randomized_strategy_shuffles_when_full() {
+    let (env, client, token_id, members) = setup_test_env(5);
+    let group_id = create_group(&client, &token_id, &members, 5, PayoutStrategy::Randomized);
+
+    let group = client.get_group(&group_id);
+    assert!(is_permutation(&group.payout_order, &members));
+    assert_eq!(group.members, members);
+
+    // Payouts follow the materialized order
+    for expected in group.payout_order.iter() {
+        assert_eq!(run_cycle(&env, &client, group_id), expected);
+    }
+}
+
+#[test]
+fn test_randomized_strategy_not_shuffled_before_full() {
+    let (_env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group(&client, &token_id, &members, 5, PayoutStrategy::Randomized);
+
+    // Still forming, so the order remains join order
+    let status = client.get_group_status(&group_id);
+    assert_eq!(status.payout_order, members);
+}
+
+#[test]
+fn test_randomized_strategy_shuffles_on_manual_start() {
+    let (env, client, token_id, members) = setup_test_env(4);
+    let group_id = create_group(&client, &token_id, &members, 10, PayoutStrategy::Randomized);
+
+    client.start_group(&members.get(0).unwrap(), &group_id);
+
+    let group = client.get_group(&group_id);
+    assert!(is_permutation(&group.payout_order, &members));
+    assert_eq!(
+        run_cycle(&env, &client, group_id),
+        group.payout_order.get(0).unwrap()
+    );
+}
+
+#[test]
+fn test_need_based_strategy_uses_creator_order() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group(&client, &token_id, &members, 3, PayoutStrategy::NeedBased);
+
+    let creator = members.get(0).unwrap();
+    let order = vec![
+        &env,
+        members.get(2).unwrap(),
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+    ];
+    client.set_payout_order(&creator, &group_id, &order);
+
+    assert_eq!(client.get_group_status(&group_id).payout_order, order);
+    assert_eq!(
+        client.get_group_status(&group_id).next_recipient,
+        members.get(2).unwrap()
+    );
+
+    for expected in order.iter() {
+        assert_eq!(run_cycle(&env, &client, group_id), expected);
+    }
+}
+
+#[test]
+fn test_need_based_order_appends_later_joiners() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let creator = members.get(0).unwrap();
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &3u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::NeedBased,
+        &GroupSettings::default(),
+    );
+    client.join_group(&members.get(1).unwrap(), &group_id);
+
+    let order = vec![&env, members.get(1).unwrap(), creator.clone()];
+    client.set_payout_order(&creator, &group_id, &order);
+
+    client.join_group(&members.get(2).unwrap(), &group_id);
+
+    let mut expected = order.clone();
+    expected.push_back(members.get(2).unwrap());
+    assert_eq!(client.get_group(&group_id).payout_order, expected);
+}
+
+#[test]
+fn test_set_payout_order_locked_after_contribution() {
+    let (env, client, token_id, members) = setup_test_env(2);
+    let group_id = create_group(&client, &token_id, &members, 2, PayoutStrategy::NeedBased);
+
+    let creator = members.get(0).unwrap();
+    client.contribute(&members.get(1).unwrap(), &group_id);
+
+    let order = vec![&env, members.get(1).unwrap(), creator.clone()];
+    let result = client.try_set_payout_order(&creator, &group_id, &order);
+    assert_eq!(result, Err(Ok(AjoError::PayoutOrderLocked)));
+}
+
+#[test]
+fn test_set_payout_order_rejects_invalid_orders() {
+    let (env, client, token_id, members) = setup_test_env(3);
+    let group_id = create_group(&client, &token_id, &members, 3, PayoutStrategy::NeedBased);
+    let creator = members.get(0).unwrap();
+
+    // Missing a member
+    let short = vec![&env, members.get(0).unwrap(), members.get(1).unwrap()];
+    assert_eq!(
+        client.try_set_payout_order(&creator, &group_id, &short),
+        Err(Ok(AjoError::InvalidPayoutOrder))
+    );
+
+    // Duplicate member
+    let duplicate = vec![
+        &env,
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(1).unwrap(),
+    ];
+    assert_eq!(
+        client.try_set_payout_order(&creator, &group_id, &duplicate),
+        Err(Ok(AjoError::InvalidPayoutOrder))
+    );
+
+    // Outsider
+    let outsider = vec![
+        &env,
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        Address::generate(&env),
+    ];
+    assert_eq!(
+        client.try_set_payout_order(&creator, &group_id, &outsider),
+        Err(Ok(AjoError::InvalidPayoutOrder))
+    );
+}
+
+#[test]
+fn test_set_payout_order_requires_creator_and_need_based() {
+    let (env, client, token_id, members) = setup_test_env(2);
+    let need_based = create_group(&client, &token_id, &members, 2, PayoutStrategy::NeedBased);
+    let order = vec![&env, members.get(1).unwrap(), members.get(0).unwrap()];
+
+    assert_eq!(
+        client.try_set_payout_order(&members.get(1).unwrap(), &need_based, &order),
+        Err(Ok(AjoError::Unauthorized))
+    );
+
+    let fixed = create_group(&client, &token_id, &members, 2, PayoutStrategy::Fixed);
+    assert_eq!(
+        client.try_set_payout_order(&members.get(0).unwrap(), &fixed, &order),
+        Err(Ok(AjoError::InvalidPayoutStrategy))
+    );
+}
+
+#[test]
+fn test_bid_based_strategy_falls_back_to_payout_order() {
+    let (env, client, token_id, members) = setup_test_env(2);
+    let group_id = create_group(&client, &token_id, &members, 2, PayoutStrategy::BidBased);
+
+    assert_eq!(run_cycle(&env, &client, group_id), members.get(0).unwrap());
+    assert_eq!(run_cycle(&env, &client, group_id), members.get(1).unwrap());
+}
@@ -1,27 +1,47 @@
 #![cfg(test)]
 
-use soroban_ajo::{AjoContract, AjoContractClient};
-use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env};
-
-/// Helper function to create a test environment and contract
-fn setup_test_env() -> (Env, AjoContractClient<'static>, Address, Address, Address) {
+use soroban_ajo::{AjoContract, AjoContractClient, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+/// Helper function to create a test environment, contract and funded token
+fn setup_test_env() -> (
+    Env,
+    AjoContractClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register_contract(None, AjoContract);
     let client = AjoContractClient::new(&env, &contract_id);
 
+    // Register a token contract
+    let token_admin = Address::generate(&env);
+    let token_id = env.register_stellar_asset_contract_v2(token_admin).address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
     // Generate test addresses
     let creator = Address::generate(&env);
     let member2 = Address::generate(&env);
     let member3 = Address::generate(&env);
 
-    (env, client, creator, member2, member3)
+    // Fund members so they can cover contributions and penalties
+    for member in [&creator, &member2, &member3] {
+        token_admin_client.mint(member, &10_000_000_000i128);
+    }
+
+    (env, client, token_id, creator, member2, member3)
 }
 
 #[test]
 fn test_create_group_with_grace_period_and_penalty() {
-    let (_env, client, creator, _, _) = setup_test_env();
+    let (_env, client, token_id, creator, _, _) = setup_test_env();
 
     let contribution = 100_000_000i128; // 10 XLM
     let cycle_duration = 604_800u64; // 1 week
@@ -31,11 +51,14 @@ fn test_create_group_with_grace_period_and_penalty() {
 
     let group_id = client.create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
 
     assert_eq!(group_id, 1);
@@ -48,7 +71,7 @@ fn test_create_group_with_grace_period_and_penalty() {
 
 #[test]
 fn test_on_time_contribution() {
-    let (env, client, creator, member2, _) = setup_test_env();
+    let (_env, client, token_id, creator, member2, _) = setup_test_env();
 
     let contribution = 100_000_000i128;
     let cycle_duration = 604_800u64;
@@ -58,15 +81,19 @@ fn test_on_time_contribution() {
 
     let group_id = client.create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
 
     // Join group
     client.join_group(&member2, &group_id);
+    client.start_group(&creator, &group_id);
 
     // Contribute on time (within cycle window)
     client.contribute(&creator, &group_id);
@@ -86,7 +113,7 @@ fn test_on_time_contribution() {
 
 #[test]
 fn test_late_contribution_with_penalty() {
-    let (env, client, creator, member2, _) = setup_test_env();
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
 
     let contribution = 100_000_000i128; // 10 XLM
     let cycle_duration = 604_800u64; // 1 week
@@ -96,15 +123,19 @@ fn test_late_contribution_with_penalty() {
 
     let group_id = client.create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
 
     // Join group
     client.join_group(&member2, &group_id);
+    client.start_group(&creator, &group_id);
 
     // Creator contributes on time
     client.contribute(&creator, &group_id);
@@ -139,7 +170,7 @@ fn test_late_contribution_with_penalty() {
 
 #[test]
 fn test_contribution_after_grace_period_fails() {
-    let (env, client, creator, member2, _) = setup_test_env();
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
 
     let contribution = 100_000_000i128;
     let cycle_duration = 604_800u64;
@@ -149,14 +180,18 @@ fn test_contribution_after_grace_period_fails() {
 
     let group_id = client.create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
 
     client.join_group(&member2, &group_id);
+    client.start_group(&creator, &group_id);
 
     // Creator contributes on time
     client.contribute(&creator, &group_id);
@@ -174,7 +209,7 @@ fn test_contribution_after_grace_period_fails() {
 
 #[test]
 fn test_payout_includes_penalties() {
-    let (env, client, creator, member2, _) = setup_test_env();
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
 
     let contribution = 100_000_000i128; // 10 XLM
     let cycle_duration = 604_800u64;
@@ -184,11 +219,14 @@ fn test_payout_includes_penalties() {
 
     let group_id = client.create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
 
     client.join_group(&member2, &group_id);
@@ -220,7 +258,7 @@ fn test_payout_includes_penalties() {
 
 #[test]
 fn test_payout_delayed_during_grace_period() {
-    let (env, client, creator, member2, _) = setup_test_env();
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
 
     let contribution = 100_000_000i128;
     let cycle_duration = 604_800u64;
@@ -230,11 +268,14 @@ fn test_payout_delayed_during_grace_period() {
 
     let group_id = client.create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
 
     client.join_group(&member2, &group_id);
@@ -256,7 +297,7 @@ fn test_payout_delayed_during_grace_period() {
 
 #[test]
 fn test_reliability_score_calculation() {
-    let (env, client, creator, member2, member3) = setup_test_env();
+    let (env, client, token_id, creator, member2, member3) = setup_test_env();
 
     let contribution = 100_000_000i128;
     let cycle_duration = 604_800u64;
@@ -266,11 +307,14 @@ fn test_reliability_score_calculation() {
 
     let group_id = client.create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
 
     client.join_group(&member2, &group_id);
@@ -308,7 +352,7 @@ fn test_reliability_score_calculation() {
 
 #[test]
 fn test_group_status_includes_penalty_info() {
-    let (env, client, creator, member2, _) = setup_test_env();
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
 
     let contribution = 100_000_000i128;
     let cycle_duration = 604_800u64;
@@ -318,11 +362,14 @@ fn test_group_status_includes_penalty_info() {
 
     let group_id = client.create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
 
     client.join_group(&member2, &group_id);
@@ -345,7 +392,7 @@ fn test_group_status_includes_penalty_info() {
 
 #[test]
 fn test_invalid_penalty_rate() {
-    let (_env, client, creator, _, _) = setup_test_env();
+    let (_env, client, token_id, creator, _, _) = setup_test_env();
 
     let contribution = 100_000_000i128;
     let cycle_duration = 604_800u64;
@@ -355,11 +402,14 @@ fn test_invalid_penalty_rate() {
 
     let result = client.try_create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
     
     assert!(result.is_err());
@@ -368,7 +418,7 @@ fn test_invalid_penalty_rate() {
 
 #[test]
 fn test_invalid_grace_period() {
-    let (_env, client, creator, _, _) = setup_test_env();
+    let (_env, client, token_id, creator, _, _) = setup_test_env();
 
     let contribution = 100_000_000i128;
     let cycle_duration = 604_800u64;
@@ -378,13 +428,170 @@ fn test_invalid_grace_period() {
 
     let result = client.try_create_group(
         &creator,
+        &token_id,
         &contribution,
         &cycle_duration,
         &max_members,
         &grace_period,
         &penalty_rate,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
     );
     
     assert!(result.is_err());
     assert_eq!(result, Err(Ok(soroban_ajo::AjoError::InvalidGracePeriod)));
 }
+
+#[test]
+fn test_late_contribution_transfers_penalty() {
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
+    let token_client = token::Client::new(&env, &token_id);
+
+    let contribution = 100_000_000i128;
+    let cycle_duration = 604_800u64;
+    let grace_period = 86400u64;
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &contribution,
+        &cycle_duration,
+        &2u32,
+        &grace_period,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&member2, &group_id);
+
+    // On-time contribution only transfers the contribution amount
+    let creator_before = token_client.balance(&creator);
+    client.contribute(&creator, &group_id);
+    assert_eq!(token_client.balance(&creator), creator_before - contribution);
+
+    // Late contribution transfers contribution plus 10% penalty
+    env.ledger().with_mut(|li| {
+        li.timestamp = li.timestamp + cycle_duration + 3600;
+    });
+    let member2_before = token_client.balance(&member2);
+    client.contribute(&member2, &group_id);
+    let expected_penalty = 10_000_000i128;
+    assert_eq!(
+        token_client.balance(&member2),
+        member2_before - contribution - expected_penalty
+    );
+    assert_eq!(
+        client.get_contract_balance(&token_id),
+        contribution * 2 + expected_penalty
+    );
+
+    // Recipient receives the full pool plus the penalty bonus
+    env.ledger().with_mut(|li| {
+        li.timestamp = li.timestamp + grace_period;
+    });
+    let recipient_before = token_client.balance(&creator);
+    client.execute_payout(&group_id);
+    client.claim(&creator, &group_id);
+    assert_eq!(
+        token_client.balance(&creator),
+        recipient_before + contribution * 2 + expected_penalty
+    );
+    assert_eq!(client.get_contract_balance(&token_id), 0);
+}
+
+#[test]
+fn test_contribution_at_cycle_end_is_on_time() {
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
+
+    let cycle_duration = 604_800u64;
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &100_000_000i128,
+        &cycle_duration,
+        &2u32,
+        &86400u64,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&member2, &group_id);
+
+    // Exactly at the cycle end boundary
+    env.ledger().with_mut(|li| {
+        li.timestamp = li.timestamp + cycle_duration;
+    });
+    client.contribute(&creator, &group_id);
+
+    let detail = client.get_contribution_detail(&group_id, &1u32, &creator);
+    assert!(!detail.is_late);
+    assert_eq!(detail.penalty_amount, 0);
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), 0);
+}
+
+#[test]
+fn test_contribution_at_grace_period_end_is_late() {
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
+
+    let cycle_duration = 604_800u64;
+    let grace_period = 86400u64;
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &100_000_000i128,
+        &cycle_duration,
+        &2u32,
+        &grace_period,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&member2, &group_id);
+
+    // Last second of the grace period is still accepted, with a penalty
+    env.ledger().with_mut(|li| {
+        li.timestamp = li.timestamp + cycle_duration + grace_period;
+    });
+    client.contribute(&member2, &group_id);
+
+    let detail = client.get_contribution_detail(&group_id, &1u32, &member2);
+    assert!(detail.is_late);
+    assert_eq!(detail.penalty_amount, 5_000_000i128);
+
+    let record = client.get_member_penalty_record(&group_id, &member2);
+    assert_eq!(record.late_count, 1);
+    assert_eq!(record.total_penalties, 5_000_000i128);
+}
+
+#[test]
+fn test_zero_penalty_rate_late_contribution() {
+    let (env, client, token_id, creator, member2, _) = setup_test_env();
+
+    let cycle_duration = 604_800u64;
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &100_000_000i128,
+        &cycle_duration,
+        &2u32,
+        &86400u64,
+        &0u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    client.join_group(&member2, &group_id);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = li.timestamp + cycle_duration + 3600;
+    });
+    client.contribute(&member2, &group_id);
+
+    // Still counted as late for reliability, but no penalty charged
+    let record = client.get_member_penalty_record(&group_id, &member2);
+    assert_eq!(record.late_count, 1);
+    assert_eq!(record.total_penalties, 0);
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), 0);
+}
@@ -0,0 +1,218 @@
+#![cfg(test)]
+
+//! Prepayment tests
+//!
+//! These tests verify `contribute_ahead`: prepaid cycles being recorded as
+//! contributions when each cycle starts, capping to the cycles a group has
+//! left, and unused prepayments being refunded.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 10;
+
+/// Helper function to create a test environment, a group of `max_members`
+/// joined by `member_count` funded members
+fn setup_test_env(
+    member_count: u32,
+    max_members: u32,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &max_members,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_id, members, group_id)
+}
+
+fn contributed(client: &AjoContractClient, group_id: u64, member: &Address) -> bool {
+    client
+        .get_contribution_status(&group_id, &client.get_group(&group_id).current_cycle)
+        .iter()
+        .any(|(address, paid)| address == *member && paid)
+}
+
+/// Pays the current cycle for every member who hasn't, then executes the payout.
+fn finish_cycle(env: &Env, client: &AjoContractClient, group_id: u64) {
+    for member in client.get_group(&group_id).members.iter() {
+        if !contributed(client, group_id, &member) {
+            client.contribute(&member, &group_id);
+        }
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+}
+
+#[test]
+fn test_prepaid_cycles_recorded_when_cycles_start() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3, 3);
+    let member = members.get(1).unwrap();
+
+    client.contribute(&member, &group_id);
+    assert_eq!(client.contribute_ahead(&member, &group_id, &2u32), 2);
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&member),
+        FUNDING - CONTRIBUTION * 3
+    );
+    assert_eq!(client.get_prepaid_cycles(&group_id, &member), 2);
+
+    finish_cycle(&env, &client, group_id);
+    assert!(contributed(&client, group_id, &member));
+    assert_eq!(client.get_prepaid_cycles(&group_id, &member), 1);
+
+    let detail = client.get_contribution_detail(&group_id, &2u32, &member);
+    assert!(!detail.is_late);
+    assert_eq!(detail.amount, CONTRIBUTION);
+    assert_eq!(
+        client.try_contribute(&member, &group_id),
+        Err(Ok(AjoError::AlreadyContributed))
+    );
+
+    finish_cycle(&env, &client, group_id);
+    assert!(contributed(&client, group_id, &member));
+    assert_eq!(client.get_prepaid_cycles(&group_id, &member), 0);
+}
+
+#[test]
+fn test_prepaying_a_forming_group_covers_the_first_cycle() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(2, 3);
+    let (creator, member) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.contribute_ahead(&member, &group_id, &1u32);
+    client.start_group(&creator, &group_id);
+
+    assert!(contributed(&client, group_id, &member));
+    assert!(!contributed(&client, group_id, &creator));
+    assert_eq!(client.get_prepaid_cycles(&group_id, &member), 0);
+}
+
+#[test]
+fn test_prepayment_capped_at_remaining_cycles() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3, 3);
+    let member = members.get(1).unwrap();
+
+    assert_eq!(client.contribute_ahead(&member, &group_id, &1u32), 1);
+    assert_eq!(client.contribute_ahead(&member, &group_id, &5u32), 1);
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&member),
+        FUNDING - CONTRIBUTION * 2
+    );
+
+    assert_eq!(
+        client.try_contribute_ahead(&member, &group_id, &1u32),
+        Err(Ok(AjoError::AlreadyContributed))
+    );
+}
+
+#[test]
+fn test_cancel_refunds_prepayments() {
+    let (env, client, token_id, members, group_id) = setup_test_env(3, 3);
+    let (creator, member) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.contribute(&member, &group_id);
+    client.contribute_ahead(&member, &group_id, &2u32);
+    client.cancel_group(&creator, &group_id);
+
+    assert_eq!(client.get_prepaid_cycles(&group_id, &member), 0);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &member),
+        CONTRIBUTION * 3
+    );
+    client.claim(&member, &group_id);
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&member),
+        FUNDING
+    );
+}
+
+#[test]
+fn test_leaving_refunds_prepayments() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(2, 3);
+    let member = members.get(1).unwrap();
+
+    client.contribute_ahead(&member, &group_id, &2u32);
+    client.leave_group(&member, &group_id);
+
+    assert_eq!(client.get_prepaid_cycles(&group_id, &member), 0);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &member),
+        CONTRIBUTION * 2
+    );
+}
+
+#[test]
+fn test_unused_prepayments_refunded_on_completion() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(3, 3);
+    let (creator, member, leaver) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    client.contribute_ahead(&member, &group_id, &2u32);
+
+    // A member leaves the rotation, so the last prepaid cycle is never reached
+    client.request_exit(&leaver, &group_id);
+    client.approve_exit(&creator, &group_id, &leaver);
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+
+    assert!(client.is_complete(&group_id));
+    assert_eq!(client.get_prepaid_cycles(&group_id, &member), 0);
+    // The cycle 2 payout plus the unused prepaid cycle
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &member),
+        CONTRIBUTION * 3
+    );
+}
+
+#[test]
+fn test_contribute_ahead_validation() {
+    let (env, client, _token_id, members, group_id) = setup_test_env(2, 2);
+
+    assert_eq!(
+        client.try_contribute_ahead(&members.get(1).unwrap(), &group_id, &0u32),
+        Err(Ok(AjoError::ContributionAmountZero))
+    );
+    assert_eq!(
+        client.try_contribute_ahead(&Address::generate(&env), &group_id, &1u32),
+        Err(Ok(AjoError::NotMember))
+    );
+}
@@ -0,0 +1,436 @@
+#![cfg(test)]
+
+//! Governance proposal tests
+//!
+//! These tests verify proposing changes to a running group, voting on them,
+//! and executing them: amending the group's rules, reordering payouts,
+//! removing a member and replacing the creator, each with its own approval
+//! threshold.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, DataKey, GovernanceSettings, GroupSettings,
+    PayoutStrategy, ProposalAction, VoteChoice,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const VOTING_PERIOD: u64 = 604_800;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment with a full, started group
+/// of `member_count` funded members using `governance`
+fn setup_test_env(
+    member_count: u32,
+    governance: GovernanceSettings,
+) -> (Env, AjoContractClient<'static>, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            governance,
+            ..Default::default()
+        },
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, members, group_id)
+}
+
+/// Every member in `voters` votes `choice`, then voting ends.
+fn vote_and_close(
+    env: &Env,
+    client: &AjoContractClient,
+    group_id: u64,
+    proposal_id: u64,
+    voters: &Vec<Address>,
+    choice: VoteChoice,
+) {
+    for voter in voters.iter() {
+        client.vote(&voter, &group_id, &proposal_id, &choice);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += VOTING_PERIOD + 1;
+    });
+}
+
+#[test]
+fn test_amend_rules_by_vote() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let proposer = members.get(1).unwrap();
+
+    let grace = client.propose(&proposer, &group_id, &ProposalAction::SetGracePeriod(3_600));
+    let penalty = client.propose(&proposer, &group_id, &ProposalAction::SetPenaltyRate(20));
+    let cycle = client.propose(
+        &proposer,
+        &group_id,
+        &ProposalAction::SetCycleDuration(1_209_600),
+    );
+    assert_eq!((grace, penalty, cycle), (1, 2, 3));
+
+    for proposal_id in [grace, penalty, cycle] {
+        for voter in members.iter() {
+            client.vote(&voter, &group_id, &proposal_id, &VoteChoice::For);
+        }
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += VOTING_PERIOD + 1;
+    });
+    for proposal_id in [grace, penalty, cycle] {
+        assert!(client.execute_proposal(&proposer, &group_id, &proposal_id));
+    }
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.grace_period, 3_600);
+    assert_eq!(group.penalty_rate, 20);
+    assert_eq!(group.cycle_duration, 1_209_600);
+
+    let proposal = client.get_proposal(&group_id, &grace);
+    assert!(proposal.executed);
+    assert!(proposal.approved);
+    assert_eq!(proposal.votes_for, 3);
+}
+
+#[test]
+fn test_rejected_proposal_changes_nothing() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let proposer = members.get(1).unwrap();
+
+    let proposal_id = client.propose(&proposer, &group_id, &ProposalAction::SetPenaltyRate(50));
+    client.vote(&proposer, &group_id, &proposal_id, &VoteChoice::For);
+    vote_and_close(
+        &env,
+        &client,
+        group_id,
+        proposal_id,
+        &members.slice(2..3),
+        VoteChoice::Against,
+    );
+
+    assert!(!client.execute_proposal(&proposer, &group_id, &proposal_id));
+    assert_eq!(client.get_group(&group_id).penalty_rate, 10);
+
+    let proposal = client.get_proposal(&group_id, &proposal_id);
+    assert!(proposal.executed);
+    assert!(!proposal.approved);
+
+    let result = client.try_execute_proposal(&proposer, &group_id, &proposal_id);
+    assert_eq!(result, Err(Ok(AjoError::RefundAlreadyExecuted)));
+}
+
+#[test]
+fn test_vote_rules() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let voter = members.get(1).unwrap();
+    let proposal_id = client.propose(&voter, &group_id, &ProposalAction::SetPenaltyRate(20));
+
+    // Votes can change until the deadline, but not repeat
+    client.vote(&voter, &group_id, &proposal_id, &VoteChoice::Against);
+    client.vote(&voter, &group_id, &proposal_id, &VoteChoice::Abstain);
+    let proposal = client.get_proposal(&group_id, &proposal_id);
+    assert_eq!(proposal.votes_against, 0);
+    assert_eq!(proposal.votes_abstain, 1);
+
+    let result = client.try_vote(&voter, &group_id, &proposal_id, &VoteChoice::Abstain);
+    assert_eq!(result, Err(Ok(AjoError::AlreadyVoted)));
+
+    let outsider = Address::generate(&env);
+    let result = client.try_vote(&outsider, &group_id, &proposal_id, &VoteChoice::For);
+    assert_eq!(result, Err(Ok(AjoError::NotMember)));
+
+    let result = client.try_vote(&voter, &group_id, &99, &VoteChoice::For);
+    assert_eq!(result, Err(Ok(AjoError::RecordNotFound)));
+
+    let result = client.try_execute_proposal(&voter, &group_id, &proposal_id);
+    assert_eq!(result, Err(Ok(AjoError::VotingPeriodActive)));
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += VOTING_PERIOD + 1;
+    });
+    let result = client.try_vote(&voter, &group_id, &proposal_id, &VoteChoice::For);
+    assert_eq!(result, Err(Ok(AjoError::VotingPeriodEnded)));
+}
+
+#[test]
+fn test_propose_validates_action() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let (creator, proposer) = (members.get(0).unwrap(), members.get(1).unwrap());
+    let outsider = Address::generate(&env);
+
+    let cases = [
+        (
+            ProposalAction::SetGracePeriod(8 * 86_400),
+            AjoError::InvalidGracePeriod,
+        ),
+        (
+            ProposalAction::SetPenaltyRate(101),
+            AjoError::InvalidPenaltyRate,
+        ),
+        (
+            ProposalAction::SetCycleDuration(0),
+            AjoError::CycleDurationZero,
+        ),
+        (
+            ProposalAction::SetCycleDuration(60),
+            AjoError::CycleDurationZero,
+        ),
+        (
+            ProposalAction::SetCycleDuration(366 * 86_400),
+            AjoError::CycleDurationZero,
+        ),
+        (
+            ProposalAction::SetPayoutOrder(vec![&env, creator.clone()]),
+            AjoError::InvalidPayoutOrder,
+        ),
+        (
+            ProposalAction::RemoveMember(outsider.clone()),
+            AjoError::NotMember,
+        ),
+        (
+            ProposalAction::RemoveMember(creator.clone()),
+            AjoError::Unauthorized,
+        ),
+        (
+            ProposalAction::ReplaceCreator(outsider),
+            AjoError::NotMember,
+        ),
+    ];
+    for (action, error) in cases {
+        let result = client.try_propose(&proposer, &group_id, &action);
+        assert_eq!(result, Err(Ok(error)));
+    }
+
+    let result = client.try_propose(
+        &Address::generate(&env),
+        &group_id,
+        &ProposalAction::SetPenaltyRate(20),
+    );
+    assert_eq!(result, Err(Ok(AjoError::NotMember)));
+}
+
+#[test]
+fn test_reorder_unpaid_slots() {
+    let (env, client, members, group_id) = setup_test_env(3, GovernanceSettings::default());
+    let (first, second, third) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    // Pay out the first slot, then swap the two remaining ones
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+
+    let order = vec![&env, third.clone(), second.clone()];
+    let proposal_id = client.propose(&second, &group_id, &ProposalAction::SetPayoutOrder(order));
+    vote_and_close(
+        &env,
+        &client,
+        group_id,
+        proposal_id,
+        &members,
+        VoteChoice::For,
+    );
+    client.execute_proposal(&second, &group_id, &proposal_id);
+
+    assert_eq!(
+        client.get_group(&group_id).payout_order,
+        vec![&env, first, third, second]
+    );
+}
+
+#[test]
+fn test_remove_member_needs_supermajority() {
+    let (env, client, members, group_id) = setup_test_env(4, GovernanceSettings::default());
+    let proposer = members.get(0).unwrap();
+    let removed = members.get(3).unwrap();
+    let action = ProposalAction::RemoveMember(removed.clone());
+    client.contribute(&removed, &group_id);
+
+    // Two thirds in favour falls just short of the default 67% for removals
+    let proposal_id = client.propose(&proposer, &group_id, &action);
+    client.vote(
+        &members.get(2).unwrap(),
+        &group_id,
+        &proposal_id,
+        &VoteChoice::Against,
+    );
+    vote_and_close(
+        &env,
+        &client,
+        group_id,
+        proposal_id,
+        &members.slice(0..2),
+        VoteChoice::For,
+    );
+    assert!(!client.execute_proposal(&proposer, &group_id, &proposal_id));
+    assert!(client.get_group(&group_id).members.contains(&removed));
+
+    let proposal_id = client.propose(&proposer, &group_id, &action);
+    vote_and_close(
+        &env,
+        &client,
+        group_id,
+        proposal_id,
+        &members.slice(0..3),
+        VoteChoice::For,
+    );
+    assert!(client.execute_proposal(&proposer, &group_id, &proposal_id));
+
+    // The member paid this cycle, so that contribution is credited back
+    let group = client.get_group(&group_id);
+    assert_eq!(group.members.len(), 3);
+    assert!(!group.payout_order.contains(&removed));
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &removed),
+        CONTRIBUTION
+    );
+}
+
+#[test]
+fn test_stale_proposal_closes_as_failed() {
+    let (env, client, members, group_id) = setup_test_env(4, GovernanceSettings::default());
+    let proposer = members.get(0).unwrap();
+    let removed = members.get(3).unwrap();
+    let action = ProposalAction::RemoveMember(removed.clone());
+
+    // Two proposals to remove the same member; the member votes on the second
+    let first = client.propose(&proposer, &group_id, &action);
+    let second = client.propose(&proposer, &group_id, &action);
+    client.vote(&removed, &group_id, &second, &VoteChoice::Against);
+    for proposal_id in [first, second] {
+        for voter in members.slice(0..3).iter() {
+            client.vote(&voter, &group_id, &proposal_id, &VoteChoice::For);
+        }
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += VOTING_PERIOD + 1;
+    });
+    assert!(client.execute_proposal(&proposer, &group_id, &first));
+
+    // The second passed, but the member has already left
+    assert!(!client.execute_proposal(&proposer, &group_id, &second));
+    let proposal = client.get_proposal(&group_id, &second);
+    assert!(proposal.executed);
+    assert!(!proposal.approved);
+    assert_eq!(proposal.votes_for, 3);
+    assert_eq!(proposal.votes_against, 1);
+
+    // The removed member's vote is cleared along with everyone else's
+    let vote_left = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .has(&DataKey::ProposalVote(group_id, second, removed.clone()))
+    });
+    assert!(!vote_left);
+}
+
+#[test]
+fn test_replace_creator_with_custom_threshold() {
+    let governance = GovernanceSettings {
+        creator_threshold: 100,
+        ..Default::default()
+    };
+    let (env, client, members, group_id) = setup_test_env(3, governance);
+    let new_creator = members.get(1).unwrap();
+    let action = ProposalAction::ReplaceCreator(new_creator.clone());
+
+    let proposal_id = client.propose(&new_creator, &group_id, &action);
+    client.vote(
+        &members.get(0).unwrap(),
+        &group_id,
+        &proposal_id,
+        &VoteChoice::Against,
+    );
+    vote_and_close(
+        &env,
+        &client,
+        group_id,
+        proposal_id,
+        &members.slice(1..3),
+        VoteChoice::For,
+    );
+    assert!(!client.execute_proposal(&new_creator, &group_id, &proposal_id));
+
+    let proposal_id = client.propose(&new_creator, &group_id, &action);
+    vote_and_close(
+        &env,
+        &client,
+        group_id,
+        proposal_id,
+        &members,
+        VoteChoice::For,
+    );
+    assert!(client.execute_proposal(&new_creator, &group_id, &proposal_id));
+    assert_eq!(client.get_group(&group_id).creator, new_creator);
+
+    let proposals = client.list_proposals(&group_id);
+    assert_eq!(proposals.len(), 2);
+    assert!(!proposals.get(0).unwrap().approved);
+    assert!(proposals.get(1).unwrap().approved);
+}
+
+#[test]
+fn test_forming_group_cannot_be_governed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+    let creator = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &3u32,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+
+    let result = client.try_propose(&creator, &group_id, &ProposalAction::SetPenaltyRate(20));
+    assert_eq!(result, Err(Ok(AjoError::InvalidGroupState)));
+    assert!(client.list_proposals(&group_id).is_empty());
+}
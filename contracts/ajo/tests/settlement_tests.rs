@@ -0,0 +1,223 @@
+#![cfg(test)]
+
+//! Settlement tests
+//!
+//! These tests verify that refunding a group settles each member's net
+//! position across every cycle: contributions and penalties paid in, minus
+//! payouts received, shared out of what is left in the group's escrow.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, DataKey, GroupSettings, MemberPosition, PayoutStrategy,
+    VoteChoice,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment with an admin and a full
+/// group of `member_count` funded members
+fn setup_test_env(
+    member_count: u32,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, admin, members, group_id)
+}
+
+/// Every member pays the current cycle, then the payout is executed.
+fn finish_cycle(env: &Env, client: &AjoContractClient, group_id: u64) {
+    for member in client.get_group(&group_id).members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    end_cycle(env);
+    client.execute_payout(&group_id);
+}
+
+/// Moves the ledger past the current cycle's grace period.
+fn end_cycle(env: &Env) {
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+}
+
+#[test]
+fn test_position_tracks_payments_and_payouts() {
+    let (env, client, _admin, members, group_id) = setup_test_env(2);
+    let recipient = members.get(0).unwrap();
+    let other = members.get(1).unwrap();
+
+    finish_cycle(&env, &client, group_id);
+
+    assert_eq!(
+        client.get_member_position(&group_id, &recipient),
+        MemberPosition {
+            contributed: CONTRIBUTION,
+            penalties: 0,
+            received: CONTRIBUTION * 2,
+        }
+    );
+    let position = client.get_member_position(&group_id, &other);
+    assert_eq!(position.contributed, CONTRIBUTION);
+    assert_eq!(position.net(), CONTRIBUTION);
+}
+
+#[test]
+fn test_emergency_refund_settles_every_cycle() {
+    let (env, client, admin, members, group_id) = setup_test_env(4);
+
+    // Two pots paid out, then a third cycle fully paid in
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+
+    client.emergency_refund(&admin, &group_id);
+
+    // Members who were paid a pot owe more than they paid in
+    for member in members.slice(0..2).iter() {
+        let record = client.get_refund_record(&group_id, &member);
+        assert_eq!(record.amount, 0);
+        assert_eq!(record.contributed, CONTRIBUTION * 3);
+        assert_eq!(record.received, CONTRIBUTION * 4);
+    }
+
+    // The rest of the escrow is shared by the members never paid, rather
+    // than only returning the current cycle's contribution
+    for member in members.slice(2..4).iter() {
+        let record = client.get_refund_record(&group_id, &member);
+        assert_eq!(record.contributed, CONTRIBUTION * 3);
+        assert_eq!(record.received, 0);
+        assert_eq!(record.amount, CONTRIBUTION * 2);
+        assert_eq!(client.claim(&member, &group_id), CONTRIBUTION * 2);
+    }
+
+    assert_eq!(client.get_group_escrow(&group_id).available(), 0);
+}
+
+#[test]
+fn test_refund_includes_penalties_paid() {
+    let (env, client, _admin, members, group_id) = setup_test_env(2);
+    let creator = members.get(0).unwrap();
+    let late = members.get(1).unwrap();
+
+    client.contribute(&creator, &group_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + 1;
+    });
+    client.contribute(&late, &group_id);
+
+    client.cancel_group(&creator, &group_id);
+
+    let record = client.get_refund_record(&group_id, &late);
+    assert_eq!(record.penalties_paid, CONTRIBUTION / 10);
+    assert_eq!(record.amount, CONTRIBUTION + CONTRIBUTION / 10);
+    assert_eq!(
+        client.get_refund_record(&group_id, &creator).amount,
+        CONTRIBUTION
+    );
+}
+
+#[test]
+fn test_vote_refund_settles_net_positions() {
+    let (env, client, _admin, members, group_id) = setup_test_env(3);
+
+    // One pot paid out, then a second cycle paid by everyone
+    finish_cycle(&env, &client, group_id);
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    end_cycle(&env);
+
+    let requester = members.get(1).unwrap();
+    client.request_refund(&requester, &group_id);
+    for member in members.iter() {
+        client.vote_refund(&member, &group_id, &VoteChoice::For);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += 7 * 86_400 + 1;
+    });
+    client.execute_refund(&requester, &group_id);
+
+    // Three contributions are left after the first pot, shared by the two
+    // members who are each owed two contributions
+    assert_eq!(
+        client
+            .get_refund_record(&group_id, &members.get(0).unwrap())
+            .amount,
+        0
+    );
+    for member in members.slice(1..3).iter() {
+        assert_eq!(
+            client.get_refund_record(&group_id, &member).amount,
+            CONTRIBUTION * 3 / 2
+        );
+    }
+    assert_eq!(client.get_group_escrow(&group_id).available(), 0);
+}
+
+#[test]
+fn test_leftover_escrow_shared_when_nobody_is_owed() {
+    let (env, client, _admin, members, group_id) = setup_test_env(3);
+    let creator = members.get(0).unwrap();
+
+    // Tokens sit in the group's escrow that no member's position accounts for
+    let mut escrow = client.get_group_escrow(&group_id);
+    escrow.deposits += 7;
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(group_id), &escrow);
+    });
+
+    client.cancel_group(&creator, &group_id);
+
+    // Nobody paid anything, so it is split evenly with the remainder to the
+    // first member
+    assert_eq!(client.get_refund_record(&group_id, &creator).amount, 3);
+    for member in members.slice(1..3).iter() {
+        assert_eq!(client.get_refund_record(&group_id, &member).amount, 2);
+    }
+    assert_eq!(client.get_group_escrow(&group_id).available(), 0);
+}
@@ -0,0 +1,184 @@
+#![cfg(test)]
+
+//! Share weight tests
+//!
+//! These tests verify that members of a `variable_shares` group can choose a
+//! share weight, that their obligations scale with it, and that payouts and
+//! refunds follow the weighted contributions.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 10;
+
+/// Helper function to create a test environment and a forming group that
+/// three funded members have joined
+fn setup_test_env(
+    variable_shares: bool,
+) -> (Env, AjoContractClient<'static>, Address, Vec<Address>, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..3 {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &5u32,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            variable_shares,
+            ..Default::default()
+        },
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_id, members, group_id)
+}
+
+#[test]
+fn test_obligations_scale_with_share_weight() {
+    let (env, client, token_id, members, group_id) = setup_test_env(true);
+    let token_client = token::Client::new(&env, &token_id);
+    let (creator, double, half) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    client.set_share_weight(&double, &group_id, &200u32);
+    client.set_share_weight(&half, &group_id, &50u32);
+    assert_eq!(client.get_share_weight(&group_id, &double), 200);
+    assert_eq!(client.get_share_weight(&group_id, &creator), 100);
+    client.start_group(&creator, &group_id);
+
+    for member in members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    assert_eq!(token_client.balance(&double), FUNDING - CONTRIBUTION * 2);
+    assert_eq!(token_client.balance(&half), FUNDING - CONTRIBUTION / 2);
+    assert_eq!(
+        client.get_amount_paid(&group_id, &1u32, &half),
+        CONTRIBUTION / 2
+    );
+
+    // The pot is the sum of the weighted contributions
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+    assert_eq!(
+        client.get_payout_record(&group_id, &1u32).amount,
+        CONTRIBUTION * 7 / 2
+    );
+}
+
+#[test]
+fn test_late_penalty_scales_with_share_weight() {
+    let (env, client, token_id, members, group_id) = setup_test_env(true);
+    let (creator, double) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.set_share_weight(&double, &group_id, &200u32);
+    client.start_group(&creator, &group_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + 3600;
+    });
+
+    client.contribute(&double, &group_id);
+    let penalty = CONTRIBUTION * 2 / 10;
+    assert_eq!(client.get_cycle_penalty_pool(&group_id, &1u32), penalty);
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&double),
+        FUNDING - CONTRIBUTION * 2 - penalty
+    );
+}
+
+#[test]
+fn test_cancel_refunds_weighted_contributions() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(true);
+    let (creator, double, half) = (
+        members.get(0).unwrap(),
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+    );
+
+    client.set_share_weight(&double, &group_id, &200u32);
+    client.set_share_weight(&half, &group_id, &50u32);
+    client.start_group(&creator, &group_id);
+    client.contribute(&double, &group_id);
+    client.contribute(&half, &group_id);
+    client.cancel_group(&creator, &group_id);
+
+    assert_eq!(
+        client.get_refund_record(&group_id, &double).amount,
+        CONTRIBUTION * 2
+    );
+    assert_eq!(
+        client.get_refund_record(&group_id, &half).amount,
+        CONTRIBUTION / 2
+    );
+}
+
+#[test]
+fn test_share_weight_locked_once_started() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(true);
+    let (creator, member) = (members.get(0).unwrap(), members.get(1).unwrap());
+
+    client.start_group(&creator, &group_id);
+    assert_eq!(
+        client.try_set_share_weight(&member, &group_id, &200u32),
+        Err(Ok(AjoError::InvalidGroupState))
+    );
+}
+
+#[test]
+fn test_share_weight_requires_variable_shares() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(false);
+
+    assert_eq!(
+        client.try_set_share_weight(&members.get(1).unwrap(), &group_id, &200u32),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_share_weight_range() {
+    let (_env, client, _token_id, members, group_id) = setup_test_env(true);
+    let member = members.get(1).unwrap();
+
+    assert_eq!(
+        client.try_set_share_weight(&member, &group_id, &0u32),
+        Err(Ok(AjoError::InvalidShareWeight))
+    );
+    assert_eq!(
+        client.try_set_share_weight(&member, &group_id, &1_001u32),
+        Err(Ok(AjoError::InvalidShareWeight))
+    );
+    client.set_share_weight(&member, &group_id, &1_000u32);
+}
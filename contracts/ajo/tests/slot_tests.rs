@@ -0,0 +1,296 @@
+#![cfg(test)]
+
+//! Multiple slot tests
+//!
+//! These tests verify that a member can hold several payout slots ("hands"):
+//! taking extra slots with `join_group`, paying a contribution per slot and
+//! receiving a payout for each slot.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, GroupSettings, JoinPolicy, PayoutStrategy,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 10;
+
+/// Helper function to create a test environment, a token and two funded members
+fn setup_test_env() -> (Env, AjoContractClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    token_admin_client.mint(&creator, &FUNDING);
+    token_admin_client.mint(&member, &FUNDING);
+
+    (env, client, token_id, creator, member)
+}
+
+/// Creates a group of `max_members` slots allowing `max_slots` per member.
+fn create_group(
+    client: &AjoContractClient,
+    token_id: &Address,
+    creator: &Address,
+    max_members: u32,
+    max_slots: u32,
+    payout_strategy: PayoutStrategy,
+    join_policy: JoinPolicy,
+) -> u64 {
+    client.create_group(
+        creator,
+        token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &max_members,
+        &GRACE_PERIOD,
+        &10u32,
+        &payout_strategy,
+        &GroupSettings {
+            join_policy,
+            max_slots_per_member: max_slots,
+            ..Default::default()
+        },
+    )
+}
+
+fn finish_cycle(env: &Env, client: &AjoContractClient, group_id: u64) {
+    for member in client.get_group(&group_id).members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+}
+
+#[test]
+fn test_member_takes_a_second_slot() {
+    let (env, client, token_id, creator, member) = setup_test_env();
+    let group_id = create_group(
+        &client,
+        &token_id,
+        &creator,
+        3,
+        2,
+        PayoutStrategy::Fixed,
+        JoinPolicy::Open,
+    );
+
+    client.join_group(&member, &group_id);
+    client.join_group(&member, &group_id);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.members, vec![&env, creator.clone(), member.clone()]);
+    assert_eq!(
+        group.payout_order,
+        vec![&env, creator.clone(), member.clone(), member.clone()]
+    );
+    assert_eq!(client.get_member_slots(&group_id, &member), 2);
+    assert_eq!(client.get_member_slots(&group_id, &creator), 1);
+
+    // Three slots fill the group, so it has started
+    assert!(client.get_group_status(&group_id).is_cycle_active);
+}
+
+#[test]
+fn test_contribution_and_payouts_scale_with_slots() {
+    let (env, client, token_id, creator, member) = setup_test_env();
+    let token_client = token::Client::new(&env, &token_id);
+    let group_id = create_group(
+        &client,
+        &token_id,
+        &creator,
+        3,
+        2,
+        PayoutStrategy::Fixed,
+        JoinPolicy::Open,
+    );
+    client.join_group(&member, &group_id);
+    client.join_group(&member, &group_id);
+
+    client.contribute(&member, &group_id);
+    assert_eq!(token_client.balance(&member), FUNDING - CONTRIBUTION * 2);
+    assert_eq!(
+        client
+            .get_contribution_detail(&group_id, &1u32, &member)
+            .amount,
+        CONTRIBUTION * 2
+    );
+
+    client.contribute(&creator, &group_id);
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+    assert_eq!(
+        client.get_payout_record(&group_id, &1u32).amount,
+        CONTRIBUTION * 3
+    );
+
+    // The member is paid once for each slot, and the rotation lasts three cycles
+    finish_cycle(&env, &client, group_id);
+    finish_cycle(&env, &client, group_id);
+    assert!(client.is_complete(&group_id));
+    assert_eq!(client.get_payout_record(&group_id, &2u32).member, member);
+    assert_eq!(client.get_payout_record(&group_id, &3u32).member, member);
+    assert_eq!(
+        client.get_claimable_balance(&group_id, &member),
+        CONTRIBUTION * 6
+    );
+}
+
+#[test]
+fn test_slot_limit_enforced() {
+    let (_env, client, token_id, creator, member) = setup_test_env();
+
+    let single = create_group(
+        &client,
+        &token_id,
+        &creator,
+        5,
+        0,
+        PayoutStrategy::Fixed,
+        JoinPolicy::Open,
+    );
+    client.join_group(&member, &single);
+    assert_eq!(
+        client.try_join_group(&member, &single),
+        Err(Ok(AjoError::AlreadyMember))
+    );
+
+    let double = create_group(
+        &client,
+        &token_id,
+        &creator,
+        5,
+        2,
+        PayoutStrategy::Fixed,
+        JoinPolicy::Open,
+    );
+    client.join_group(&member, &double);
+    client.join_group(&member, &double);
+    assert_eq!(
+        client.try_join_group(&member, &double),
+        Err(Ok(AjoError::AlreadyMember))
+    );
+}
+
+#[test]
+fn test_extra_slot_rejected_after_prepaying() {
+    let (_env, client, token_id, creator, member) = setup_test_env();
+    let group_id = create_group(
+        &client,
+        &token_id,
+        &creator,
+        4,
+        2,
+        PayoutStrategy::Fixed,
+        JoinPolicy::Open,
+    );
+
+    client.join_group(&member, &group_id);
+    client.contribute_ahead(&member, &group_id, &1);
+
+    assert_eq!(
+        client.try_join_group(&member, &group_id),
+        Err(Ok(AjoError::AlreadyContributed))
+    );
+    assert_eq!(client.get_member_slots(&group_id, &member), 1);
+}
+
+#[test]
+fn test_extra_slot_needs_no_invite() {
+    let (_env, client, token_id, creator, member) = setup_test_env();
+    let group_id = create_group(
+        &client,
+        &token_id,
+        &creator,
+        4,
+        2,
+        PayoutStrategy::Fixed,
+        JoinPolicy::InviteOnly,
+    );
+
+    client.invite_member(&creator, &group_id, &member);
+    client.join_group(&member, &group_id);
+    client.join_group(&member, &group_id);
+
+    assert_eq!(client.get_member_slots(&group_id, &member), 2);
+}
+
+#[test]
+fn test_collateral_locked_per_slot() {
+    let (env, client, token_id, creator, member) = setup_test_env();
+    let collateral = CONTRIBUTION / 2;
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &4u32,
+        &GRACE_PERIOD,
+        &10u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings {
+            collateral_amount: collateral,
+            max_slots_per_member: 2,
+            ..Default::default()
+        },
+    );
+
+    client.join_group(&member, &group_id);
+    client.join_group(&member, &group_id);
+
+    assert_eq!(
+        client.get_member_collateral(&group_id, &member),
+        collateral * 2
+    );
+    assert_eq!(
+        token::Client::new(&env, &token_id).balance(&member),
+        FUNDING - collateral * 2
+    );
+}
+
+#[test]
+fn test_payout_order_must_keep_every_slot() {
+    let (env, client, token_id, creator, member) = setup_test_env();
+    let group_id = create_group(
+        &client,
+        &token_id,
+        &creator,
+        4,
+        2,
+        PayoutStrategy::NeedBased,
+        JoinPolicy::Open,
+    );
+    client.join_group(&member, &group_id);
+    client.join_group(&member, &group_id);
+
+    assert_eq!(
+        client.try_set_payout_order(
+            &creator,
+            &group_id,
+            &vec![&env, member.clone(), creator.clone(), creator.clone()],
+        ),
+        Err(Ok(AjoError::InvalidPayoutOrder))
+    );
+
+    let order = vec![&env, member.clone(), creator.clone(), member.clone()];
+    client.set_payout_order(&creator, &group_id, &order);
+    assert_eq!(client.get_group(&group_id).payout_order, order);
+}
@@ -0,0 +1,177 @@
+#![cfg(test)]
+
+//! Slot transfer tests
+//!
+//! These tests verify that `transfer_slot` hands a member's position, payout
+//! status and history to a new address.
+
+use soroban_ajo::{AjoContract, AjoContractClient, AjoError, GroupSettings, PayoutStrategy};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment, a fixed-order group and funded members
+fn setup_test_env(
+    member_count: u32,
+) -> (
+    Env,
+    AjoContractClient<'static>,
+    token::StellarAssetClient<'static>,
+    Vec<Address>,
+    u64,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let group_id = client.create_group(
+        &members.get(0).unwrap(),
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &member_count,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &group_id);
+    }
+
+    (env, client, token_admin_client, members, group_id)
+}
+
+/// Has every current member contribute, waits out the grace period and pays out.
+fn run_cycle(env: &Env, client: &AjoContractClient, group_id: u64) {
+    for member in client.get_group(&group_id).members.iter() {
+        client.contribute(&member, &group_id);
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+    });
+    client.execute_payout(&group_id);
+}
+
+#[test]
+fn test_transfer_unpaid_slot_keeps_payout_position() {
+    let (env, client, token_admin_client, members, group_id) = setup_test_env(3);
+    let from = members.get(1).unwrap();
+    let to = Address::generate(&env);
+    token_admin_client.mint(&to, &FUNDING);
+
+    client.transfer_slot(&from, &to, &group_id);
+
+    let group = client.get_group(&group_id);
+    assert_eq!(group.members.get(1).unwrap(), to);
+    assert_eq!(group.payout_order.get(1).unwrap(), to);
+    assert!(!client.is_member(&group_id, &from));
+
+    // The new holder receives the second payout
+    run_cycle(&env, &client, group_id);
+    run_cycle(&env, &client, group_id);
+    assert_eq!(client.get_payout_record(&group_id, &2u32).member, to);
+}
+
+#[test]
+fn test_transfer_paid_slot_moves_payout_status() {
+    let (env, client, token_admin_client, members, group_id) = setup_test_env(3);
+    let from = members.get(1).unwrap();
+    let to = Address::generate(&env);
+    token_admin_client.mint(&to, &FUNDING);
+
+    run_cycle(&env, &client, group_id);
+    run_cycle(&env, &client, group_id);
+
+    client.transfer_slot(&from, &to, &group_id);
+
+    // The new holder inherits the completed payout and can't be paid again
+    assert_eq!(
+        client.try_request_exit(&to, &group_id),
+        Err(Ok(AjoError::AlreadyReceivedPayout))
+    );
+
+    // ...and keeps contributing until the rotation ends
+    run_cycle(&env, &client, group_id);
+    assert_eq!(
+        client.get_payout_record(&group_id, &3u32).member,
+        members.get(2).unwrap()
+    );
+    assert!(client.is_complete(&group_id));
+}
+
+#[test]
+fn test_transfer_moves_contribution_history() {
+    let (env, client, _token_admin_client, members, group_id) = setup_test_env(3);
+    let from = members.get(2).unwrap();
+    let to = Address::generate(&env);
+
+    // A late contribution builds up penalty history
+    env.ledger().with_mut(|li| {
+        li.timestamp += CYCLE_DURATION + 3600;
+    });
+    client.contribute(&from, &group_id);
+
+    client.transfer_slot(&from, &to, &group_id);
+
+    // The current cycle's contribution counts for the new holder
+    let status = client.get_contribution_status(&group_id, &1u32);
+    assert!(status.contains(&(to.clone(), true)));
+
+    let detail = client.get_contribution_detail(&group_id, &1u32, &to);
+    assert_eq!(detail.member, to);
+    assert!(detail.is_late);
+
+    let record = client.get_member_penalty_record(&group_id, &to);
+    assert_eq!(record.member, to);
+    assert_eq!(record.late_count, 1);
+
+    assert_eq!(
+        client.try_contribute(&to, &group_id),
+        Err(Ok(AjoError::AlreadyContributed))
+    );
+}
+
+#[test]
+fn test_transfer_slot_validation() {
+    let (env, client, _token_admin_client, members, group_id) = setup_test_env(3);
+    let outsider = Address::generate(&env);
+
+    assert_eq!(
+        client.try_transfer_slot(&outsider, &Address::generate(&env), &group_id),
+        Err(Ok(AjoError::NotMember))
+    );
+    assert_eq!(
+        client.try_transfer_slot(
+            &members.get(1).unwrap(),
+            &members.get(2).unwrap(),
+            &group_id
+        ),
+        Err(Ok(AjoError::AlreadyMember))
+    );
+    assert_eq!(
+        client.try_transfer_slot(&members.get(0).unwrap(), &outsider, &group_id),
+        Err(Ok(AjoError::Unauthorized))
+    );
+}
@@ -0,0 +1,251 @@
+#![cfg(test)]
+
+//! Storage TTL tests
+//!
+//! These tests verify that group entries are kept alive for the group's
+//! remaining lifetime and that `bump_group` re-extends them on demand.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, DataKey, GroupSettings, PayoutStrategy,
+};
+use soroban_sdk::{
+    testutils::{storage::Instance as _, storage::Persistent as _, Address as _, Ledger},
+    token, Address, Env,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+
+/// Helper function to create a test environment, contract and funded token
+fn setup_test_env() -> (Env, AjoContractClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+
+    let creator = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_id).mint(&creator, &(CONTRIBUTION * 10));
+
+    (env, client, contract_id, token_id, creator)
+}
+
+/// Expected TTL in ledgers for a fresh group with the given member cap.
+fn expected_ttl(max_members: u32) -> u32 {
+    let remaining_secs = max_members as u64 * (CYCLE_DURATION + GRACE_PERIOD) + 604_800;
+    (remaining_secs / 5) as u32 + 30 * 17_280
+}
+
+/// Adds a funded second member and starts the group so contributions open.
+fn start_with_second_member(
+    env: &Env,
+    client: &AjoContractClient,
+    token_id: &Address,
+    creator: &Address,
+    group_id: u64,
+) {
+    let member = Address::generate(env);
+    token::StellarAssetClient::new(env, token_id).mint(&member, &(CONTRIBUTION * 10));
+    client.join_group(&member, &group_id);
+    client.start_group(creator, &group_id);
+}
+
+fn group_ttl(env: &Env, contract_id: &Address, group_id: u64) -> u32 {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::Group(group_id))
+    })
+}
+
+#[test]
+fn test_group_ttl_covers_remaining_cycles() {
+    let (env, client, contract_id, token_id, creator) = setup_test_env();
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &10u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+
+    assert_eq!(group_ttl(&env, &contract_id, group_id), expected_ttl(10));
+}
+
+#[test]
+fn test_contribution_entry_ttl_extended() {
+    let (env, client, contract_id, token_id, creator) = setup_test_env();
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &10u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    start_with_second_member(&env, &client, &token_id, &creator, group_id);
+    client.contribute(&creator, &group_id);
+
+    let ttl = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::Contribution(group_id, 1u32, creator.clone()))
+    });
+    assert_eq!(ttl, expected_ttl(10));
+}
+
+#[test]
+fn test_group_ttl_capped_at_max() {
+    let (env, client, contract_id, token_id, creator) = setup_test_env();
+
+    // 100 yearly cycles is far beyond the network's maximum TTL
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &31_536_000u64,
+        &100u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+
+    let max_ttl = env.as_contract(&contract_id, || env.storage().max_ttl());
+    assert_eq!(group_ttl(&env, &contract_id, group_id), max_ttl);
+}
+
+#[test]
+fn test_bump_group_restores_ttl() {
+    let (env, client, contract_id, token_id, creator) = setup_test_env();
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &10u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    start_with_second_member(&env, &client, &token_id, &creator, group_id);
+    client.contribute(&creator, &group_id);
+
+    // Let a large number of ledgers pass without touching the group
+    let elapsed = 200_000u32;
+    env.ledger().with_mut(|li| {
+        li.sequence_number += elapsed;
+    });
+    assert_eq!(
+        group_ttl(&env, &contract_id, group_id),
+        expected_ttl(10) - elapsed
+    );
+
+    assert_eq!(client.bump_group(&group_id, &0), None);
+
+    assert_eq!(group_ttl(&env, &contract_id, group_id), expected_ttl(10));
+    let contrib_ttl = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::Contribution(group_id, 1u32, creator.clone()))
+    });
+    assert_eq!(contrib_ttl, expected_ttl(10));
+}
+
+#[test]
+fn test_bump_group_in_batches() {
+    let (env, client, contract_id, token_id, creator) = setup_test_env();
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &10u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+    let mut last = creator.clone();
+    for _ in 1..10 {
+        last = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token_id).mint(&last, &(CONTRIBUTION * 10));
+        client.join_group(&last, &group_id);
+    }
+    client.contribute(&last, &group_id);
+
+    let elapsed = 200_000u32;
+    env.ledger().with_mut(|li| {
+        li.sequence_number += elapsed;
+    });
+    let contrib_ttl = || {
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::Contribution(group_id, 1u32, last.clone()))
+        })
+    };
+
+    // 10 members' own entries fill the first batch; their entries for the
+    // current cycle come in the second
+    assert_eq!(client.bump_group(&group_id, &0), Some(10));
+    assert_eq!(group_ttl(&env, &contract_id, group_id), expected_ttl(10));
+    assert_eq!(contrib_ttl(), expected_ttl(10) - elapsed);
+
+    assert_eq!(client.bump_group(&group_id, &10), None);
+    assert_eq!(contrib_ttl(), expected_ttl(10));
+}
+
+#[test]
+fn test_bump_group_extends_instance() {
+    let (env, client, contract_id, token_id, creator) = setup_test_env();
+
+    let group_id = client.create_group(
+        &creator,
+        &token_id,
+        &CONTRIBUTION,
+        &CYCLE_DURATION,
+        &10u32,
+        &GRACE_PERIOD,
+        &5u32,
+        &PayoutStrategy::Fixed,
+        &GroupSettings::default(),
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number += 2 * 17_280;
+    });
+    client.bump_group(&group_id, &0);
+
+    let instance_ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+    assert_eq!(instance_ttl, 30 * 17_280);
+}
+
+#[test]
+fn test_bump_nonexistent_group_fails() {
+    let (_env, client, _, _, _) = setup_test_env();
+
+    let result = client.try_bump_group(&99u64, &0);
+    assert_eq!(result, Err(Ok(AjoError::GroupNotFound)));
+}
@@ -0,0 +1,222 @@
+#![cfg(test)]
+
+//! Template and cloning tests
+//!
+//! These tests verify saving group parameters as a template, creating groups
+//! from a template, and cloning a completed group with its members invited.
+
+use soroban_ajo::{
+    AjoContract, AjoContractClient, AjoError, GroupParams, GroupSettings, JoinPolicy,
+    PayoutStrategy,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+const CONTRIBUTION: i128 = 100_000_000;
+const CYCLE_DURATION: u64 = 604_800;
+const GRACE_PERIOD: u64 = 86_400;
+const FUNDING: i128 = CONTRIBUTION * 20;
+
+/// Helper function to create a test environment with `member_count` funded
+/// addresses and invite-only group parameters
+fn setup_test_env(
+    member_count: u32,
+) -> (Env, AjoContractClient<'static>, GroupParams, Vec<Address>) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AjoContract);
+    let client = AjoContractClient::new(&env, &contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
+
+    let mut members = Vec::new(&env);
+    for _ in 0..member_count {
+        let member = Address::generate(&env);
+        token_admin_client.mint(&member, &FUNDING);
+        members.push_back(member);
+    }
+
+    let params = GroupParams {
+        token_address: token_id,
+        contribution_amount: CONTRIBUTION,
+        cycle_duration: CYCLE_DURATION,
+        max_members: member_count,
+        grace_period: GRACE_PERIOD,
+        penalty_rate: 10,
+        payout_strategy: PayoutStrategy::Fixed,
+        settings: GroupSettings {
+            join_policy: JoinPolicy::InviteOnly,
+            ..Default::default()
+        },
+    };
+
+    (env, client, params, members)
+}
+
+/// Creates a group with `params`, fills it and runs it to completion.
+fn run_group(
+    env: &Env,
+    client: &AjoContractClient,
+    params: &GroupParams,
+    members: &Vec<Address>,
+) -> u64 {
+    let creator = members.get(0).unwrap();
+    let group_id = client.create_group(
+        &creator,
+        &params.token_address,
+        &params.contribution_amount,
+        &params.cycle_duration,
+        &params.max_members,
+        &params.grace_period,
+        &params.penalty_rate,
+        &params.payout_strategy,
+        &params.settings,
+    );
+    for member in members.iter().skip(1) {
+        client.invite_member(&creator, &group_id, &member);
+        client.join_group(&member, &group_id);
+    }
+
+    for _ in 0..members.len() {
+        for member in members.iter() {
+            client.contribute(&member, &group_id);
+        }
+        env.ledger().with_mut(|li| {
+            li.timestamp += CYCLE_DURATION + GRACE_PERIOD + 1;
+        });
+        client.execute_payout(&group_id);
+    }
+
+    group_id
+}
+
+#[test]
+fn test_create_template_stores_params() {
+    let (_env, client, params, members) = setup_test_env(3);
+    let owner = members.get(0).unwrap();
+
+    let template_id = client.create_template(&owner, &params);
+    assert_eq!(template_id, 1);
+
+    let template = client.get_template(&template_id);
+    assert_eq!(template.id, template_id);
+    assert_eq!(template.owner, owner);
+    assert_eq!(template.params, params);
+
+    assert_eq!(client.create_template(&owner, &params), 2);
+}
+
+#[test]
+fn test_create_template_validates_params() {
+    let (_env, client, mut params, members) = setup_test_env(3);
+    params.contribution_amount = 0;
+
+    let result = client.try_create_template(&members.get(0).unwrap(), &params);
+    assert_eq!(result, Err(Ok(AjoError::ContributionAmountZero)));
+}
+
+#[test]
+fn test_get_missing_template_fails() {
+    let (_env, client, _params, _members) = setup_test_env(3);
+
+    let result = client.try_get_template(&1);
+    assert_eq!(result, Err(Ok(AjoError::RecordNotFound)));
+}
+
+#[test]
+fn test_create_group_from_template() {
+    let (_env, client, params, members) = setup_test_env(3);
+    let template_id = client.create_template(&members.get(0).unwrap(), &params);
+
+    // Anyone may use the template, and each use creates a separate group
+    let creator = members.get(1).unwrap();
+    let first = client.create_group_from_template(&creator, &template_id);
+    let second = client.create_group_from_template(&creator, &template_id);
+    assert_ne!(first, second);
+
+    let group = client.get_group(&first);
+    assert_eq!(group.creator, creator);
+    assert_eq!(group.members.len(), 1);
+    assert_eq!(group.token_address, params.token_address);
+    assert_eq!(group.contribution_amount, params.contribution_amount);
+    assert_eq!(group.cycle_duration, params.cycle_duration);
+    assert_eq!(group.max_members, params.max_members);
+    assert_eq!(group.grace_period, params.grace_period);
+    assert_eq!(group.penalty_rate, params.penalty_rate);
+    assert_eq!(group.settings, params.settings);
+
+    let result = client.try_create_group_from_template(&creator, &99);
+    assert_eq!(result, Err(Ok(AjoError::RecordNotFound)));
+}
+
+#[test]
+fn test_clone_group_invites_previous_members() {
+    let (env, client, params, members) = setup_test_env(3);
+    let creator = members.get(0).unwrap();
+    let group_id = run_group(&env, &client, &params, &members);
+
+    let clone_id = client.clone_group(&creator, &group_id, &true);
+    let clone = client.get_group(&clone_id);
+    let source = client.get_group(&group_id);
+    assert_eq!(clone.creator, creator);
+    assert_eq!(clone.members.len(), 1);
+    assert_eq!(clone.contribution_amount, source.contribution_amount);
+    assert_eq!(clone.settings, source.settings);
+    assert!(!clone.is_complete);
+
+    let invites = client.get_invites(&clone_id);
+    assert_eq!(invites.len(), 2);
+    assert!(!invites.contains(&creator));
+
+    // Invited members can join the invite-only clone directly
+    for member in members.iter().skip(1) {
+        client.join_group(&member, &clone_id);
+    }
+    assert_eq!(client.get_group(&clone_id).members.len(), 3);
+}
+
+#[test]
+fn test_clone_group_without_invites() {
+    let (env, client, params, members) = setup_test_env(2);
+    let group_id = run_group(&env, &client, &params, &members);
+
+    let clone_id = client.clone_group(&members.get(0).unwrap(), &group_id, &false);
+
+    assert!(client.get_invites(&clone_id).is_empty());
+    let result = client.try_join_group(&members.get(1).unwrap(), &clone_id);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_clone_group_requires_completed_group_and_creator() {
+    let (env, client, params, members) = setup_test_env(2);
+    let creator = members.get(0).unwrap();
+
+    let forming = client.create_group(
+        &creator,
+        &params.token_address,
+        &params.contribution_amount,
+        &params.cycle_duration,
+        &params.max_members,
+        &params.grace_period,
+        &params.penalty_rate,
+        &params.payout_strategy,
+        &params.settings,
+    );
+    let result = client.try_clone_group(&creator, &forming, &true);
+    assert_eq!(result, Err(Ok(AjoError::InvalidGroupState)));
+
+    let group_id = run_group(&env, &client, &params, &members);
+    let result = client.try_clone_group(&members.get(1).unwrap(), &group_id, &true);
+    assert_eq!(result, Err(Ok(AjoError::Unauthorized)));
+
+    let result = client.try_clone_group(&creator, &99, &true);
+    assert_eq!(result, Err(Ok(AjoError::GroupNotFound)));
+}